@@ -1,4 +1,6 @@
+use std::io::IsTerminal;
 use clap::{Parser, Subcommand};
+use vlitz_core::OutputFormat;
 
 #[derive(Parser)]
 #[clap(name = "vlitz", about = "Frida CLI Debugger", version)]
@@ -43,6 +45,22 @@ pub struct Cli {
     #[clap(short = 'l', long = "load")]
     pub load_script: Option<String>,
 
+    /// 스크립트 명령어가 실패해도 계속 실행
+    #[clap(long = "continue-on-error", requires = "load_script")]
+    pub continue_on_error: bool,
+
+    /// 명령어 결과 출력 형식 (plain, table, json, csv)
+    #[clap(short = 'o', long = "output", default_value = "plain", value_parser = parse_output)]
+    pub output: OutputFormat,
+
+    /// JSON으로 출력 (`--output json`과 같음)
+    #[clap(long = "json")]
+    pub json: bool,
+
+    /// 실행할 명령어 (여러 번 지정 가능, 실행 후 종료)
+    #[clap(short = 'e', long = "exec")]
+    pub exec: Vec<String>,
+
     /// 서브 명령어
     #[clap(subcommand)]
     pub command: Option<CliCommand>,
@@ -102,8 +120,23 @@ impl Cli {
         None
     }
 
+    /// 명령어 결과 출력 형식 (`--json`이 우선)
+    pub fn output_format(&self) -> OutputFormat {
+        if self.json { OutputFormat::Json } else { self.output }
+    }
+
+    /// 배치 모드 여부 (`-e`로 명령어를 주었거나 표준 입력이 터미널이 아님)
+    pub fn is_batch(&self) -> bool {
+        !self.exec.is_empty() || !std::io::stdin().is_terminal()
+    }
+
     /// 명령행 인자 처리
     pub fn is_command(&self) -> bool {
         self.command.is_some()
     }
-} 
\ No newline at end of file
+} 
+
+/// `--output` 값 파싱
+fn parse_output(value: &str) -> Result<OutputFormat, String> {
+    value.parse().map_err(|e: vlitz_shared::VlitzError| e.to_string())
+}
@@ -0,0 +1,418 @@
+use rustyline::completion::{Completer, FilenameCompleter, Pair};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Helper, Result};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use vlitz_core::{CommandExecutor, CommandRegistry, CommandSpec, DataStore};
+use vlitz_runtime::EventCategory;
+use vlitz_shared::{MemoryType, FILTER_FIELDS};
+use colored::*;
+
+/// 세션에서 가져온 이름을 다시 쓰는 시간
+const CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// 인자 자리에 그대로 입력하는 키워드
+const KEYWORDS: &[&str] = &["all", "thread", "below", "above", "every"];
+
+/// REPL 자동완성과 인자 힌트
+///
+/// 명령어 이름, 하위 명령어, 인자 형식은 실행기의 명령어 등록부를 따르고,
+/// 라이브러리 항목, 모듈/익스포트, 프리셋 이름은 현재 세션 상태에서 가져온다.
+pub struct ReplHelper {
+    executor: Rc<RefCell<CommandExecutor>>,
+    files: FilenameCompleter,
+    /// 모듈 이름 (가져온 시각, 이름)
+    modules: RefCell<Option<(Instant, Vec<String>)>>,
+    /// 모듈별 익스포트 이름
+    exports: RefCell<HashMap<String, (Instant, Vec<String>)>>,
+}
+
+/// 커서 앞 입력 (완성된 토큰, 입력 중인 단어와 시작 위치)
+struct Input<'l> {
+    /// 마지막 파이프(`|`) 뒤의 토큰
+    tokens: Vec<&'l str>,
+    word: &'l str,
+    start: usize,
+    /// 리디렉션(`>`, `>>`) 파일 이름을 입력하는 중
+    redirect: bool,
+}
+
+impl<'l> Input<'l> {
+    fn split(line: &'l str, pos: usize) -> Self {
+        let head = &line[..pos];
+        let start = head.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let mut tokens: Vec<&str> = head[..start].split_whitespace().collect();
+        if let Some(pipe) = tokens.iter().rposition(|token| *token == "|") {
+            tokens.drain(..=pipe);
+        }
+        let redirect = tokens.last().is_some_and(|token| *token == ">" || *token == ">>");
+        Self { tokens, word: &head[start..], start, redirect }
+    }
+}
+
+/// 입력 중인 단어가 채울 자리
+enum Target<'r, 'l> {
+    Command,
+    Subcommand(&'r str),
+    Argument { spec: &'r CommandSpec, args: Vec<&'l str> },
+}
+
+impl<'r, 'l> Target<'r, 'l> {
+    fn resolve(registry: &'r CommandRegistry, tokens: &[&'l str]) -> Option<Self> {
+        let (spec, rest) = match tokens {
+            [] => return Some(Target::Command),
+            [name] => match registry.find_group(name) {
+                Some(group) => return Some(Target::Subcommand(&group.name)),
+                None => (registry.find(name, None)?, &tokens[1..]),
+            },
+            [name, subcommand, ..] => match registry.find_group(name) {
+                Some(_) => (registry.find(name, Some(subcommand))?, &tokens[2..]),
+                None => (registry.find(name, None)?, &tokens[1..]),
+            },
+        };
+        let flags = spec.flags();
+        let args = rest.iter().copied().filter(|arg| !flags.contains(arg)).collect();
+        Some(Target::Argument { spec, args })
+    }
+}
+
+impl ReplHelper {
+    pub fn new(executor: Rc<RefCell<CommandExecutor>>) -> Self {
+        Self {
+            executor,
+            files: FilenameCompleter::new(),
+            modules: RefCell::new(None),
+            exports: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// 커서 위치의 자동완성 후보 (바꿀 시작 위치, 후보)
+    pub fn candidates(&self, line: &str, pos: usize) -> (usize, Vec<Pair>) {
+        let input = Input::split(line, pos);
+        if input.redirect {
+            return self.files.complete_path(line, pos).unwrap_or((pos, Vec::new()));
+        }
+        let Ok(executor) = self.executor.try_borrow() else {
+            return (pos, Vec::new());
+        };
+        let registry = executor.registry();
+        let candidates = match Target::resolve(registry, &input.tokens) {
+            Some(Target::Command) => literals(command_names(registry), input.word),
+            Some(Target::Subcommand(group)) => literals(subcommand_names(registry, group), input.word),
+            Some(Target::Argument { spec, args }) => {
+                if input.word.starts_with('-') {
+                    literals(spec.flags(), input.word)
+                } else {
+                    match spec.arg_slots().get(args.len()) {
+                        Some(&"file") => return self.files.complete_path(line, pos).unwrap_or((pos, Vec::new())),
+                        Some(slot) => self.slot_candidates(registry, slot, &args, input.word),
+                        None => Vec::new(),
+                    }
+                }
+            },
+            None => Vec::new(),
+        };
+        (input.start, candidates)
+    }
+
+    /// 커서 뒤에 흐리게 보여줄 힌트 (명령어 이름 나머지 또는 남은 인자 형식)
+    pub fn hint_text(&self, line: &str, pos: usize) -> Option<String> {
+        if pos < line.len() {
+            return None;
+        }
+        let input = Input::split(line, pos);
+        if input.redirect {
+            return None;
+        }
+        let executor = self.executor.try_borrow().ok()?;
+        let registry = executor.registry();
+        let (names, group) = match Target::resolve(registry, &input.tokens)? {
+            Target::Command => (command_names(registry), None),
+            Target::Subcommand(group) => (subcommand_names(registry, group), Some(group)),
+            Target::Argument { spec, args } => {
+                let filled = args.len() + usize::from(!input.word.is_empty());
+                let signature = remaining_signature(spec, filled);
+                if signature.is_empty() {
+                    return None;
+                }
+                let separator = if input.word.is_empty() { "" } else { " " };
+                return Some(format!("{}{}", separator, signature));
+            },
+        };
+
+        // 이름을 입력하는 중이면 하나로 좁혀졌을 때만 나머지와 인자 형식을 보여줌
+        if input.word.is_empty() {
+            return None;
+        }
+        let mut matches = names.iter().filter(|name| name.starts_with(input.word));
+        let name = matches.next()?;
+        if matches.next().is_some() {
+            return None;
+        }
+        let suffix = &name[input.word.len()..];
+        let signature = match (group, registry.find_group(name)) {
+            (Some(group), _) => registry.find(group, Some(name)).map(|spec| remaining_signature(spec, 0)),
+            (None, Some(found)) => Some(format!("<{}>", subcommand_names(registry, &found.name).join("|"))),
+            (None, None) => registry.find(name, None).map(|spec| remaining_signature(spec, 0)),
+        };
+        match signature {
+            Some(signature) if !signature.is_empty() => Some(format!("{} {}", suffix, signature)),
+            _ if suffix.is_empty() => None,
+            _ => Some(suffix.to_string()),
+        }
+    }
+
+    /// 인자 자리 이름에 맞는 후보 (`a|b`는 선택지마다)
+    fn slot_candidates(&self, registry: &CommandRegistry, slot: &str, args: &[&str], word: &str) -> Vec<Pair> {
+        let mut candidates = Vec::new();
+        for kind in slot.split('|') {
+            let found = match kind {
+                "selector" | "from" | "to" => self.selector_candidates(word, true),
+                "address" => self.selector_candidates(word, false),
+                "type" => literals(MemoryType::ALL.iter().map(|t| t.agent_name()), word),
+                "field" => literals(DataStore::SORT_FIELDS.iter().copied(), word),
+                "filter" => literals(FILTER_FIELDS.iter().copied(), word),
+                "category" => literals(EventCategory::ALL.iter().map(|c| c.name()), word),
+                "command" => literals(command_names(registry), word),
+                "subcommand" => match args.last().and_then(|name| registry.find_group(name)) {
+                    Some(group) => literals(subcommand_names(registry, &group.name), word),
+                    None => Vec::new(),
+                },
+                "module" => literals(self.module_names(), word),
+                "export" => match args.last() {
+                    Some(module) => literals(self.export_names(module), word),
+                    None => Vec::new(),
+                },
+                "preset" => literals(self.preset_names(), word),
+                "bookmark" => literals(self.bookmark_names(), word),
+                "option" | "option=value" => {
+                    let suffix = if kind == "option" { "" } else { "=" };
+                    let options = args.first().map(|preset| self.option_names(preset)).unwrap_or_default();
+                    literals(options.into_iter().map(|option| option + suffix), word)
+                },
+                keyword if KEYWORDS.contains(&keyword) => literals([keyword], word),
+                _ => Vec::new(),
+            };
+            candidates.extend(found);
+        }
+        candidates
+    }
+
+    /// 선택자 후보 (`lib:`/`log:` 접두사, 라이브러리 항목은 번호와 레이블)
+    fn selector_candidates(&self, word: &str, allow_all: bool) -> Vec<Pair> {
+        let Some(rest) = word.strip_prefix("lib:") else {
+            let prefixes = if allow_all { &["lib:", "log:", "all"][..] } else { &["lib:", "log:"][..] };
+            return literals(prefixes.iter().copied(), word);
+        };
+        let Ok(executor) = self.executor.try_borrow() else {
+            return Vec::new();
+        };
+
+        // 번호 또는 레이블 앞부분으로 찾기
+        executor.store().get_lib().iter()
+            .enumerate()
+            .filter(|(idx, data)| {
+                idx.to_string().starts_with(rest) || data.label.as_deref().is_some_and(|label| label.starts_with(rest))
+            })
+            .map(|(idx, data)| Pair {
+                display: format!("lib:{} ({})", idx, data.label.clone().unwrap_or_else(|| data.get_display_name())),
+                replacement: format!("lib:{}", idx),
+            })
+            .collect()
+    }
+
+    /// 북마크 후보 (`@name`)
+    fn bookmark_names(&self) -> Vec<String> {
+        match self.executor.try_borrow() {
+            Ok(executor) => executor.navigator().bookmarks().keys().map(|name| format!("@{}", name)).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn preset_names(&self) -> Vec<String> {
+        match self.executor.try_borrow() {
+            Ok(executor) => executor.presets().list().iter().map(|(preset, _)| preset.name().to_string()).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn option_names(&self, preset: &str) -> Vec<String> {
+        let Ok(executor) = self.executor.try_borrow() else {
+            return Vec::new();
+        };
+        executor.presets().get(preset)
+            .map(|preset| preset.options().into_iter().map(|option| option.name).collect())
+            .unwrap_or_default()
+    }
+
+    /// 대상 프로세스의 모듈 이름 (attach 전이거나 실패하면 빈 목록)
+    fn module_names(&self) -> Vec<String> {
+        if let Some((fetched, names)) = self.modules.borrow().as_ref() {
+            if fetched.elapsed() < CACHE_TTL {
+                return names.clone();
+            }
+        }
+        let Ok(executor) = self.executor.try_borrow() else {
+            return Vec::new();
+        };
+        let names: Vec<String> = match executor.agent().and_then(|agent| agent.module_infos(None)) {
+            Ok(modules) => modules.into_iter().map(|module| module.name).collect(),
+            Err(_) => return Vec::new(),
+        };
+        *self.modules.borrow_mut() = Some((Instant::now(), names.clone()));
+        names
+    }
+
+    /// 모듈의 익스포트 이름
+    fn export_names(&self, module: &str) -> Vec<String> {
+        if let Some((fetched, names)) = self.exports.borrow().get(module) {
+            if fetched.elapsed() < CACHE_TTL {
+                return names.clone();
+            }
+        }
+        let Ok(executor) = self.executor.try_borrow() else {
+            return Vec::new();
+        };
+        let names: Vec<String> = match executor.agent().and_then(|agent| agent.query_exports(module, None)) {
+            Ok(exports) => exports.iter().filter_map(|data| data.get_name().map(str::to_string)).collect(),
+            Err(_) => return Vec::new(),
+        };
+        self.exports.borrow_mut().insert(module.to_string(), (Instant::now(), names.clone()));
+        names
+    }
+}
+
+/// 첫 단어로 쓸 수 있는 이름 (정렬, 중복 제거)
+fn command_names(registry: &CommandRegistry) -> Vec<String> {
+    let names: BTreeSet<&str> = registry.top_level_names().into_iter().collect();
+    names.into_iter().map(str::to_string).collect()
+}
+
+/// 그룹의 하위 명령어 이름 (등록 순, 별칭 제외)
+fn subcommand_names(registry: &CommandRegistry, group: &str) -> Vec<String> {
+    registry.subcommands(group).map(|spec| spec.name().to_string()).collect()
+}
+
+/// 이미 채운 위치 인자 수 이후의 인자 형식 (플래그는 항상 남김)
+fn remaining_signature(spec: &CommandSpec, filled: usize) -> String {
+    let (flags, positional): (Vec<&str>, Vec<&str>) = spec.args.split_whitespace()
+        .partition(|token| token.trim_start_matches(['<', '[']).starts_with('-'));
+    positional.into_iter().skip(filled).chain(flags).collect::<Vec<_>>().join(" ")
+}
+
+/// 입력 중인 단어로 시작하는 후보
+fn literals<I, S>(names: I, word: &str) -> Vec<Pair>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    names.into_iter()
+        .map(Into::into)
+        .filter(|name| name.starts_with(word))
+        .map(|name| Pair { display: name.clone(), replacement: name })
+        .collect()
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Result<(usize, Vec<Pair>)> {
+        Ok(self.candidates(line, pos))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+
+    fn hint(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Option<String> {
+        self.hint_text(line, pos)
+    }
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        Cow::Owned(hint.dimmed().to_string())
+    }
+}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use vlitz_core::Command;
+    use vlitz_runtime::MockBackend;
+
+    fn helper() -> ReplHelper {
+        let mut executor = CommandExecutor::new(20);
+        executor.set_backend(Box::new(MockBackend::new()
+            .on_value("queryModules", json!({ "id": 1, "total": 2, "runtime": null }))
+            .on_value("readQuery", json!([
+                { "name": "libc.so", "base": "0x7f000000", "size": 4096 },
+                { "name": "libgame.so", "base": "0x7f100000", "size": 8192 },
+            ]))
+            .on_value("closeQuery", json!(true))));
+        ReplHelper::new(Rc::new(RefCell::new(executor)))
+    }
+
+    fn complete(helper: &ReplHelper, line: &str) -> Vec<String> {
+        helper.candidates(line, line.len()).1.into_iter().map(|pair| pair.replacement).collect()
+    }
+
+    #[test]
+    fn test_complete_commands_and_arguments() {
+        let helper = helper();
+        assert_eq!(complete(&helper, "pr"), vec!["preset", "prv"]);
+        assert_eq!(complete(&helper, "lib s"), vec!["save"]);
+        assert_eq!(complete(&helper, "mem read lib:0 f"), vec!["float"]);
+        assert_eq!(complete(&helper, "list exports libg"), vec!["libgame.so"]);
+        assert_eq!(complete(&helper, "preset apply speedhack f"), vec!["factor="]);
+        assert_eq!(complete(&helper, "preset revert a"), vec!["antiroot", "antifrida", "all"]);
+        assert_eq!(complete(&helper, "method lib:0 x --i"), vec!["--inherited"]);
+        assert_eq!(complete(&helper, "lib clear na"), vec!["name"]);
+        assert_eq!(helper.candidates("sav l", 5).0, 4);
+        // 파이프 뒤는 새 명령어
+        assert_eq!(complete(&helper, "list module | lib s"), vec!["save"]);
+        assert_eq!(complete(&helper, "lg | me"), vec!["mem", "meta", "method"]);
+    }
+
+    #[test]
+    fn test_complete_lib_labels() {
+        let helper = helper();
+        {
+            let mut executor = helper.executor.borrow_mut();
+            executor.execute(&Command::parse("list module").unwrap());
+            executor.execute(&Command::parse("sav log:0-1").unwrap());
+            executor.execute(&Command::parse("meta label lib:1 game").unwrap());
+            executor.execute(&Command::parse("sel lib:@game").unwrap());
+            executor.execute(&Command::parse("nav mark game_base").unwrap());
+        }
+        assert_eq!(complete(&helper, ": @g"), vec!["@game_base"]);
+        assert_eq!(complete(&helper, "sav ")[..4], ["lib:", "log:", "all", "type"]);
+        assert_eq!(complete(&helper, "r lib:g"), vec!["lib:1"]);
+        let (_, pairs) = helper.candidates("rm lib:", 7);
+        assert_eq!(pairs.iter().map(|pair| pair.display.as_str()).collect::<Vec<_>>(), vec!["lib:0 (libc.so @ 0x7f000000)", "lib:1 (game)"]);
+    }
+
+    #[test]
+    fn test_hints_show_signature() {
+        let helper = helper();
+        assert_eq!(helper.hint_text("mem read ", 9).as_deref(), Some("[address] [type]"));
+        assert_eq!(helper.hint_text("mem read 0x10", 13).as_deref(), Some(" [type]"));
+        assert_eq!(helper.hint_text("preset se", 9).as_deref(), Some("t <preset> <option> <value>"));
+        assert_eq!(helper.hint_text("sav", 3).as_deref(), Some(" <selector|filter>"));
+        assert_eq!(helper.hint_text("lib", 3).as_deref(), Some(" <list|save|move|remove|clear>"));
+        assert_eq!(helper.hint_text("l", 1), None);
+        assert_eq!(helper.hint_text("lib list ", 9), None);
+        assert_eq!(helper.hint_text("ls | sav", 8).as_deref(), Some(" <selector|filter>"));
+        assert_eq!(helper.hint_text("ls > ", 5), None);
+    }
+}
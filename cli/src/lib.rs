@@ -1,5 +1,7 @@
 pub mod repl;
 pub mod cli;
+pub mod completer;
 
 pub use repl::*;
-pub use cli::*; 
\ No newline at end of file
+pub use cli::*;
+pub use completer::*; 
\ No newline at end of file
@@ -1,92 +1,384 @@
 use rustyline::error::ReadlineError;
-use rustyline::{DefaultEditor, Result};
+use rustyline::history::DefaultHistory;
+use rustyline::{CompletionType, Editor, ExternalPrinter, Result};
 use rustyline::config::Configurer;
-use vlitz_core::{Command, CommandExecutor, CommandResult};
+use std::cell::RefCell;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::OnceLock;
+use vlitz_core::{CommandExecutor, CommandResult, CommandType, OutputFormat, Pipeline};
+use vlitz_preset::user_preset_dir;
+use vlitz_runtime::{CancelToken, Event, EventBus, EventCategory, RpcBackend};
+use vlitz_script::{is_incomplete, OnError, ScriptError, ScriptExecutor, ScriptLine};
+use vlitz_shared::config_dir;
 use anyhow::{Result as AnyhowResult, anyhow};
 use colored::*;
+use crate::completer::ReplHelper;
+
+/// 블록이 닫히지 않았을 때 이어서 입력받는 프롬프트
+const CONTINUATION_PROMPT: &str = "... ";
+
+/// 대상별 히스토리 파일에 남기는 최대 항목 수
+const MAX_HISTORY: usize = 1000;
+
+/// Ctrl-C로 취소할 토큰 (시그널 핸들러에서 사용)
+static INTERRUPT: OnceLock<CancelToken> = OnceLock::new();
 
 pub struct Repl {
-    editor: DefaultEditor,
-    executor: CommandExecutor,
+    editor: Editor<ReplHelper, DefaultHistory>,
+    /// 자동완성이 세션 상태를 읽을 수 있도록 공유
+    executor: Rc<RefCell<CommandExecutor>>,
+    /// 스크립트 상태 (변수, 프로시저, 이벤트 핸들러는 실행 사이에 유지)
+    scripts: ScriptExecutor,
+    /// 입력을 저장할 히스토리 파일 (`load_history` 전에는 없음)
+    history_path: Option<PathBuf>,
+    /// 명령어 결과 출력 형식 (줄마다 `--output`으로 바꿀 수 있음)
+    output: OutputFormat,
+    /// 배치 모드 (프롬프트, 색, 비동기 이벤트 출력 없음)
+    batch: bool,
+    /// 실패한 명령어가 있었는지 (배치 모드 종료 코드)
+    failed: bool,
 }
 
 impl Repl {
     pub fn new(items_per_page: usize) -> AnyhowResult<Self> {
-        let mut editor = DefaultEditor::new()?;
+        let mut editor = Editor::new()?;
         
-        // 히스토리 파일 설정
-        editor.set_auto_add_history(true);
+        // 파이프나 파일로 출력하면 색 없이
+        if !std::io::stdout().is_terminal() {
+            colored::control::set_override(false);
+        }
         
-        // 명령어 자동완성 설정 (추후 확장 가능)
+        // 여러 줄 블록을 한 항목으로 남기도록 히스토리는 직접 추가
+        editor.set_auto_add_history(false);
+        editor.set_max_history_size(MAX_HISTORY)?;
         
-        let executor = CommandExecutor::new(items_per_page);
+        let mut executor = CommandExecutor::new(items_per_page);
+        install_interrupt_handler(executor.cancel_token());
+        
+        // ~/.config/vlitz/presets 사용자 프리셋 (잘못된 매니페스트는 경고만 출력)
+        if let Some(dir) = user_preset_dir() {
+            for error in executor.presets_mut().load_dir(&dir) {
+                println!("{}: {}", "Preset error".yellow(), error);
+            }
+        }
+        
+        // 명령어 표와 세션 상태 기반 자동완성/힌트
+        let executor = Rc::new(RefCell::new(executor));
+        editor.set_helper(Some(ReplHelper::new(executor.clone())));
+        editor.set_completion_type(CompletionType::List);
         
         Ok(Self {
             editor,
             executor,
+            scripts: ScriptExecutor::new(),
+            history_path: None,
+            output: OutputFormat::Plain,
+            batch: false,
+            failed: false,
         })
     }
     
+    /// 배치 모드로 전환 (`set_backend` 전에 호출)
+    pub fn set_batch(&mut self) {
+        self.batch = true;
+        colored::control::set_override(false);
+    }
+    
+    /// 기본 출력 형식 변경 (`--output`)
+    pub fn set_output(&mut self, output: OutputFormat) {
+        self.output = output;
+    }
+    
+    /// 대상별 히스토리 파일을 불러오고 이후 입력을 저장 (`~/.config/vlitz/history`)
+    pub fn load_history(&mut self, target: Option<&str>) {
+        let Some(path) = history_path(target) else {
+            return;
+        };
+        if path.exists() {
+            if let Err(e) = self.editor.load_history(&path) {
+                println!("{}: Failed to load history {}: {}", "Warning".yellow(), path.display(), e);
+            }
+        } else if let Some(dir) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                println!("{}: Failed to create {}: {}", "Warning".yellow(), dir.display(), e);
+                return;
+            }
+        }
+        self.history_path = Some(path);
+    }
+    
+    /// 대상별 북마크를 불러오고 `nav mark`로 바꿀 때마다 저장 (`~/.config/vlitz/bookmarks`)
+    pub fn load_bookmarks(&mut self, target: Option<&str>) {
+        let Some(path) = bookmarks_path(target) else {
+            return;
+        };
+        if let Err(e) = self.executor.borrow_mut().load_bookmarks(path) {
+            println!("{}: {}", "Warning".yellow(), e);
+        }
+    }
+    
+    /// 세션 attach 후 RPC 백엔드 연결
+    pub fn set_backend(&mut self, backend: Box<dyn RpcBackend>) {
+        // 배치 출력 사이에 이벤트가 끼어들지 않도록 대화형에서만 바로 출력
+        if let Some(events) = backend.events().filter(|_| !self.batch) {
+            self.print_events(events);
+        }
+        self.executor.borrow_mut().set_backend(backend);
+    }
+    
+    /// 에이전트 이벤트를 받는 즉시 출력 (입력 중인 프롬프트는 다시 그려짐)
+    fn print_events(&mut self, events: &EventBus) {
+        match self.editor.create_external_printer() {
+            Ok(mut printer) => events.subscribe(move |event| {
+                if let Err(e) = printer.print(format_event(event)) {
+                    log::debug!("Failed to print event: {}", e);
+                }
+            }),
+            Err(e) => log::warn!("Asynchronous event output is unavailable: {}", e),
+        }
+    }
+    
+    /// .vzs 스크립트 실행 (exit 명령어로 종료 요청되면 true)
+    pub fn run_script(&mut self, path: &Path, on_error: OnError) -> bool {
+        self.executor.borrow().cancel_token().reset();
+        self.scripts.set_on_error(on_error);
+        let output = self.output;
+        let report = self.scripts.execute_file(&mut self.executor.borrow_mut(), path, |line, result| {
+            print_script_line(line, output);
+            print_result(result, output);
+        });
+        
+        self.print_errors("Script error", &report.errors);
+        print!("{}", output.render_message(&report.summary()));
+        
+        report.exit_requested
+    }
+    
+    /// 명령어 한 줄을 스크립트 실행기로 실행 (exit 명령어로 종료 요청되면 true)
+    fn run_line(&mut self, line: &str, output: OutputFormat) -> bool {
+        self.scripts.set_on_error(OnError::Stop);
+        let report = self.scripts.execute(&mut self.executor.borrow_mut(), line, |_, result| print_result(result, output));
+        
+        self.print_errors("Error", &report.errors);
+        report.exit_requested
+    }
+    
+    /// 스크립트 이벤트 핸들러 실행 (exit 명령어로 종료 요청되면 true)
+    fn dispatch_events(&mut self) -> bool {
+        self.executor.borrow().cancel_token().reset();
+        let output = self.output;
+        let report = self.scripts.dispatch_events(&mut self.executor.borrow_mut(), |line, result| {
+            print_script_line(line, output);
+            print_result(result, output);
+        });
+        
+        self.print_errors("Handler error", &report.errors);
+        report.exit_requested
+    }
+    
+    /// 프롬프트에서 입력 하나 읽기 (열린 블록이 `end`로 닫힐 때까지 이어지는 줄을 받음)
+    ///
+    /// Ctrl-C는 입력 중인 내용을 버리고 None을 반환한다.
+    fn read_input(&mut self, prompt: &str) -> Result<Option<String>> {
+        let mut input = String::new();
+        loop {
+            let prompt = if input.is_empty() { prompt } else { CONTINUATION_PROMPT };
+            match self.editor.readline(prompt) {
+                Ok(line) => {
+                    if !input.is_empty() {
+                        input.push('\n');
+                    }
+                    input.push_str(&line);
+                },
+                Err(ReadlineError::Interrupted) => {
+                    if input.is_empty() {
+                        println!("{}", "Type exit or press Ctrl-D to quit".dimmed());
+                    }
+                    return Ok(None);
+                },
+                // 블록 입력 중 Ctrl-D는 블록만 버림
+                Err(ReadlineError::Eof) if !input.is_empty() => return Ok(None),
+                Err(e) => return Err(e),
+            }
+            if !is_incomplete(&input) {
+                break;
+            }
+        }
+        
+        if !input.trim().is_empty() {
+            self.add_history(&input);
+        }
+        Ok(Some(input))
+    }
+    
+    /// 히스토리에 입력 추가 (히스토리 파일이 있으면 바로 덧붙임)
+    fn add_history(&mut self, input: &str) {
+        if let Err(e) = self.editor.add_history_entry(input) {
+            log::debug!("Failed to add history entry: {}", e);
+        }
+        if let Some(path) = &self.history_path {
+            if let Err(e) = self.editor.append_history(path) {
+                log::debug!("Failed to save history to {}: {}", path.display(), e);
+            }
+        }
+    }
+    
+    /// 입력 하나 실행 (종료 요청되면 true)
+    fn execute_input(&mut self, input: &str) -> bool {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return false;
+        }
+        
+        // 종료 명령어 처리
+        if trimmed == "exit" || trimmed == "quit" {
+            return true;
+        }
+        
+        self.executor.borrow().cancel_token().reset();
+        
+        // 여러 줄 블록은 스크립트로 실행
+        if trimmed.contains('\n') {
+            return self.run_line(trimmed, self.output);
+        }
+        
+        // 명령어 파싱 (파이프/리디렉션이 있으면 실행기가 단계별로 실행)
+        let mut output = self.output;
+        let command = match Pipeline::parse(trimmed) {
+            Ok(mut pipeline) if pipeline.is_single() => {
+                output = pipeline.output.unwrap_or(output);
+                Ok(pipeline.stages.remove(0))
+            },
+            Ok(pipeline) => {
+                let result = self.executor.borrow_mut().execute_pipeline(&pipeline);
+                if let CommandResult::Exit = result {
+                    return true;
+                }
+                // 리디렉션한 줄의 형식은 파일에만 적용 (화면에는 결과 요약)
+                let output = if pipeline.redirect.is_some() { self.output } else { pipeline.output.unwrap_or(output) };
+                self.show(&result, output);
+                return false;
+            },
+            Err(e) => Err(e),
+        };
+        match command {
+            Ok(command) if command.get_type() == CommandType::Run => {
+                // 스크립트 실행은 REPL에서 처리 (-c/--continue: 오류가 나도 계속)
+                let path = match command.get_raw_arg(0) {
+                    Some(path) => path,
+                    None => {
+                        self.print_error("Error", "Script path argument required", output);
+                        return false;
+                    },
+                };
+                let on_error = if command.has_flag("-c", "--continue") { OnError::Continue } else { OnError::Stop };
+                self.run_script(Path::new(path), on_error)
+            },
+            Ok(command) if matches!(command.get_type(), CommandType::OnList | CommandType::OnRemove) => {
+                let result = self.scripts.execute_handler_command(&self.executor.borrow(), &command);
+                self.show(&result, output);
+                false
+            },
+            Ok(command) if ScriptExecutor::handles(&self.executor.borrow(), &command) => {
+                // .vzs 프리셋 적용/되돌리기, 스크립트 프로시저
+                self.run_line(trimmed, output)
+            },
+            Ok(command) => {
+                // 명령어 실행
+                let result = self.executor.borrow_mut().execute(&command);
+                if let CommandResult::Exit = result {
+                    return true;
+                }
+                self.show(&result, output);
+                false
+            },
+            Err(e) => {
+                self.print_error("Parse error", &e.to_string(), output);
+                false
+            }
+        }
+    }
+    
+    /// 명령어 결과 출력 (실패는 기록)
+    fn show(&mut self, result: &CommandResult, output: OutputFormat) {
+        if let CommandResult::Error(_) = result {
+            self.failed = true;
+        }
+        print_result(result, output);
+    }
+    
+    /// 오류 출력 후 기록 (`label`은 텍스트 형식의 접두사)
+    fn print_error(&mut self, label: &str, message: &str, output: OutputFormat) {
+        self.failed = true;
+        if output.is_text() {
+            println!("{}: {}", label.red(), message);
+        } else {
+            print!("{}", output.render_error(&format!("{}: {}", label, message)));
+        }
+    }
+    
+    /// 스크립트 실행 오류 출력
+    fn print_errors(&mut self, label: &str, errors: &[ScriptError]) {
+        for error in errors {
+            self.print_error(label, &error.to_string(), self.output);
+        }
+    }
+    
+    /// 실패한 명령어나 스크립트 오류가 있었는지
+    pub fn has_failed(&self) -> bool {
+        self.failed
+    }
+    
+    /// 명령어를 프롬프트 없이 차례로 실행 (`-e`, 표준 입력), 실패한 명령어가 없으면 true
+    ///
+    /// 블록은 `end`로 닫힐 때까지 모아서 실행하고, 종료 명령어가 나오면 나머지는 실행하지 않는다.
+    pub fn run_batch<I: IntoIterator<Item = String>>(&mut self, lines: I) -> bool {
+        let mut block = String::new();
+        for line in lines {
+            if !block.is_empty() {
+                block.push('\n');
+            }
+            block.push_str(&line);
+            if is_incomplete(&block) {
+                continue;
+            }
+            
+            let input = std::mem::take(&mut block);
+            if self.dispatch_events() || self.execute_input(&input) {
+                break;
+            }
+        }
+        
+        if !block.trim().is_empty() {
+            self.print_error("Parse error", "Unclosed block at end of input", self.output);
+        }
+        !self.has_failed()
+    }
+    
     pub fn run(&mut self) -> AnyhowResult<()> {
         println!("{}", "VLITZ - Frida CLI Debugger".bold().green());
         println!("Type {} for help", "help".cyan());
         
         loop {
-            let prompt = self.executor.get_prompt();
+            // 지난 명령어 이후 도착한 이벤트로 핸들러 실행
+            if self.dispatch_events() {
+                println!("Exiting...");
+                break;
+            }
             
-            // 라인 읽기
-            let readline = self.editor.readline(&prompt);
-            match readline {
-                Ok(line) => {
-                    let trimmed = line.trim();
-                    if trimmed.is_empty() {
-                        continue;
-                    }
-                    
-                    // 종료 명령어 처리
-                    if trimmed == "exit" || trimmed == "quit" {
+            let prompt = self.executor.borrow().get_prompt();
+            
+            // 입력 읽기 (Ctrl-C는 입력만 취소)
+            match self.read_input(&prompt) {
+                Ok(Some(input)) => {
+                    if self.execute_input(&input) {
                         println!("Exiting...");
                         break;
                     }
-                    
-                    // 명령어 파싱
-                    match Command::parse(trimmed) {
-                        Ok(command) => {
-                            // 명령어 실행
-                            match self.executor.execute(&command) {
-                                CommandResult::Success(msg) => {
-                                    if !msg.is_empty() {
-                                        println!("{}", msg);
-                                    }
-                                },
-                                CommandResult::Error(err) => {
-                                    println!("{}: {}", "Error".red(), err);
-                                },
-                                CommandResult::DataList(data_list) => {
-                                    for (idx, data) in data_list {
-                                        let display = format!("[{}] [{}] {}", 
-                                            idx, 
-                                            data.data_type.to_string().cyan(), 
-                                            data.get_display_name()
-                                        );
-                                        println!("{}", display);
-                                    }
-                                },
-                                CommandResult::Exit => {
-                                    println!("Exiting...");
-                                    break;
-                                },
-                            }
-                        },
-                        Err(e) => {
-                            println!("{}: {}", "Parse error".red(), e);
-                        }
-                    }
-                },
-                Err(ReadlineError::Interrupted) => {
-                    println!("Ctrl-C");
-                    break;
                 },
+                Ok(None) => continue,
                 Err(ReadlineError::Eof) => {
                     println!("Ctrl-D");
                     break;
@@ -100,4 +392,132 @@ impl Repl {
         
         Ok(())
     }
-} 
\ No newline at end of file
+}
+
+/// 대상별 히스토리 파일 경로 (대상이 없으면 `default`)
+pub fn history_path(target: Option<&str>) -> Option<PathBuf> {
+    target_file("history", target, "history")
+}
+
+/// 대상별 북마크 파일 경로 (대상이 없으면 `default`)
+pub fn bookmarks_path(target: Option<&str>) -> Option<PathBuf> {
+    target_file("bookmarks", target, "json")
+}
+
+/// 설정 디렉터리 아래 대상 이름으로 된 파일 (파일 이름에 쓸 수 없는 글자는 `_`)
+fn target_file(dir: &str, target: Option<&str>, extension: &str) -> Option<PathBuf> {
+    let name: String = match target {
+        Some(target) if !target.trim().is_empty() => target.trim()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+            .collect(),
+        _ => "default".to_string(),
+    };
+    config_dir().map(|config| config.join(dir).join(format!("{}.{}", name, extension)))
+}
+
+/// SIGINT를 받으면 진행 중인 명령어를 취소 (이미 취소 중이면 강제 종료)
+///
+/// 프롬프트에서의 Ctrl-C는 rustyline이 직접 처리하므로 명령어 실행 중에만 호출된다.
+extern "C" fn on_interrupt(_: libc::c_int) {
+    if let Some(token) = INTERRUPT.get() {
+        if token.is_cancelled() {
+            // 시그널 핸들러에서 호출 가능한 _exit만 사용
+            unsafe { libc::_exit(130) };
+        }
+        token.cancel();
+    }
+}
+
+fn install_interrupt_handler(token: &CancelToken) {
+    if INTERRUPT.set(token.clone()).is_err() {
+        return;
+    }
+    // 핸들러는 원자적 플래그만 바꾸고 _exit 외의 함수를 부르지 않음
+    unsafe {
+        libc::signal(libc::SIGINT, on_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t);
+    }
+}
+
+/// 비동기 출력용 이벤트 한 줄
+fn format_event(event: &Event) -> String {
+    let header = format!("[{} #{}]", event.category().name(), event.id);
+    let header = match event.category() {
+        EventCategory::Hook => header.cyan(),
+        EventCategory::Watch => header.yellow(),
+        EventCategory::Preset => header.magenta(),
+        EventCategory::Send => header.green(),
+        EventCategory::Error => header.red(),
+        EventCategory::Log => header.dimmed(),
+    };
+    format!("{} {}", header, event.summary())
+}
+
+/// 스크립트에서 실행한 줄 표시 (JSON/CSV 출력에는 넣지 않음)
+fn print_script_line(line: &ScriptLine, output: OutputFormat) {
+    if output.is_text() {
+        println!("{} {}", format!("{}:", line.number).dimmed(), line.text.cyan());
+    }
+}
+
+/// 명령어 실행 결과 출력
+///
+/// 텍스트 형식은 색을 입혀 출력하고, JSON/CSV는 렌더러 출력을 그대로 쓴다.
+fn print_result(result: &CommandResult, output: OutputFormat) {
+    match (result, output) {
+        (_, OutputFormat::Json | OutputFormat::Csv) | (CommandResult::DataList(_), OutputFormat::Table) => {
+            print!("{}", output.render_result(result));
+        },
+        (CommandResult::Success(msg), _) => {
+            if !msg.is_empty() {
+                println!("{}", msg);
+            }
+        },
+        (CommandResult::Error(err), _) => {
+            println!("{}: {}", "Error".red(), err);
+        },
+        (CommandResult::DataList(data_list), _) => {
+            for (idx, data) in data_list {
+                let display = format!("[{}] [{}] {}", 
+                    idx, 
+                    data.data_type.to_string().cyan(), 
+                    data.get_display_name()
+                );
+                println!("{}", display);
+            }
+        },
+        (CommandResult::Exit, _) => {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_reports_failures() {
+        let lines = |lines: &[&str]| lines.iter().map(|line| line.to_string()).collect::<Vec<_>>();
+        let mut repl = Repl::new(20).unwrap();
+        repl.set_batch();
+        assert!(repl.run_batch(lines(&["help sav", "if true", "unsel", "end"])));
+        
+        // 실패해도 나머지 명령어는 실행하고, exit 뒤는 실행하지 않음
+        assert!(!repl.run_batch(lines(&["lib nope", "exit", "nope"])));
+        
+        let mut repl = Repl::new(20).unwrap();
+        repl.set_batch();
+        assert!(!repl.run_batch(lines(&["while true"])));
+    }
+
+    #[test]
+    fn test_history_path_per_target() {
+        let Some(default) = history_path(None) else {
+            return;
+        };
+        assert!(default.ends_with("vlitz/history/default.history"));
+        assert_eq!(history_path(Some("  ")), Some(default));
+        assert!(history_path(Some("com.example.app")).unwrap().ends_with("history/com.example.app.history"));
+        assert!(history_path(Some("/usr/bin/my app")).unwrap().ends_with("history/_usr_bin_my_app.history"));
+        assert!(bookmarks_path(Some("com.example.app")).unwrap().ends_with("bookmarks/com.example.app.json"));
+    }
+}
@@ -1,5 +1,6 @@
 use std::str::FromStr;
 use vlitz_shared::{VlitzError, VlitzResult, Selector};
+use crate::registry::CommandRegistry;
 
 /// 명령어 인자 타입
 #[derive(Debug, Clone)]
@@ -69,6 +70,15 @@ impl CommandArg {
             _ => Err(VlitzError::TypeConversion("Expected Float argument".to_string())),
         }
     }
+
+    /// 셀렉터로 해석 가능한 경우 셀렉터 반환 (숫자 인자 포함, 음수는 끝에서부터)
+    pub fn to_selector(&self) -> Option<Selector> {
+        match self {
+            CommandArg::Selector(s) => Some(s.clone()),
+            CommandArg::Number(n) => Some(Selector::index(*n)),
+            _ => None,
+        }
+    }
 }
 
 /// 명령어 구조체
@@ -80,42 +90,66 @@ pub struct Command {
     pub subcommand: Option<String>,
     /// 인자 목록
     pub args: Vec<CommandArg>,
+    /// 해석 전 원본 인자 토큰
+    pub raw_args: Vec<String>,
+    /// 플래그 인자 (-i, --inherited 등)
+    pub flags: Vec<String>,
 }
 
 impl Command {
     /// 문자열에서 명령어 파싱
+    ///
+    /// 파이프라인 연산자(`|`, `>`, `>>`)가 있으면 오류이며, 파이프라인은 `Pipeline::parse`로 파싱한다.
     pub fn parse(input: &str) -> VlitzResult<Self> {
-        let trimmed = input.trim();
-        if trimmed.is_empty() {
-            return Err(VlitzError::CommandParse("Empty command".to_string()));
+        let mut words = Vec::new();
+        for token in split_tokens(input.trim()) {
+            match token {
+                Token::Word(word) => words.push(word),
+                operator => return Err(VlitzError::CommandParse(format!("Unexpected '{}'", operator))),
+            }
         }
+        Self::from_words(words)
+    }
 
-        let tokens = split_tokens(trimmed);
+    /// 토큰 분리가 끝난 단어들로 명령어 생성
+    pub(crate) fn from_words(tokens: Vec<String>) -> VlitzResult<Self> {
         if tokens.is_empty() {
             return Err(VlitzError::CommandParse("Empty command".to_string()));
         }
 
         let name = tokens[0].to_string();
         
-        let subcommand = if tokens.len() > 1 {
-            Some(tokens[1].to_string())
+        // 그룹 명령어인 경우에만 두 번째 토큰을 하위 명령어로 취급 (sel 1, class Main 등은 인자)
+        let (subcommand, arg_start) = if tokens.len() > 1 && CommandRegistry::builtin().find_group(&name).is_some() {
+            (Some(tokens[1].to_string()), 2)
         } else {
-            None
+            (None, 1)
         };
 
-        let args = if tokens.len() > 2 {
-            parse_args(&tokens[2..])
-        } else {
-            Vec::new()
-        };
+        let (flags, raw_args): (Vec<String>, Vec<String>) = tokens.get(arg_start..)
+            .unwrap_or(&[])
+            .iter()
+            .cloned()
+            .partition(|token| is_flag(token));
+        let args = parse_args(&raw_args);
 
         Ok(Command {
             name,
             subcommand,
             args,
+            raw_args,
+            flags,
         })
     }
 
+    /// 맨 앞에 셀렉터 인자 끼워 넣기 (파이프라인 입력을 암시적 셀렉터로 쓸 때)
+    pub fn with_selector(&self, selector: &str) -> VlitzResult<Self> {
+        let mut command = self.clone();
+        command.args.insert(0, CommandArg::Selector(Selector::from_str(selector)?));
+        command.raw_args.insert(0, selector.to_string());
+        Ok(command)
+    }
+
     /// 인자가 있는지 확인
     pub fn has_args(&self) -> bool {
         !self.args.is_empty()
@@ -130,14 +164,68 @@ impl Command {
     pub fn get_arg(&self, index: usize) -> Option<&CommandArg> {
         self.args.get(index)
     }
+
+    /// 원본 인자 문자열 가져오기
+    pub fn get_raw_arg(&self, index: usize) -> Option<&str> {
+        self.raw_args.get(index).map(|s| s.as_str())
+    }
+
+    /// 플래그 인자 존재 여부 (예: -i, --inherited)
+    pub fn has_flag(&self, short: &str, long: &str) -> bool {
+        self.flags.iter().any(|flag| flag == short || flag == long)
+    }
+}
+
+/// 플래그 토큰 여부 (음수 인자와 구분하기 위해 문자로 시작해야 함)
+fn is_flag(token: &str) -> bool {
+    let mut chars = token.chars();
+    chars.next() == Some('-') && chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '-')
+}
+
+/// 명령어 줄 토큰
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    Word(String),
+    /// 파이프 (`|`)
+    Pipe,
+    /// 출력 리디렉션 (`>`, 이어 쓰기는 `>>`)
+    Redirect { append: bool },
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Pipe => write!(f, "|"),
+            Token::Redirect { append: false } => write!(f, ">"),
+            Token::Redirect { append: true } => write!(f, ">>"),
+        }
+    }
 }
 
 /// 토큰 분리
-fn split_tokens(input: &str) -> Vec<String> {
+///
+/// 공백으로 떨어진 `|`, `>`, `>>`만 연산자로 보고, 따옴표로 감싸거나 `\`로 이스케이프하거나
+/// 필터 표현식 안에 붙여 쓴 경우(`name:a|name:b`, `size>100`)는 일반 단어로 취급한다.
+pub(crate) fn split_tokens(input: &str) -> Vec<Token> {
     let mut tokens = Vec::new();
     let mut current_token = String::new();
     let mut in_quotes = false;
     let mut escape_next = false;
+    // 현재 토큰에 따옴표나 이스케이프가 있었는지 (연산자로 보지 않음)
+    let mut literal = false;
+
+    let mut finish = |token: &mut String, literal: &mut bool| {
+        let word = std::mem::take(token);
+        let operator = match word.as_str() {
+            "|" if !*literal => Some(Token::Pipe),
+            ">" if !*literal => Some(Token::Redirect { append: false }),
+            ">>" if !*literal => Some(Token::Redirect { append: true }),
+            _ => None,
+        };
+        tokens.push(operator.unwrap_or(Token::Word(word)));
+        *literal = false;
+    };
 
     for c in input.chars() {
         if escape_next {
@@ -148,18 +236,21 @@ fn split_tokens(input: &str) -> Vec<String> {
 
         if c == '\\' {
             escape_next = true;
+            literal = true;
             continue;
         }
 
         if c == '"' {
             in_quotes = !in_quotes;
+            literal = true;
             continue;
         }
 
         if c.is_whitespace() && !in_quotes {
-            if !current_token.is_empty() {
-                tokens.push(current_token);
-                current_token = String::new();
+            if current_token.is_empty() {
+                literal = false;
+            } else {
+                finish(&mut current_token, &mut literal);
             }
             continue;
         }
@@ -168,7 +259,7 @@ fn split_tokens(input: &str) -> Vec<String> {
     }
 
     if !current_token.is_empty() {
-        tokens.push(current_token);
+        finish(&mut current_token, &mut literal);
     }
 
     tokens
@@ -227,12 +318,19 @@ pub enum CommandType {
     NavAdd,
     NavSub,
     NavGoto,
+    NavBack,
+    NavForward,
+    NavHistory,
+    NavMark,
+    NavUnmark,
     
     // Log 명령어
     LogList,
     LogNext,
     LogPrev,
     LogSort,
+    LogGrep,
+    LogFilter,
     
     // Library 명령어
     LibList,
@@ -252,6 +350,8 @@ pub enum CommandType {
     ListMethod,
     ListModule,
     ListExports,
+    ListImports,
+    ListSymbols,
     ListRange,
     
     // Memory 명령어
@@ -265,9 +365,21 @@ pub enum CommandType {
     MemUnwatch,
     MemUnlock,
     MemUntrace,
+    MemEvents,
+    MemLocks,
     MemType,
     MemDisas,
     
+    // Trace 명령어
+    TraceTree,
+    TraceStats,
+    TraceExport,
+    TraceClear,
+    TraceStalk,
+    TraceUnstalk,
+    TraceCoverage,
+    TraceDrcov,
+    
     // Attacher 명령어
     AttachHook,
     AttachUnhook,
@@ -284,151 +396,44 @@ pub enum CommandType {
     ScanCh,
     ScanUnch,
     
+    // Preset 명령어
+    PresetList,
+    PresetApply,
+    PresetRevert,
+    PresetStatus,
+    PresetSet,
+    PresetInfo,
+    
+    // 에이전트 이벤트 로그
+    EventList,
+    EventClear,
+    
+    // 스크립트 이벤트 핸들러 (스크립트 실행기에서 처리)
+    OnList,
+    OnRemove,
+    
     // Utilities
     Fields,
     Help,
     Run,
+    Exit,
+    
+    // 추가 등록된 명령어 (실행기에 등록한 처리기로 실행)
+    Registered,
+    // 스크립트 프로시저 (스크립트 실행기에서 처리)
+    Procedure,
     
     // 기타
     Unknown,
 }
 
 impl Command {
-    /// 명령어 타입 해석
+    /// 명령어 타입 해석 (내장 명령어 표 기준, 추가 등록된 명령어는 `CommandExecutor::command_spec`으로 확인)
     pub fn get_type(&self) -> CommandType {
-        match self.name.as_str() {
-            "nav" | "navigator" => {
-                match self.subcommand.as_deref() {
-                    Some("select") | Some("sel") => CommandType::NavSelect,
-                    Some("unselect") | Some("unsel") => CommandType::NavUnselect,
-                    Some("add") | Some("+") => CommandType::NavAdd,
-                    Some("sub") | Some("-") => CommandType::NavSub,
-                    Some("goto") | Some(":") => CommandType::NavGoto,
-                    _ => CommandType::Unknown,
-                }
-            },
-            "sel" => CommandType::NavSelect,
-            "unsel" => CommandType::NavUnselect,
-            "+" => CommandType::NavAdd,
-            "-" => CommandType::NavSub,
-            ":" => CommandType::NavGoto,
-            
-            "log" => {
-                match self.subcommand.as_deref() {
-                    Some("list") | Some("lg") => CommandType::LogList,
-                    Some("next") | Some("nxt") => CommandType::LogNext,
-                    Some("prev") | Some("prv") => CommandType::LogPrev,
-                    Some("sort") => CommandType::LogSort,
-                    _ => CommandType::Unknown,
-                }
-            },
-            "lg" => CommandType::LogList,
-            "nxt" => CommandType::LogNext,
-            "prv" => CommandType::LogPrev,
-            
-            "lib" => {
-                match self.subcommand.as_deref() {
-                    Some("list") | Some("ls") => CommandType::LibList,
-                    Some("save") | Some("sav") => CommandType::LibSave,
-                    Some("move") | Some("mv") => CommandType::LibMove,
-                    Some("remove") | Some("rm") => CommandType::LibRemove,
-                    Some("clear") | Some("clr") => CommandType::LibClear,
-                    _ => CommandType::Unknown,
-                }
-            },
-            "ls" => CommandType::LibList,
-            "sav" => CommandType::LibSave,
-            "mv" => CommandType::LibMove,
-            "rm" => CommandType::LibRemove,
-            "clr" => CommandType::LibClear,
-            
-            "meta" => {
-                match self.subcommand.as_deref() {
-                    Some("label") => CommandType::MetaLabel,
-                    Some("tag") => CommandType::MetaTag,
-                    Some("untag") => CommandType::MetaUntag,
-                    Some("tags") => CommandType::MetaTags,
-                    _ => CommandType::Unknown,
-                }
-            },
-            
-            "list" => {
-                match self.subcommand.as_deref() {
-                    Some("class") => CommandType::ListClass,
-                    Some("method") => CommandType::ListMethod,
-                    Some("module") => CommandType::ListModule,
-                    Some("exports") => CommandType::ListExports,
-                    Some("range") => CommandType::ListRange,
-                    _ => CommandType::Unknown,
-                }
-            },
-            "class" => CommandType::ListClass,
-            "method" => CommandType::ListMethod,
-            "module" => CommandType::ListModule,
-            "exports" => CommandType::ListExports,
-            "range" => CommandType::ListRange,
-            
-            "mem" => {
-                match self.subcommand.as_deref() {
-                    Some("dump") | Some("d") => CommandType::MemDump,
-                    Some("read") | Some("r") => CommandType::MemRead,
-                    Some("write") | Some("w") => CommandType::MemWrite,
-                    Some("list") | Some("lm") => CommandType::MemList,
-                    Some("watch") => CommandType::MemWatch,
-                    Some("lock") => CommandType::MemLock,
-                    Some("trace") => CommandType::MemTrace,
-                    Some("unwatch") => CommandType::MemUnwatch,
-                    Some("unlock") => CommandType::MemUnlock,
-                    Some("untrace") => CommandType::MemUntrace,
-                    Some("type") => CommandType::MemType,
-                    Some("disas") => CommandType::MemDisas,
-                    _ => CommandType::Unknown,
-                }
-            },
-            "d" => CommandType::MemDump,
-            "r" => CommandType::MemRead,
-            "w" => CommandType::MemWrite,
-            "lm" => CommandType::MemList,
-            
-            "attach" => {
-                match self.subcommand.as_deref() {
-                    Some("hook") => CommandType::AttachHook,
-                    Some("unhook") => CommandType::AttachUnhook,
-                    Some("call") => CommandType::AttachCall,
-                    Some("list") | Some("la") => CommandType::AttachList,
-                    _ => CommandType::Unknown,
-                }
-            },
-            "la" => CommandType::AttachList,
-            
-            "scan" => {
-                match self.subcommand.as_deref() {
-                    Some("search") => CommandType::ScanSearch,
-                    Some("exact") => CommandType::ScanExact,
-                    Some("min") => CommandType::ScanMin,
-                    Some("max") => CommandType::ScanMax,
-                    Some("inc") => CommandType::ScanInc,
-                    Some("dec") => CommandType::ScanDec,
-                    Some("ch") => CommandType::ScanCh,
-                    Some("unch") => CommandType::ScanUnch,
-                    _ => CommandType::Unknown,
-                }
-            },
-            "search" => CommandType::ScanSearch,
-            "exact" => CommandType::ScanExact,
-            "min" => CommandType::ScanMin,
-            "max" => CommandType::ScanMax,
-            "inc" => CommandType::ScanInc,
-            "dec" => CommandType::ScanDec,
-            "ch" => CommandType::ScanCh,
-            "unch" => CommandType::ScanUnch,
-            
-            "fields" => CommandType::Fields,
-            "help" => CommandType::Help,
-            "run" => CommandType::Run,
-            
-            _ => CommandType::Unknown,
-        }
+        CommandRegistry::builtin()
+            .resolve(self)
+            .map(|spec| spec.command_type.clone())
+            .unwrap_or(CommandType::Unknown)
     }
 }
 
@@ -472,6 +477,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_selectors() {
+        use vlitz_shared::{SelectorSource, SelectorTerm};
+
+        let selector = |input: &str| match Command::parse(&format!("sel {}", input)).unwrap().args[0].to_selector() {
+            Some(selector) => selector,
+            None => panic!("Expected selector: {}", input),
+        };
+        assert_eq!(selector("-1").terms, vec![SelectorTerm::Index(-1)]);
+        assert_eq!(selector("5-").terms, vec![SelectorTerm::Range(5, None)]);
+        assert_eq!(selector("-3-").terms, vec![SelectorTerm::Range(-3, None)]);
+        assert_eq!(selector("6-3").terms, vec![SelectorTerm::Range(6, Some(3))]);
+
+        let lib = selector("lib:@main,#hot,2");
+        assert_eq!(lib.source, Some(SelectorSource::Lib));
+        assert_eq!(lib.terms, vec![SelectorTerm::Label("main".to_string()), SelectorTerm::Tag("hot".to_string()), SelectorTerm::Index(2)]);
+        assert_eq!(lib.to_string(), "lib:@main,#hot,2");
+        assert_eq!(selector("log:type=Function").terms, vec![SelectorTerm::Filter("type=Function".to_string())]);
+        assert_eq!(selector("nav:0").source, Some(SelectorSource::Nav));
+
+        // 접두사 없는 필터 식은 셀렉터가 아님
+        let cmd = Command::parse("sav name:open").unwrap();
+        assert!(matches!(&cmd.args[0], CommandArg::FilterExpr(expr) if expr == "name:open"));
+        for invalid in ["lib:", "1-x", "@", "lib:nope"] {
+            assert!(Selector::from_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+
     #[test]
     fn test_get_command_type() {
         let cmd = Command::parse("nav select 1").unwrap();
@@ -483,4 +516,4 @@ mod tests {
         let cmd = Command::parse("search 47.3 float").unwrap();
         assert_eq!(cmd.get_type(), CommandType::ScanSearch);
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+use vlitz_runtime::{CoverageBatch, ModuleInfo, StalkHandle, StalkKind};
+
+/// 커버리지 수집 세션
+#[derive(Debug, Clone)]
+pub struct StalkSession {
+    /// 에이전트 수집 ID
+    pub id: u64,
+    pub kind: StalkKind,
+    pub target: String,
+    /// 수집 중 여부 (중지 후에도 수집된 블록은 유지)
+    pub active: bool,
+}
+
+/// 모듈별 커버리지 요약
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleCoverage {
+    pub name: String,
+    /// 실행된 기본 블록 수
+    pub blocks: usize,
+    /// 실행된 블록의 총 크기 (바이트)
+    pub bytes: u64,
+}
+
+/// 기본 블록 커버리지 (모든 세션 합산)
+#[derive(Debug, Default)]
+pub struct CoverageMap {
+    sessions: Vec<StalkSession>,
+    /// 블록 시작 주소 -> 블록 크기
+    blocks: BTreeMap<u64, u32>,
+}
+
+impl CoverageMap {
+    /// 새 커버리지 맵 생성
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 수집 세션 추가
+    pub fn add_session(&mut self, handle: StalkHandle) {
+        self.sessions.push(StalkSession {
+            id: handle.id,
+            kind: handle.kind,
+            target: handle.target,
+            active: true,
+        });
+    }
+
+    /// 전체 세션
+    pub fn sessions(&self) -> &[StalkSession] {
+        &self.sessions
+    }
+
+    /// 수집 중인 세션 ID 목록
+    pub fn active_ids(&self) -> Vec<u64> {
+        self.sessions.iter().filter(|s| s.active).map(|s| s.id).collect()
+    }
+
+    /// 세션을 중지 상태로 표시
+    pub fn stop_session(&mut self, id: u64) {
+        if let Some(session) = self.sessions.iter_mut().find(|s| s.id == id) {
+            session.active = false;
+        }
+    }
+
+    /// 에이전트에서 가져온 블록 반영 후 새 블록 수 반환
+    pub fn ingest(&mut self, batches: Vec<CoverageBatch>) -> usize {
+        let before = self.blocks.len();
+        for block in batches.into_iter().flat_map(|batch| batch.blocks) {
+            let size = self.blocks.entry(block.start()).or_insert(0);
+            *size = (*size).max(block.size());
+        }
+        self.blocks.len() - before
+    }
+
+    /// 수집된 블록 수
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// 수집된 블록과 중지된 세션 제거
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+        self.sessions.retain(|s| s.active);
+    }
+
+    /// 블록이 속한 모듈 인덱스
+    fn module_index(modules: &[ModuleInfo], address: u64) -> Option<usize> {
+        modules.iter().position(|m| address >= m.base && address < m.base + m.size as u64)
+    }
+
+    /// 모듈별 요약 (블록 수 내림차순)과 어느 모듈에도 속하지 않는 블록 수
+    pub fn summary(&self, modules: &[ModuleInfo]) -> (Vec<ModuleCoverage>, usize) {
+        let mut per_module: BTreeMap<usize, (usize, u64)> = BTreeMap::new();
+        let mut unmapped = 0;
+
+        for (&start, &size) in &self.blocks {
+            match Self::module_index(modules, start) {
+                Some(idx) => {
+                    let entry = per_module.entry(idx).or_default();
+                    entry.0 += 1;
+                    entry.1 += size as u64;
+                },
+                None => unmapped += 1,
+            }
+        }
+
+        let mut summary: Vec<ModuleCoverage> = per_module.into_iter()
+            .map(|(idx, (blocks, bytes))| ModuleCoverage { name: modules[idx].name.clone(), blocks, bytes })
+            .collect();
+        summary.sort_by(|a, b| b.blocks.cmp(&a.blocks).then_with(|| a.name.cmp(&b.name)));
+        (summary, unmapped)
+    }
+
+    /// drcov(v2) 형식으로 내보내기 (Lighthouse, Ghidra Dragon Dance 등에서 사용)
+    ///
+    /// 모듈 테이블에는 모든 모듈을 기록하고, 모듈 밖의 블록은 제외한다.
+    pub fn to_drcov(&self, modules: &[ModuleInfo]) -> Vec<u8> {
+        let mut entries: Vec<(u32, u16, u16)> = Vec::new();
+        for (&start, &size) in &self.blocks {
+            if let Some(idx) = Self::module_index(modules, start) {
+                let offset = (start - modules[idx].base) as u32;
+                entries.push((offset, size.min(u16::MAX as u32) as u16, idx as u16));
+            }
+        }
+
+        let mut header = String::from("DRCOV VERSION: 2\nDRCOV FLAVOR: vlitz\n");
+        header.push_str(&format!("Module Table: version 2, count {}\n", modules.len()));
+        header.push_str("Columns: id, base, end, entry, checksum, timestamp, path\n");
+        for (idx, module) in modules.iter().enumerate() {
+            header.push_str(&format!(
+                "{}, 0x{:016x}, 0x{:016x}, 0x{:016x}, 0x{:08x}, 0x{:08x}, {}\n",
+                idx,
+                module.base,
+                module.base + module.size as u64,
+                0,
+                0,
+                0,
+                module.path.as_deref().unwrap_or(&module.name)
+            ));
+        }
+        header.push_str(&format!("BB Table: {} bbs\n", entries.len()));
+
+        let mut output = header.into_bytes();
+        for (offset, size, module_id) in entries {
+            output.extend_from_slice(&offset.to_le_bytes());
+            output.extend_from_slice(&size.to_le_bytes());
+            output.extend_from_slice(&module_id.to_le_bytes());
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vlitz_runtime::CoverageBlock;
+
+    fn modules() -> Vec<ModuleInfo> {
+        vec![
+            ModuleInfo { name: "libgame.so".to_string(), base: 0x7000_0000, size: 0x10000, path: Some("/data/app/lib/libgame.so".to_string()) },
+            ModuleInfo { name: "libc.so".to_string(), base: 0x7100_0000, size: 0x10000, path: None },
+        ]
+    }
+
+    fn coverage() -> CoverageMap {
+        let mut coverage = CoverageMap::new();
+        let new_blocks = coverage.ingest(vec![CoverageBatch {
+            stalk_id: 1,
+            blocks: vec![
+                CoverageBlock(0x7000_0100, 12),
+                CoverageBlock(0x7000_0200, 8),
+                CoverageBlock(0x7100_0010, 4),
+                CoverageBlock(0x7000_0100, 12),
+                CoverageBlock(0x1000, 4),
+            ],
+        }]);
+        assert_eq!(new_blocks, 4);
+        coverage
+    }
+
+    #[test]
+    fn test_summary_per_module() {
+        let (summary, unmapped) = coverage().summary(&modules());
+
+        assert_eq!(summary[0], ModuleCoverage { name: "libgame.so".to_string(), blocks: 2, bytes: 20 });
+        assert_eq!(summary[1].name, "libc.so");
+        assert_eq!(unmapped, 1);
+    }
+
+    #[test]
+    fn test_drcov_format() {
+        let output = coverage().to_drcov(&modules());
+        let marker = b"BB Table: 3 bbs\n";
+        let table_start = output.windows(marker.len()).position(|w| w == marker).unwrap() + marker.len();
+        let header = String::from_utf8_lossy(&output[..table_start]);
+
+        assert!(header.starts_with("DRCOV VERSION: 2\n"));
+        assert!(header.contains("Module Table: version 2, count 2\n"));
+        assert!(header.contains("0, 0x0000000070000000, 0x0000000070010000, 0x0000000000000000, 0x00000000, 0x00000000, /data/app/lib/libgame.so\n"));
+        assert!(header.contains(", libc.so\n"));
+
+        let table = &output[table_start..];
+        assert_eq!(table.len(), 3 * 8);
+        assert_eq!(&table[..8], &[0x00, 0x01, 0x00, 0x00, 12, 0, 0, 0]);
+        assert_eq!(&table[16..], &[0x10, 0x00, 0x00, 0x00, 4, 0, 1, 0]);
+    }
+}
@@ -1,10 +1,35 @@
 use crate::command::{Command, CommandType, CommandArg};
 use crate::store::DataStore;
 use crate::navigator::Navigator;
-use vlitz_shared::{VlitzError, VlitzResult, VzData, Selector};
-use vlitz_shared::filter::FilterParser;
-use vlitz_shared::utils::format_vzdata;
-use std::str::FromStr;
+use crate::monitor::{LockEntry, MemoryMonitor, MonitorEntry, TraceEntry, WatchEntry};
+use crate::trace::CallTrace;
+use crate::coverage::CoverageMap;
+use crate::registry::{CommandRegistry, CommandSource, CommandSpec};
+use crate::pipeline::{PipeInput, PipeSource, Pipeline, Redirect};
+use vlitz_shared::{FilterParser, MemoryType, MemoryValue, VlitzError, VlitzResult, VzData, Selector, SelectorSource};
+use vlitz_shared::utils::{addr_to_hex, format_vzdata, hex_to_addr};
+use vlitz_preset::{parse_overrides, PresetManager};
+use vlitz_runtime::{Agent, CancelToken, ClassHierarchy, EventBus, EventFilter, LockMode, LockOptions, NamePattern, RpcBackend, ScriptMessage, TraceHandle, DEFAULT_TRACE_ARG_COUNT};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// 모듈 단위로 조회하는 항목 종류
+#[derive(Debug, Clone, Copy)]
+enum ModuleItems {
+    Exports,
+    Imports,
+    Symbols,
+}
+
+impl ModuleItems {
+    fn name(&self) -> &'static str {
+        match self {
+            ModuleItems::Exports => "exports",
+            ModuleItems::Imports => "imports",
+            ModuleItems::Symbols => "symbols",
+        }
+    }
+}
 
 /// 명령어 실행 결과 열거형
 pub enum CommandResult {
@@ -18,12 +43,33 @@ pub enum CommandResult {
     Exit,
 }
 
+/// 추가 등록된 명령어 처리기
+pub type CommandHandler = Rc<dyn Fn(&mut CommandExecutor, &Command) -> CommandResult>;
+
 /// 명령어 실행기 구조체
 pub struct CommandExecutor {
     /// 데이터 저장소
     store: DataStore,
     /// 내비게이터
     navigator: Navigator,
+    /// mem list 테이블 (watch, lock, trace)
+    monitor: MemoryMonitor,
+    /// 함수 추적 호출 기록
+    trace: CallTrace,
+    /// Stalker 기본 블록 커버리지
+    coverage: CoverageMap,
+    /// 자동 후킹 프리셋
+    presets: PresetManager,
+    /// 에이전트 RPC 백엔드 (attach 전에는 없음)
+    backend: Option<Box<dyn RpcBackend>>,
+    /// 명령어 정의 (내장 명령어와 프리셋/스크립트가 추가한 명령어)
+    registry: CommandRegistry,
+    /// 추가 등록된 명령어의 처리기 (전체 이름 -> 처리기)
+    handlers: HashMap<String, CommandHandler>,
+    /// 진행 중인 명령어 취소 요청 (REPL의 Ctrl-C)
+    cancel: CancelToken,
+    /// 실행 중인 파이프라인 단계의 입력 (앞 명령어의 데이터 목록)
+    pipe_input: Option<PipeInput>,
 }
 
 impl CommandExecutor {
@@ -32,12 +78,211 @@ impl CommandExecutor {
         Self {
             store: DataStore::new(items_per_page),
             navigator: Navigator::new(),
+            monitor: MemoryMonitor::new(),
+            trace: CallTrace::new(),
+            coverage: CoverageMap::new(),
+            presets: PresetManager::new(),
+            backend: None,
+            registry: CommandRegistry::default(),
+            handlers: HashMap::new(),
+            cancel: CancelToken::new(),
+            pipe_input: None,
+        }
+    }
+
+    /// RPC 백엔드 설정 (세션 attach 후 호출)
+    pub fn set_backend(&mut self, backend: Box<dyn RpcBackend>) {
+        self.backend = Some(backend);
+    }
+
+    /// RPC 백엔드 해제 (세션 detach 전 호출)
+    ///
+    /// 적용된 프리셋을 되돌리고 에이전트에 걸린 잠금을 모두 해제한 뒤 mem list를 비운다.
+    pub fn clear_backend(&mut self) -> VlitzResult<()> {
+        if let Some(backend) = self.backend.as_deref_mut() {
+            if let Err(e) = self.presets.revert_all(backend) {
+                log::warn!("Failed to revert presets: {}", e);
+            }
+        }
+        self.presets.forget_applied();
+        self.sync_preset_commands();
+        
+        let result = match self.agent() {
+            Ok(agent) => agent.remove_all_locks().map(|_| ()),
+            Err(_) => Ok(()),
+        };
+        self.monitor = MemoryMonitor::new();
+        self.backend = None;
+        result
+    }
+
+    /// 에이전트 래퍼 가져오기
+    pub fn agent(&self) -> VlitzResult<Agent<'_>> {
+        self.backend.as_deref()
+            .map(|backend| Agent::new(backend).with_cancel(&self.cancel))
+            .ok_or_else(|| VlitzError::Frida("Not attached to a process".to_string()))
+    }
+
+    /// 명령어 취소 토큰 (복제해서 시그널 핸들러 등에서 취소 요청)
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.cancel
+    }
+
+    /// 에이전트가 보낸 메시지를 모두 꺼냄 (attach 전에는 빈 목록)
+    pub fn drain_messages(&self) -> Vec<ScriptMessage> {
+        self.backend.as_deref()
+            .map(RpcBackend::drain_messages)
+            .unwrap_or_default()
+    }
+
+    /// 명령어 등록부
+    pub fn registry(&self) -> &CommandRegistry {
+        &self.registry
+    }
+
+    /// 명령어 등록부 변경 (처리기 없이 정의만 추가할 때, 예: 스크립트 프로시저)
+    pub fn registry_mut(&mut self) -> &mut CommandRegistry {
+        &mut self.registry
+    }
+
+    /// 파싱된 명령어의 정의 (추가 등록된 명령어 포함)
+    pub fn command_spec(&self, command: &Command) -> Option<&CommandSpec> {
+        self.registry.resolve(command)
+    }
+
+    /// 처리기와 함께 명령어 추가 (이름이 겹치면 오류)
+    pub fn register_command(&mut self, spec: CommandSpec, handler: CommandHandler) -> VlitzResult<()> {
+        let spec = CommandSpec { command_type: CommandType::Registered, ..spec };
+        let full_name = spec.full_name();
+        self.registry.register(spec)?;
+        self.handlers.insert(full_name, handler);
+        Ok(())
+    }
+
+    /// 추가한 명령어 제거 (제거했으면 true)
+    pub fn unregister_command(&mut self, full_name: &str) -> bool {
+        self.handlers.remove(full_name);
+        self.registry.unregister(full_name).is_some()
+    }
+
+    /// 실행 중인 파이프라인 단계의 입력 (추가 등록된 명령어 처리기가 직접 읽을 때 사용)
+    pub fn pipe_input(&self) -> Option<&PipeInput> {
+        self.pipe_input.as_ref()
+    }
+
+    /// 파이프라인 실행
+    ///
+    /// 앞 명령어의 데이터 목록(DataList, 로그를 새로 채운 경우 로그)을 다음 명령어의 입력으로 넘기고,
+    /// 입력을 받는 명령어가 셀렉터를 생략하면 입력 항목을 가리키는 셀렉터를 채운다.
+    pub fn execute_pipeline(&mut self, pipeline: &Pipeline) -> CommandResult {
+        let mut input: Option<PipeInput> = None;
+        let mut output = None;
+        let mut result = CommandResult::Success(String::new());
+
+        for (idx, stage) in pipeline.stages.iter().enumerate() {
+            let command = match &input {
+                Some(input) => match self.bind_pipe_input(stage, input) {
+                    Ok(command) => command,
+                    Err(e) => return CommandResult::Error(e),
+                },
+                None => stage.clone(),
+            };
+
+            let generation = self.store.log_generation();
+            let source = input.as_ref().map(|input| input.source);
+            self.pipe_input = input.take();
+            result = self.execute(&command);
+            self.pipe_input = None;
+            if matches!(result, CommandResult::Error(_) | CommandResult::Exit) {
+                return result;
+            }
+
+            output = self.pipe_output(&command, &result, generation, source);
+            if idx + 1 < pipeline.stages.len() {
+                match output.take() {
+                    Some(output) => input = Some(output),
+                    None => {
+                        let name = self.registry.resolve(&command).map_or_else(|| command.name.clone(), CommandSpec::full_name);
+                        return CommandResult::Error(format!("'{}' produces no items to pipe", name));
+                    },
+                }
+            }
+        }
+
+        match &pipeline.redirect {
+            Some(redirect) => self.write_redirect(redirect, output.as_ref(), &result),
+            None => result,
+        }
+    }
+
+    /// 파이프 입력을 받도록 명령어 준비 (셀렉터를 생략했으면 입력 항목 셀렉터를 맨 앞에 넣음)
+    fn bind_pipe_input(&self, stage: &Command, input: &PipeInput) -> Result<Command, String> {
+        let Some(spec) = self.registry.resolve(stage) else {
+            // 모르는 명령어 오류는 실행하면서 보고
+            return Ok(stage.clone());
+        };
+        if !spec.input {
+            return Err(format!("'{}' does not take piped input", spec.full_name()));
+        }
+        if input.items.is_empty() {
+            return Err(format!("No items to pipe into '{}'", spec.full_name()));
+        }
+
+        let takes_selector = spec.arg_slots().first().is_some_and(|slot| slot.split('|').any(|kind| kind == "selector"));
+        let has_selector = stage.get_arg(0).and_then(CommandArg::to_selector).is_some();
+        if takes_selector && !has_selector {
+            stage.with_selector(&input.selector()).map_err(|e| e.to_string())
+        } else {
+            Ok(stage.clone())
+        }
+    }
+
+    /// 명령어 결과에서 다음 단계로 넘길 데이터 (DataList는 입력과 같은 저장소 인덱스로 봄)
+    fn pipe_output(&self, command: &Command, result: &CommandResult, generation: u64, source: Option<PipeSource>) -> Option<PipeInput> {
+        let all = |source: PipeSource, data: &[VzData]| PipeInput {
+            source,
+            items: data.iter().cloned().enumerate().collect(),
+        };
+        let command_type = self.registry.resolve(command).map(|spec| spec.command_type.clone());
+
+        match (result, command_type) {
+            (CommandResult::DataList(items), _) => Some(PipeInput { source: source.unwrap_or(PipeSource::Log), items: items.clone() }),
+            (_, Some(CommandType::LogList)) => Some(all(PipeSource::Log, self.store.get_log())),
+            (_, Some(CommandType::LibList)) => Some(all(PipeSource::Lib, self.store.get_lib())),
+            (CommandResult::Success(_), _) if self.store.log_generation() != generation => Some(all(PipeSource::Log, self.store.get_log())),
+            _ => None,
+        }
+    }
+
+    /// 마지막 명령어 결과를 파일에 씀 (데이터가 있으면 항목, 없으면 메시지)
+    fn write_redirect(&self, redirect: &Redirect, output: Option<&PipeInput>, result: &CommandResult) -> CommandResult {
+        let (text, summary) = match (output, result) {
+            (Some(output), _) => (redirect.format.render_items(&output.items), format!("{} items", output.items.len())),
+            (None, CommandResult::Success(message)) => (redirect.format.render_message(message), "output".to_string()),
+            _ => (String::new(), "output".to_string()),
+        };
+
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(redirect.append)
+            .truncate(!redirect.append)
+            .open(&redirect.path);
+        match file.and_then(|mut file| std::io::Write::write_all(&mut file, text.as_bytes())) {
+            Ok(_) => {
+                let verb = if redirect.append { "Appended" } else { "Wrote" };
+                CommandResult::Success(format!("{} {} to {}", verb, summary, redirect.path))
+            },
+            Err(e) => CommandResult::Error(format!("Failed to write {}: {}", redirect.path, e)),
         }
     }
 
     /// 명령어 실행
     pub fn execute(&mut self, command: &Command) -> CommandResult {
-        let cmd_type = command.get_type();
+        let cmd_type = match self.registry.check(command) {
+            Ok(spec) => spec.command_type.clone(),
+            Err(e) => return CommandResult::Error(e),
+        };
         
         match cmd_type {
             // Navigator 명령어
@@ -46,12 +291,19 @@ impl CommandExecutor {
             CommandType::NavAdd => self.execute_nav_add(command),
             CommandType::NavSub => self.execute_nav_sub(command),
             CommandType::NavGoto => self.execute_nav_goto(command),
+            CommandType::NavBack => self.execute_nav_back(command),
+            CommandType::NavForward => self.execute_nav_forward(command),
+            CommandType::NavHistory => self.execute_nav_history(command),
+            CommandType::NavMark => self.execute_nav_mark(command),
+            CommandType::NavUnmark => self.execute_nav_unmark(command),
             
             // Log 명령어
             CommandType::LogList => self.execute_log_list(command),
             CommandType::LogNext => self.execute_log_next(command),
             CommandType::LogPrev => self.execute_log_prev(command),
             CommandType::LogSort => self.execute_log_sort(command),
+            CommandType::LogGrep => self.execute_log_grep(command),
+            CommandType::LogFilter => self.execute_log_filter(command),
             
             // Library 명령어
             CommandType::LibList => self.execute_lib_list(command),
@@ -66,8 +318,68 @@ impl CommandExecutor {
             CommandType::MetaUntag => self.execute_meta_untag(command),
             CommandType::MetaTags => self.execute_meta_tags(command),
             
+            // List 명령어
+            CommandType::ListClass => self.execute_list_class(command),
+            CommandType::ListMethod => self.execute_list_method(command),
+            CommandType::ListModule => self.execute_list_module(command),
+            CommandType::ListExports => self.execute_list_module_items(command, ModuleItems::Exports),
+            CommandType::ListImports => self.execute_list_module_items(command, ModuleItems::Imports),
+            CommandType::ListSymbols => self.execute_list_module_items(command, ModuleItems::Symbols),
+            CommandType::ListRange => self.execute_list_range(command),
+            
+            // Memory 명령어
+            CommandType::MemList => self.execute_mem_list(command),
+            CommandType::MemWatch => self.execute_mem_watch(command),
+            CommandType::MemUnwatch => self.execute_mem_unwatch(command),
+            CommandType::MemLock => self.execute_mem_lock(command),
+            CommandType::MemUnlock => self.execute_mem_unlock(command),
+            CommandType::MemLocks => self.execute_mem_locks(command),
+            CommandType::MemTrace => self.execute_mem_trace(command),
+            CommandType::MemUntrace => self.execute_mem_untrace(command),
+            
+            // Trace 명령어
+            CommandType::TraceTree => self.execute_trace_tree(command),
+            CommandType::TraceStats => self.execute_trace_stats(command),
+            CommandType::TraceExport => self.execute_trace_export(command),
+            CommandType::TraceClear => self.execute_trace_clear(command),
+            CommandType::TraceStalk => self.execute_trace_stalk(command),
+            CommandType::TraceUnstalk => self.execute_trace_unstalk(command),
+            CommandType::TraceCoverage => self.execute_trace_coverage(command),
+            CommandType::TraceDrcov => self.execute_trace_drcov(command),
+            CommandType::MemEvents => self.execute_mem_events(command),
+            
+            // Preset 명령어
+            CommandType::PresetList => self.execute_preset_list(command),
+            CommandType::PresetApply => {
+                let result = self.execute_preset_apply(command);
+                self.sync_preset_commands();
+                result
+            },
+            CommandType::PresetRevert => {
+                let result = self.execute_preset_revert(command);
+                self.sync_preset_commands();
+                result
+            },
+            CommandType::PresetStatus => self.execute_preset_status(command),
+            CommandType::PresetSet => self.execute_preset_set(command),
+            CommandType::PresetInfo => self.execute_preset_info(command),
+            
+            // Event 명령어
+            CommandType::EventList => self.execute_event_list(command),
+            CommandType::EventClear => self.execute_event_clear(command),
+            
+            CommandType::Help => self.execute_help(command),
+            CommandType::Registered => self.execute_registered(command),
+            
             // 나머지 명령어들은 실제 구현에서 추가
             
+            CommandType::Procedure => {
+                CommandResult::Error(format!("'{}' is a script procedure and must run through the script executor", command.name))
+            },
+            CommandType::OnList | CommandType::OnRemove => {
+                CommandResult::Error("Event handlers are only available while running scripts".to_string())
+            },
+            CommandType::Exit => CommandResult::Exit,
             CommandType::Unknown => CommandResult::Error("Unknown command".to_string()),
             _ => CommandResult::Error("Command not implemented yet".to_string()),
         }
@@ -78,39 +390,85 @@ impl CommandExecutor {
         self.navigator.get_prompt()
     }
 
+    /// 데이터 저장소 (읽기 전용)
+    pub fn store(&self) -> &DataStore {
+        &self.store
+    }
+
+    /// 내비게이터 (읽기 전용)
+    pub fn navigator(&self) -> &Navigator {
+        &self.navigator
+    }
+
+    /// 북마크 파일을 불러오고 `nav mark`로 바꿀 때마다 저장
+    pub fn load_bookmarks(&mut self, path: std::path::PathBuf) -> VlitzResult<usize> {
+        self.navigator.load_bookmarks(path)
+    }
+
+    /// 프리셋 관리자
+    pub fn presets(&self) -> &PresetManager {
+        &self.presets
+    }
+
+    /// 프리셋 관리자 (사용자 프리셋 등록, 명령어 스크립트 프리셋 상태 변경용)
+    pub fn presets_mut(&mut self) -> &mut PresetManager {
+        &mut self.presets
+    }
+
+    /// 셀렉터가 가리키는 항목 (접두사가 없으면 `default` 목록의 인덱스, `nav:`는 내비게이터 항목)
+    pub fn select(&self, selector: &Selector, default: SelectorSource) -> VlitzResult<(SelectorSource, Vec<(usize, VzData)>)> {
+        let (source, indices) = self.resolve_selector(selector, default)?;
+        let items = self.source_items(source);
+        Ok((source, indices.into_iter().map(|idx| (idx, items[idx].clone())).collect()))
+    }
+
+    /// 셀렉터가 가리키는 목록과 인덱스 (메모리 값 필터는 에이전트로 읽음)
+    fn resolve_selector(&self, selector: &Selector, default: SelectorSource) -> VlitzResult<(SelectorSource, Vec<usize>)> {
+        let source = selector.source.unwrap_or(default);
+        let items = self.source_items(source);
+        let indices = if selector.reads_memory() {
+            let agent = self.agent()?;
+            selector.resolve_with(items, &mut |data, memory_type| read_item(&agent, data, memory_type))?
+        } else {
+            selector.resolve(items)?
+        };
+        Ok((source, indices))
+    }
+
+    /// 셀렉터 대상 목록
+    fn source_items(&self, source: SelectorSource) -> &[VzData] {
+        match source {
+            SelectorSource::Nav => self.navigator.items(),
+            source => self.store.items(source),
+        }
+    }
+
+    /// 접두사 없는 셀렉터는 로그 인덱스로 보고 항목 하나 선택 (`what`은 오류 메시지의 대상 이름)
+    fn select_one(&self, selector: &Selector, what: &str) -> Result<(usize, VzData), String> {
+        let (_, mut items) = self.select(selector, SelectorSource::Log)
+            .map_err(|e| format!("Selection error: {}", e))?;
+        if items.len() > 1 {
+            return Err(format!("Only one {} can be used at a time", what));
+        }
+        Ok(items.remove(0))
+    }
+
     // Navigator 명령어 실행 메서드
     fn execute_nav_select(&mut self, command: &Command) -> CommandResult {
-        if command.args.is_empty() {
-            return CommandResult::Error("Selector argument required".to_string());
-        }
+        let (selector, _) = match selector_arg(command, true) {
+            Ok(selector) => selector,
+            Err(e) => return CommandResult::Error(e),
+        };
 
-        match &command.args[0] {
-            CommandArg::Selector(selector) => {
-                match self.store.select_data(selector) {
-                    Ok(data_list) => {
-                        if data_list.len() > 1 {
-                            return CommandResult::Error("Only one item can be selected".to_string());
-                        }
-                        
-                        let data = data_list[0].clone();
-                        self.navigator.select(data.clone());
-                        CommandResult::Success(format!("Selected: {}", format_vzdata(0, data)))
-                    },
-                    Err(e) => CommandResult::Error(format!("Selection error: {}", e)),
-                }
+        match self.select(&selector, SelectorSource::Log) {
+            Ok((_, items)) if items.len() > 1 => CommandResult::Error("Only one item can be selected".to_string()),
+            Ok((_, mut items)) => {
+                let (idx, data) = items.remove(0);
+                let message = format!("Selected: {}", format_vzdata(idx, &data));
+                self.navigator.select(data);
+                CommandResult::Success(message)
             },
-            CommandArg::Number(idx) => {
-                let selector = Selector::from_str(&idx.to_string()).unwrap();
-                match self.store.select_data(&selector) {
-                    Ok(data_list) => {
-                        let data = data_list[0].clone();
-                        self.navigator.select(data.clone());
-                        CommandResult::Success(format!("Selected: {}", format_vzdata(*idx as usize, data)))
-                    },
-                    Err(e) => CommandResult::Error(format!("Selection error: {}", e)),
-                }
-            },
-            _ => CommandResult::Error("Invalid selector argument".to_string()),
+            Err(e) => CommandResult::Error(format!("Selection error: {}", e)),
         }
     }
 
@@ -164,30 +522,144 @@ impl CommandExecutor {
     }
 
     fn execute_nav_goto(&mut self, command: &Command) -> CommandResult {
-        if command.args.is_empty() {
-            return CommandResult::Error("Address or selector argument required".to_string());
+        let Some(arg) = command.get_arg(0) else {
+            return CommandResult::Error("Address, selector or bookmark argument required".to_string());
+        };
+
+        // 북마크는 저장한 항목 그대로, 셀렉터는 항목의 주소로 이동 (@name은 북마크가 우선)
+        let bookmark = command.get_raw_arg(0)
+            .and_then(|name| name.strip_prefix('@'))
+            .and_then(|name| self.navigator.bookmark(name));
+        if let Some(data) = bookmark.cloned() {
+            let message = format!("Navigated to {}", data.get_display_name());
+            self.navigator.select(data);
+            return CommandResult::Success(message);
         }
 
-        match &command.args[0] {
-            CommandArg::String(addr_str) => {
-                match self.navigator.goto(addr_str) {
-                    Ok(_) => CommandResult::Success(format!("Navigated to {}", addr_str)),
-                    Err(e) => CommandResult::Error(format!("Failed to navigate: {}", e)),
-                }
+        match self.address_from_arg(arg) {
+            Ok(Some((address, _))) => {
+                self.navigator.goto(address);
+                CommandResult::Success(format!("Navigated to {}", addr_to_hex(address)))
             },
-            CommandArg::Address(addr) => {
-                let addr_str = format!("0x{:x}", addr);
-                match self.navigator.goto(&addr_str) {
-                    Ok(_) => CommandResult::Success(format!("Navigated to {}", addr_str)),
-                    Err(e) => CommandResult::Error(format!("Failed to navigate: {}", e)),
-                }
-            },
-            _ => CommandResult::Error("Invalid address argument".to_string()),
+            Ok(None) => CommandResult::Error("Invalid address argument".to_string()),
+            Err(e) => CommandResult::Error(format!("Failed to navigate: {}", e)),
+        }
+    }
+
+    fn execute_nav_back(&mut self, command: &Command) -> CommandResult {
+        let count = match history_count(command) {
+            Ok(count) => count,
+            Err(e) => return CommandResult::Error(e),
+        };
+        match self.navigator.back(count) {
+            Ok(data) => CommandResult::Success(format!("Back to {}", data.get_display_name())),
+            Err(e) => CommandResult::Error(e.to_string()),
+        }
+    }
+
+    fn execute_nav_forward(&mut self, command: &Command) -> CommandResult {
+        let count = match history_count(command) {
+            Ok(count) => count,
+            Err(e) => return CommandResult::Error(e),
+        };
+        match self.navigator.forward(count) {
+            Ok(data) => CommandResult::Success(format!("Forward to {}", data.get_display_name())),
+            Err(e) => CommandResult::Error(e.to_string()),
+        }
+    }
+
+    fn execute_nav_history(&mut self, _command: &Command) -> CommandResult {
+        let (history, current) = self.navigator.history();
+        if history.is_empty() {
+            return CommandResult::Success("Navigation history is empty".to_string());
+        }
+
+        // 현재 위치는 `*`로 표시, 인덱스는 nav: 셀렉터 인덱스
+        let lines: Vec<String> = history.iter().enumerate()
+            .map(|(idx, data)| {
+                let marker = if current == Some(idx) { "*" } else { " " };
+                format!("{} {}", marker, format_vzdata(idx, data))
+            })
+            .collect();
+        CommandResult::Success(lines.join("\n"))
+    }
+
+    fn execute_nav_mark(&mut self, command: &Command) -> CommandResult {
+        let Some(name) = command.get_raw_arg(0) else {
+            let bookmarks = self.navigator.bookmarks();
+            if bookmarks.is_empty() {
+                return CommandResult::Success("No bookmarks".to_string());
+            }
+            let lines: Vec<String> = bookmarks.iter()
+                .map(|(name, data)| format!("@{} {}", name, data.get_display_name()))
+                .collect();
+            return CommandResult::Success(lines.join("\n"));
+        };
+
+        let name = name.strip_prefix('@').unwrap_or(name);
+        if !is_bookmark_name(name) {
+            return CommandResult::Error(format!("Invalid bookmark name: {} (use letters, digits, '_', '-' and '.')", name));
+        }
+        match self.navigator.mark(name) {
+            Ok(data) => CommandResult::Success(format!("Bookmarked {} as @{}", data.get_display_name(), name)),
+            Err(e) => CommandResult::Error(format!("Failed to bookmark: {}", e)),
+        }
+    }
+
+    fn execute_nav_unmark(&mut self, command: &Command) -> CommandResult {
+        let Some(name) = command.get_raw_arg(0) else {
+            return CommandResult::Error("Bookmark name required".to_string());
+        };
+        let name = name.strip_prefix('@').unwrap_or(name);
+        match self.navigator.unmark(name) {
+            Ok(_) => CommandResult::Success(format!("Removed bookmark @{}", name)),
+            Err(e) => CommandResult::Error(format!("Failed to remove bookmark: {}", e)),
+        }
+    }
+
+    /// 로그 현재 페이지를 헤더와 함께 출력
+    fn log_page_result(&self, header: &str) -> CommandResult {
+        let mut result = format!("{}\n", header);
+        
+        for (idx, data) in self.store.get_current_log_page() {
+            result.push_str(&format!("{}\n", format_vzdata(idx, data)));
+        }
+        
+        let (page, page_count) = self.store.log_page_info();
+        if page_count > 1 {
+            result.push_str(&format!("(page {}/{}, use nxt for more)", page + 1, page_count));
+        }
+        
+        CommandResult::Success(result.trim_end().to_string())
+    }
+
+    /// 필터 식에 맞는 항목 (`invert`면 맞지 않는 항목)
+    ///
+    /// 메모리 데이터 조건(`float<30`)은 포인터 항목의 값을 에이전트로 읽어서 비교한다.
+    fn filter_items<'a>(&self, expr: &str, invert: bool, items: impl Iterator<Item = (usize, &'a VzData)>) -> Result<Vec<(usize, VzData)>, String> {
+        let filter = FilterParser::parse(expr.trim()).map_err(|e| e.to_string())?;
+        let agent = match filter.reads_memory() {
+            true => Some(self.agent().map_err(|e| e.to_string())?),
+            false => None,
+        };
+        let mut read = |data: &VzData, memory_type: MemoryType| read_item(agent.as_ref()?, data, memory_type);
+
+        let mut result = Vec::new();
+        for (idx, data) in items {
+            self.cancel.check().map_err(|e| e.to_string())?;
+            if filter.apply_with(data, &mut read) != invert {
+                result.push((idx, data.clone()));
+            }
         }
+        Ok(result)
     }
 
     // Log 명령어 실행 메서드
-    fn execute_log_list(&mut self, _command: &Command) -> CommandResult {
+    fn execute_log_list(&mut self, command: &Command) -> CommandResult {
+        if command.has_args() {
+            return self.execute_log_grep(command);
+        }
+
         let log_items = self.store.get_current_log_page();
         
         if log_items.is_empty() {
@@ -251,6 +723,36 @@ impl CommandExecutor {
         }
     }
 
+    fn execute_log_grep(&mut self, command: &Command) -> CommandResult {
+        let expr = command.raw_args.join(" ");
+        let invert = command.has_flag("-v", "--invert");
+
+        // 파이프 입력이 있으면 로그 대신 입력 항목을 거름
+        let matches = match &self.pipe_input {
+            Some(input) => self.filter_items(&expr, invert, input.items.iter().map(|(idx, data)| (*idx, data))),
+            None => self.filter_items(&expr, invert, self.store.get_log().iter().enumerate()),
+        };
+
+        match matches {
+            Ok(items) if items.is_empty() => CommandResult::Success(format!("No items match '{}'", expr)),
+            Ok(items) => CommandResult::DataList(items),
+            Err(e) => CommandResult::Error(format!("Filter error: {}", e)),
+        }
+    }
+
+    fn execute_log_filter(&mut self, command: &Command) -> CommandResult {
+        let expr = command.raw_args.join(" ");
+        let invert = command.has_flag("-v", "--invert");
+        let total = self.store.get_log().len();
+
+        let indices: Vec<usize> = match self.filter_items(&expr, invert, self.store.get_log().iter().enumerate()) {
+            Ok(items) => items.into_iter().map(|(idx, _)| idx).collect(),
+            Err(e) => return CommandResult::Error(format!("Filter error: {}", e)),
+        };
+        let kept = self.store.retain_log(&indices);
+        self.log_page_result(&format!("Kept {} of {} items matching '{}'", kept, total, expr))
+    }
+
     // Library 명령어 실행 메서드
     fn execute_lib_list(&mut self, _command: &Command) -> CommandResult {
         let lib_items = self.store.get_current_lib_page();
@@ -269,18 +771,17 @@ impl CommandExecutor {
     }
 
     fn execute_lib_save(&mut self, command: &Command) -> CommandResult {
-        if command.args.is_empty() {
-            return CommandResult::Error("Selector argument required".to_string());
-        }
+        let (selector, filter) = match selector_arg(command, true) {
+            Ok(selector) => selector,
+            Err(e) => return CommandResult::Error(e),
+        };
 
-        match &command.args[0] {
-            CommandArg::Selector(selector) => {
-                match self.store.save_to_lib(selector) {
-                    Ok(count) => CommandResult::Success(format!("Saved {} items to library", count)),
-                    Err(e) => CommandResult::Error(format!("Failed to save to library: {}", e)),
-                }
+        match self.select(&selector, SelectorSource::Log) {
+            Ok((_, items)) => {
+                let count = self.store.save_to_lib(items.into_iter().map(|(_, data)| data).collect());
+                CommandResult::Success(format!("Saved {} items{} to library", count, matching(filter.as_deref())))
             },
-            _ => CommandResult::Error("Invalid selector argument".to_string()),
+            Err(e) => CommandResult::Error(format!("Failed to save to library: {}", e)),
         }
     }
 
@@ -306,18 +807,20 @@ impl CommandExecutor {
     }
 
     fn execute_lib_remove(&mut self, command: &Command) -> CommandResult {
-        if command.args.is_empty() {
-            return CommandResult::Error("Selector argument required".to_string());
+        let (selector, filter) = match selector_arg(command, true) {
+            Ok(selector) => selector,
+            Err(e) => return CommandResult::Error(e),
+        };
+        if selector.source.is_some_and(|source| source != SelectorSource::Lib) {
+            return CommandResult::Error("Only library items can be removed (use lib:<index>)".to_string());
         }
 
-        match &command.args[0] {
-            CommandArg::Selector(selector) => {
-                match self.store.remove_from_lib(selector) {
-                    Ok(count) => CommandResult::Success(format!("Removed {} items from library", count)),
-                    Err(e) => CommandResult::Error(format!("Failed to remove from library: {}", e)),
-                }
+        match self.resolve_selector(&selector, SelectorSource::Lib) {
+            Ok((_, indices)) => {
+                let count = self.store.remove_from_lib(&indices);
+                CommandResult::Success(format!("Removed {} items{} from library", count, matching(filter.as_deref())))
             },
-            _ => CommandResult::Error("Invalid selector argument".to_string()),
+            Err(e) => CommandResult::Error(format!("Failed to remove from library: {}", e)),
         }
     }
 
@@ -343,22 +846,25 @@ impl CommandExecutor {
             return CommandResult::Error("Selector and label arguments required".to_string());
         }
 
-        let selector = match &command.args[0] {
-            CommandArg::Selector(selector) => selector,
-            _ => return CommandResult::Error("Invalid selector argument".to_string()),
+        let (selector, _) = match selector_arg(command, false) {
+            Ok(selector) => selector,
+            Err(e) => return CommandResult::Error(e),
         };
 
-        let label = match &command.args[1] {
-            CommandArg::String(label) => label,
-            _ => return CommandResult::Error("Invalid label argument".to_string()),
+        let label = match command.get_raw_arg(1) {
+            Some(label) => label,
+            None => return CommandResult::Error("Invalid label argument".to_string()),
         };
 
-        match self.store.get_data_mut(selector) {
+        let data_list = self.resolve_selector(&selector, SelectorSource::Log)
+            .and_then(|(source, indices)| self.store.get_data_mut(source, &indices));
+        match data_list {
             Ok(data_list) => {
+                let count = data_list.len();
                 for data in data_list {
-                    data.set_label(label.clone());
+                    data.set_label(label.to_string());
                 }
-                CommandResult::Success(format!("Applied label '{}' to {} items", label, data_list.len()))
+                CommandResult::Success(format!("Applied label '{}' to {} items", label, count))
             },
             Err(e) => CommandResult::Error(format!("Failed to label items: {}", e)),
         }
@@ -369,22 +875,25 @@ impl CommandExecutor {
             return CommandResult::Error("Selector and tag arguments required".to_string());
         }
 
-        let selector = match &command.args[0] {
-            CommandArg::Selector(selector) => selector,
-            _ => return CommandResult::Error("Invalid selector argument".to_string()),
+        let (selector, _) = match selector_arg(command, false) {
+            Ok(selector) => selector,
+            Err(e) => return CommandResult::Error(e),
         };
 
-        let tag = match &command.args[1] {
-            CommandArg::String(tag) => tag,
-            _ => return CommandResult::Error("Invalid tag argument".to_string()),
+        let tag = match command.get_raw_arg(1) {
+            Some(tag) => tag,
+            None => return CommandResult::Error("Invalid tag argument".to_string()),
         };
 
-        match self.store.get_data_mut(selector) {
+        let data_list = self.resolve_selector(&selector, SelectorSource::Log)
+            .and_then(|(source, indices)| self.store.get_data_mut(source, &indices));
+        match data_list {
             Ok(data_list) => {
+                let count = data_list.len();
                 for data in data_list {
-                    data.add_tag(tag.clone());
+                    data.add_tag(tag.to_string());
                 }
-                CommandResult::Success(format!("Added tag '{}' to {} items", tag, data_list.len()))
+                CommandResult::Success(format!("Added tag '{}' to {} items", tag, count))
             },
             Err(e) => CommandResult::Error(format!("Failed to tag items: {}", e)),
         }
@@ -395,17 +904,19 @@ impl CommandExecutor {
             return CommandResult::Error("Selector and tag arguments required".to_string());
         }
 
-        let selector = match &command.args[0] {
-            CommandArg::Selector(selector) => selector,
-            _ => return CommandResult::Error("Invalid selector argument".to_string()),
+        let (selector, _) = match selector_arg(command, false) {
+            Ok(selector) => selector,
+            Err(e) => return CommandResult::Error(e),
         };
 
-        let tag = match &command.args[1] {
-            CommandArg::String(tag) => tag,
-            _ => return CommandResult::Error("Invalid tag argument".to_string()),
+        let tag = match command.get_raw_arg(1) {
+            Some(tag) => tag,
+            None => return CommandResult::Error("Invalid tag argument".to_string()),
         };
 
-        match self.store.get_data_mut(selector) {
+        let data_list = self.resolve_selector(&selector, SelectorSource::Log)
+            .and_then(|(source, indices)| self.store.get_data_mut(source, &indices));
+        match data_list {
             Ok(data_list) => {
                 let mut removed = 0;
                 for data in data_list {
@@ -424,18 +935,18 @@ impl CommandExecutor {
             return CommandResult::Error("Selector argument required".to_string());
         }
 
-        let selector = match &command.args[0] {
-            CommandArg::Selector(selector) => selector,
-            _ => return CommandResult::Error("Invalid selector argument".to_string()),
+        let (selector, _) = match selector_arg(command, false) {
+            Ok(selector) => selector,
+            Err(e) => return CommandResult::Error(e),
         };
 
-        match self.store.select_data(selector) {
-            Ok(data_list) => {
+        match self.select(&selector, SelectorSource::Log) {
+            Ok((_, data_list)) => {
                 let mut result = String::new();
                 
-                for (i, data) in data_list.iter().enumerate() {
-                    let tags: Vec<_> = data.tags.iter().collect();
-                    result.push_str(&format!("Item {}: [{}]\n", i, tags.join(", ")));
+                for (idx, data) in &data_list {
+                    let tags: Vec<&str> = data.tags.iter().map(|t| t.as_str()).collect();
+                    result.push_str(&format!("Item {}: [{}]\n", idx, tags.join(", ")));
                 }
                 
                 if result.is_empty() {
@@ -447,4 +958,1665 @@ impl CommandExecutor {
             Err(e) => CommandResult::Error(format!("Failed to get tags: {}", e)),
         }
     }
-} 
\ No newline at end of file
+
+    // List 명령어 실행 메서드
+    fn execute_list_class(&mut self, command: &Command) -> CommandResult {
+        let pattern = command.get_raw_arg(0).map(NamePattern::parse);
+        
+        let classes = match self.agent().and_then(|agent| agent.query_classes(pattern.as_ref())) {
+            Ok(classes) => classes,
+            Err(e) => return CommandResult::Error(format!("Failed to list classes: {}", e)),
+        };
+        
+        let count = classes.len();
+        self.store.replace_log(classes);
+        self.log_page_result(&format!("Found {} classes", count))
+    }
+
+    fn execute_list_method(&mut self, command: &Command) -> CommandResult {
+        // 대상 클래스: 셀렉터 인자가 있으면 우선, 없으면 내비게이터 선택 데이터
+        let (target, key_index) = match command.get_arg(0).and_then(|arg| arg.to_selector()) {
+            Some(selector) => match self.select_one(&selector, "class") {
+                Ok((_, data)) => (data, 1),
+                Err(e) => return CommandResult::Error(e),
+            },
+            None => match self.navigator.get_selected() {
+                Some(data) => (data.clone(), 0),
+                None => return CommandResult::Error("Class selector argument required".to_string()),
+            },
+        };
+        
+        let class_name = if let Some(class) = target.as_class() {
+            class.name.clone()
+        } else if let Some(method) = target.as_method() {
+            method.class_name.clone()
+        } else {
+            return CommandResult::Error(format!("{} is not a class", target.get_display_name()));
+        };
+        
+        let pattern = command.get_raw_arg(key_index).map(NamePattern::parse);
+        let inherited = command.has_flag("-i", "--inherited");
+        
+        let (methods, hierarchy) = match self.agent() {
+            Ok(agent) => match agent.query_methods(&class_name, inherited, pattern.as_ref()) {
+                Ok(methods) => (methods, agent.class_hierarchy(&class_name).unwrap_or_default()),
+                Err(e) => return CommandResult::Error(format!("Failed to list methods: {}", e)),
+            },
+            Err(e) => return CommandResult::Error(format!("Failed to list methods: {}", e)),
+        };
+        
+        let count = methods.len();
+        self.store.replace_log(methods);
+        self.log_page_result(&format_class_header(&class_name, count, &hierarchy))
+    }
+
+    fn execute_list_module(&mut self, command: &Command) -> CommandResult {
+        let pattern = command.get_raw_arg(0).map(NamePattern::parse);
+        
+        let modules = match self.agent().and_then(|agent| agent.query_modules(pattern.as_ref())) {
+            Ok(modules) => modules,
+            Err(e) => return CommandResult::Error(format!("Failed to list modules: {}", e)),
+        };
+        
+        let count = modules.len();
+        self.store.replace_log(modules);
+        self.log_page_result(&format!("Found {} modules", count))
+    }
+
+    /// 대상 모듈 이름과 검색어 인자 위치 결정
+    ///
+    /// 셀렉터 인자 > 내비게이터의 VzModule > 모듈 이름 문자열 순으로 해석한다.
+    fn resolve_module(&self, command: &Command) -> Result<(String, usize), String> {
+        if let Some(selector) = command.get_arg(0).and_then(|arg| arg.to_selector()) {
+            let (_, data) = self.select_one(&selector, "module")?;
+            return match data.as_module() {
+                Some(module) => Ok((module.name.clone(), 1)),
+                None => Err(format!("{} is not a module", data.get_display_name())),
+            };
+        }
+        
+        // 모듈 이름을 직접 지정하면 선택된 모듈보다 우선 (선택된 모듈이 있으면 첫 인자는
+        // 뒤에 패턴이 오거나 로드된 모듈 이름과 같을 때만 모듈 이름, 아니면 패턴)
+        let selected = self.navigator.get_selected().and_then(|data| data.as_module());
+        if let Some(name) = command.get_raw_arg(0) {
+            if selected.is_none() || command.get_raw_arg(1).is_some() || self.is_loaded_module(name) {
+                return Ok((name.to_string(), 1));
+            }
+        }
+        
+        match selected {
+            Some(module) => Ok((module.name.clone(), 0)),
+            None => Err("Module selector or name argument required".to_string()),
+        }
+    }
+
+    /// 이름이 정확히 같은 모듈이 로드되어 있는지
+    fn is_loaded_module(&self, name: &str) -> bool {
+        self.agent()
+            .and_then(|agent| agent.query_modules(Some(&NamePattern::parse(name))))
+            .is_ok_and(|modules| modules.iter().any(|data| data.as_module().is_some_and(|module| module.name == name)))
+    }
+
+    fn execute_list_module_items(&mut self, command: &Command, items: ModuleItems) -> CommandResult {
+        let (module_name, key_index) = match self.resolve_module(command) {
+            Ok(resolved) => resolved,
+            Err(e) => return CommandResult::Error(e),
+        };
+        let pattern = command.get_raw_arg(key_index).map(NamePattern::parse);
+        
+        let result = self.agent().and_then(|agent| match items {
+            ModuleItems::Exports => agent.query_exports(&module_name, pattern.as_ref()),
+            ModuleItems::Imports => agent.query_imports(&module_name, pattern.as_ref()),
+            ModuleItems::Symbols => agent.query_symbols(&module_name, pattern.as_ref()),
+        });
+        
+        let data = match result {
+            Ok(data) => data,
+            Err(e) => return CommandResult::Error(format!("Failed to list {}: {}", items.name(), e)),
+        };
+        
+        let count = data.len();
+        self.store.replace_log(data);
+        self.log_page_result(&format!("Found {} {} in {}", count, items.name(), module_name))
+    }
+
+    fn execute_list_range(&mut self, command: &Command) -> CommandResult {
+        let protection = command.get_raw_arg(0).unwrap_or("r--");
+        if !is_protection(protection) {
+            return CommandResult::Error(format!("Invalid protection: {} (expected e.g. r-x)", protection));
+        }
+        
+        let ranges = match self.agent().and_then(|agent| agent.query_ranges(protection)) {
+            Ok(ranges) => ranges,
+            Err(e) => return CommandResult::Error(format!("Failed to list ranges: {}", e)),
+        };
+        
+        let count = ranges.len();
+        self.store.replace_log(ranges);
+        self.log_page_result(&format!("Found {} ranges ({})", count, protection))
+    }
+
+    /// 메모리 명령어의 대상 주소 결정
+    ///
+    /// 주소 인자 > 셀렉터 인자 > 내비게이터 선택 데이터 순으로 해석한다.
+    /// 반환값: (주소, 대상 데이터의 메모리 타입, 다음 인자 위치)
+    fn resolve_address(&self, command: &Command) -> Result<(u64, Option<MemoryType>, usize), String> {
+        if let Some(arg) = command.get_arg(0) {
+            if let Some((address, memory_type)) = self.address_from_arg(arg)? {
+                return Ok((address, memory_type, 1));
+            }
+        }
+        
+        self.navigator_address().map(|(address, memory_type)| (address, memory_type, 0))
+    }
+
+    /// 주소 또는 셀렉터 인자에서 주소 가져오기 (그 외 인자는 None)
+    fn address_from_arg(&self, arg: &CommandArg) -> Result<Option<(u64, Option<MemoryType>)>, String> {
+        if let CommandArg::Address(addr) = arg {
+            return Ok(Some((*addr, None)));
+        }
+        
+        let selector = match arg.to_selector() {
+            Some(selector) => selector,
+            None => return Ok(None),
+        };
+        let (_, data) = self.select_one(&selector, "address")?;
+        match data.get_address() {
+            Some(address) => Ok(Some((address, data.as_pointer().map(|p| p.memory_type)))),
+            None => Err(format!("{} has no address", data.get_display_name())),
+        }
+    }
+
+    /// 내비게이터 선택 데이터의 주소
+    fn navigator_address(&self) -> Result<(u64, Option<MemoryType>), String> {
+        match self.navigator.get_selected() {
+            Some(data) => match data.get_address() {
+                Some(address) => Ok((address, data.as_pointer().map(|p| p.memory_type))),
+                None => Err(format!("{} has no address", data.get_display_name())),
+            },
+            None => Err("Address or selector argument required".to_string()),
+        }
+    }
+
+    /// 에이전트에 쌓인 추적 이벤트를 호출 기록에 반영
+    fn sync_trace_events(&mut self) -> VlitzResult<()> {
+        if self.monitor.traces().is_empty() {
+            return Ok(());
+        }
+        let batch = self.agent()?.drain_trace_events()?;
+        self.trace.ingest(batch);
+        
+        let trace = &self.trace;
+        self.monitor.update_trace_calls(|id| trace.call_count(id));
+        Ok(())
+    }
+
+    /// 에이전트에서 새로 수집된 기본 블록을 커버리지에 반영
+    fn sync_coverage(&mut self) -> VlitzResult<()> {
+        if self.coverage.sessions().is_empty() {
+            return Ok(());
+        }
+        let batches = self.agent()?.drain_coverage()?;
+        self.coverage.ingest(batches);
+        Ok(())
+    }
+
+    /// 에이전트 잠금 테이블의 쓰기 횟수/오류를 mem list에 반영
+    fn sync_locks(&mut self) -> VlitzResult<()> {
+        if self.monitor.locks().is_empty() {
+            return Ok(());
+        }
+        let infos = self.agent()?.list_locks()?;
+        self.monitor.update_locks(&infos);
+        Ok(())
+    }
+
+    /// 에이전트에 쌓인 워치 이벤트를 mem list 항목으로 가져오기
+    fn sync_watch_events(&mut self) -> VlitzResult<()> {
+        if self.monitor.watches().is_empty() {
+            return Ok(());
+        }
+        let events = self.agent()?.drain_watch_events()?;
+        self.monitor.dispatch_watch_events(events);
+        Ok(())
+    }
+
+    // Memory 명령어 실행 메서드
+    fn execute_mem_list(&mut self, _command: &Command) -> CommandResult {
+        if let Err(e) = self.sync_watch_events() {
+            return CommandResult::Error(format!("Failed to fetch watch events: {}", e));
+        }
+        if let Err(e) = self.sync_locks() {
+            return CommandResult::Error(format!("Failed to fetch locks: {}", e));
+        }
+        if let Err(e) = self.sync_trace_events() {
+            return CommandResult::Error(format!("Failed to fetch trace events: {}", e));
+        }
+        
+        let entries = self.monitor.entries();
+        if entries.is_empty() {
+            return CommandResult::Success("Memory list is empty".to_string());
+        }
+        
+        let lines: Vec<String> = entries.iter()
+            .enumerate()
+            .map(|(idx, entry)| entry.format(idx))
+            .collect();
+        CommandResult::Success(lines.join("\n"))
+    }
+
+    fn execute_mem_watch(&mut self, command: &Command) -> CommandResult {
+        let (address, data_type, type_index) = match self.resolve_address(command) {
+            Ok(resolved) => resolved,
+            Err(e) => return CommandResult::Error(e),
+        };
+        
+        let memory_type = match command.get_raw_arg(type_index) {
+            Some(name) => match MemoryType::from_name(name) {
+                Some(memory_type) => memory_type,
+                None => return CommandResult::Error(format!("Unknown memory type: {}", name)),
+            },
+            None => data_type.unwrap_or(MemoryType::UInt),
+        };
+        let size = match memory_type.size() {
+            0 => 16,
+            size => size,
+        };
+        
+        let handle = match self.agent().and_then(|agent| agent.add_watch(address, memory_type, size)) {
+            Ok(handle) => handle,
+            Err(e) => return CommandResult::Error(format!("Failed to watch {}: {}", addr_to_hex(address), e)),
+        };
+        
+        let entry = MonitorEntry::Watch(WatchEntry {
+            id: handle.id,
+            address,
+            memory_type,
+            mode: handle.mode,
+            events: Vec::new(),
+        });
+        let message = format!("Watching {} (value: {})", entry.format(self.monitor.entries().len()), handle.value);
+        self.monitor.add(entry);
+        CommandResult::Success(message)
+    }
+
+    fn execute_mem_unwatch(&mut self, command: &Command) -> CommandResult {
+        let selector = match command.get_arg(0).and_then(|arg| arg.to_selector()) {
+            Some(selector) => selector,
+            None => return CommandResult::Error("Selector argument required".to_string()),
+        };
+        
+        let indices = match self.monitor.select_indices(&selector) {
+            Ok(indices) => indices,
+            Err(e) => return CommandResult::Error(format!("Selection error: {}", e)),
+        };
+        
+        let watch_indices: Vec<usize> = indices.into_iter()
+            .filter(|idx| self.monitor.entries()[*idx].as_watch().is_some())
+            .collect();
+        if watch_indices.is_empty() {
+            return CommandResult::Error("No watches selected".to_string());
+        }
+        
+        let agent = match self.agent() {
+            Ok(agent) => agent,
+            Err(e) => return CommandResult::Error(format!("Failed to unwatch: {}", e)),
+        };
+        for idx in &watch_indices {
+            if let Some(watch) = self.monitor.entries()[*idx].as_watch() {
+                if let Err(e) = agent.remove_watch(watch.id) {
+                    return CommandResult::Error(format!("Failed to unwatch {}: {}", addr_to_hex(watch.address), e));
+                }
+            }
+        }
+        
+        let removed = self.monitor.remove(&watch_indices);
+        CommandResult::Success(format!("Removed {} watches", removed.len()))
+    }
+
+    fn execute_mem_lock(&mut self, command: &Command) -> CommandResult {
+        match self.lock_memory(command) {
+            Ok(message) => CommandResult::Success(message),
+            Err(e) => CommandResult::Error(e),
+        }
+    }
+
+    /// mem lock 인자 해석 후 에이전트 잠금 테이블에 등록
+    fn lock_memory(&mut self, command: &Command) -> Result<String, String> {
+        const USAGE: &str = "Usage: mem lock [address|selector] <value> [type] [below|above <threshold>] [every <ms>]";
+        
+        // 위치 인자: [대상] <값> [타입], 이후 키워드 옵션
+        let keyword_pos = command.raw_args.iter()
+            .position(|arg| matches!(arg.as_str(), "below" | "above" | "every"))
+            .unwrap_or(command.raw_args.len());
+        let positional = &command.raw_args[..keyword_pos];
+        let explicit_type = positional.last().and_then(|name| MemoryType::from_name(name));
+        let value_count = positional.len() - explicit_type.is_some() as usize;
+        
+        let (address, data_type) = match value_count {
+            1 => self.navigator_address(),
+            2 => match self.address_from_arg(&command.args[0]) {
+                Ok(Some(resolved)) => Ok(resolved),
+                Ok(None) => Err(format!("Invalid address argument: {}", positional[0])),
+                Err(e) => Err(e),
+            },
+            _ => Err(USAGE.to_string()),
+        }?;
+        
+        let raw_value = positional[value_count - 1].as_str();
+        let memory_type = explicit_type.or(data_type).unwrap_or_else(|| infer_memory_type(raw_value));
+        let value = MemoryValue::parse(raw_value, memory_type)
+            .map_err(|e| format!("Invalid {} value: {}", memory_type, e))?;
+        
+        let mut options = LockOptions::default();
+        let mut keywords = command.raw_args[keyword_pos..].iter();
+        while let Some(keyword) = keywords.next() {
+            let operand = keywords.next()
+                .ok_or_else(|| format!("Missing value for '{}'", keyword))?;
+            match keyword.as_str() {
+                "below" | "above" => {
+                    if !memory_type.is_numeric() {
+                        return Err(format!("'{}' requires a numeric type, got {}", keyword, memory_type));
+                    }
+                    options.mode = if keyword == "below" { LockMode::Below } else { LockMode::Above };
+                    options.threshold = Some(operand.parse()
+                        .map_err(|_| format!("Invalid threshold: {}", operand))?);
+                },
+                "every" => {
+                    options.interval_ms = match operand.trim_end_matches("ms").parse() {
+                        Ok(ms) if ms > 0 => ms,
+                        _ => return Err(format!("Invalid interval: {}", operand)),
+                    };
+                },
+                _ => return Err(USAGE.to_string()),
+            }
+        }
+        
+        let agent = self.agent().map_err(|e| format!("Failed to lock: {}", e))?;
+        let info = agent.add_lock(address, &value, &options)
+            .map_err(|e| format!("Failed to lock {}: {}", addr_to_hex(address), e))?;
+        if let Some(error) = &info.error {
+            let _ = agent.remove_lock(info.id);
+            return Err(format!("Failed to lock {}: {}", addr_to_hex(address), error));
+        }
+        
+        let entry = MonitorEntry::Lock(LockEntry {
+            id: info.id,
+            address,
+            memory_type,
+            value: info.value,
+            options,
+            writes: info.writes,
+            error: None,
+        });
+        let mut message = format!("Locked {}", entry.format(self.monitor.entries().len()));
+        if let Some(original) = info.original {
+            message.push_str(&format!(" (was {})", original));
+        }
+        self.monitor.add(entry);
+        Ok(message)
+    }
+
+    fn execute_mem_unlock(&mut self, command: &Command) -> CommandResult {
+        let selector = match command.get_arg(0) {
+            None => Selector::all(),
+            Some(arg) => match arg.to_selector() {
+                Some(selector) => selector,
+                None => return CommandResult::Error("Invalid selector argument".to_string()),
+            },
+        };
+        
+        // 전체 해제는 추적되지 않은 잠금까지 에이전트에서 한 번에 제거
+        if selector.is_all() {
+            return match self.agent().and_then(|agent| agent.remove_all_locks()) {
+                Ok(count) => {
+                    self.monitor.clear_locks();
+                    CommandResult::Success(format!("Removed {} locks", count))
+                },
+                Err(e) => CommandResult::Error(format!("Failed to unlock: {}", e)),
+            };
+        }
+        
+        let indices = match self.monitor.select_indices(&selector) {
+            Ok(indices) => indices,
+            Err(e) => return CommandResult::Error(format!("Selection error: {}", e)),
+        };
+        let lock_indices: Vec<usize> = indices.into_iter()
+            .filter(|idx| self.monitor.entries()[*idx].as_lock().is_some())
+            .collect();
+        if lock_indices.is_empty() {
+            return CommandResult::Error("No locks selected".to_string());
+        }
+        
+        let agent = match self.agent() {
+            Ok(agent) => agent,
+            Err(e) => return CommandResult::Error(format!("Failed to unlock: {}", e)),
+        };
+        for idx in &lock_indices {
+            if let Some(lock) = self.monitor.entries()[*idx].as_lock() {
+                if let Err(e) = agent.remove_lock(lock.id) {
+                    return CommandResult::Error(format!("Failed to unlock {}: {}", addr_to_hex(lock.address), e));
+                }
+            }
+        }
+        
+        let removed = self.monitor.remove(&lock_indices);
+        CommandResult::Success(format!("Removed {} locks", removed.len()))
+    }
+
+    fn execute_mem_locks(&mut self, _command: &Command) -> CommandResult {
+        if let Err(e) = self.sync_locks() {
+            return CommandResult::Error(format!("Failed to fetch locks: {}", e));
+        }
+        
+        let locks = self.monitor.locks();
+        if locks.is_empty() {
+            return CommandResult::Success("No locks".to_string());
+        }
+        
+        let lines: Vec<String> = locks.into_iter()
+            .map(|(idx, _)| self.monitor.entries()[idx].format(idx))
+            .collect();
+        CommandResult::Success(lines.join("\n"))
+    }
+
+    fn execute_mem_trace(&mut self, command: &Command) -> CommandResult {
+        // 모듈 대상이면 패턴에 맞는 함수 익스포트 전체를 추적
+        let (module_name, key_index) = if command.has_flag("-m", "--module") {
+            match self.resolve_module(command) {
+                Ok(resolved) => resolved,
+                Err(e) => return CommandResult::Error(e),
+            }
+        } else {
+            let (data, key_index) = match self.resolve_data(command) {
+                Ok(resolved) => resolved,
+                Err(e) => return CommandResult::Error(e),
+            };
+            if let Some(module) = data.as_module() {
+                (module.name.clone(), key_index)
+            } else {
+                let address = match data.get_address() {
+                    Some(address) => address,
+                    None => return CommandResult::Error(format!("{} has no address", data.get_display_name())),
+                };
+                let name = data.get_name().map(|name| name.to_string()).unwrap_or_else(|| addr_to_hex(address));
+                let result = self.agent().and_then(|agent| agent.add_trace(address, &name, DEFAULT_TRACE_ARG_COUNT));
+                return self.trace_result(result.map(|handle| vec![handle]));
+            }
+        };
+        
+        let pattern = command.get_raw_arg(key_index).map(NamePattern::parse);
+        let result = self.agent().and_then(|agent| agent.trace_module(&module_name, pattern.as_ref(), DEFAULT_TRACE_ARG_COUNT));
+        self.trace_result(result)
+    }
+
+    /// 추적 등록 결과를 mem list와 호출 기록에 반영
+    fn trace_result(&mut self, result: VlitzResult<Vec<TraceHandle>>) -> CommandResult {
+        let handles = match result {
+            Ok(handles) if handles.is_empty() => return CommandResult::Error("No functions matched".to_string()),
+            Ok(handles) => handles,
+            Err(e) => return CommandResult::Error(format!("Failed to trace: {}", e)),
+        };
+        
+        let mut lines = Vec::with_capacity(handles.len());
+        for handle in handles {
+            let address = match hex_to_addr(&handle.address) {
+                Ok(address) => address,
+                Err(e) => return CommandResult::Error(format!("Invalid trace address: {}", e)),
+            };
+            self.trace.register(handle.id, handle.name.clone());
+            
+            let entry = MonitorEntry::Trace(TraceEntry { id: handle.id, address, name: handle.name, calls: 0 });
+            lines.push(entry.format(self.monitor.entries().len()));
+            self.monitor.add(entry);
+        }
+        
+        CommandResult::Success(format!("Tracing {} functions\n{}", lines.len(), lines.join("\n")))
+    }
+
+    fn execute_mem_untrace(&mut self, command: &Command) -> CommandResult {
+        let selector = match command.get_arg(0) {
+            None => Selector::all(),
+            Some(arg) => match arg.to_selector() {
+                Some(selector) => selector,
+                None => return CommandResult::Error("Invalid selector argument".to_string()),
+            },
+        };
+        
+        // 남은 이벤트를 먼저 가져와 해제 직전 호출까지 기록
+        if let Err(e) = self.sync_trace_events() {
+            return CommandResult::Error(format!("Failed to fetch trace events: {}", e));
+        }
+        
+        let indices = match self.monitor.select_indices(&selector) {
+            Ok(indices) => indices,
+            Err(e) => return CommandResult::Error(format!("Selection error: {}", e)),
+        };
+        let trace_indices: Vec<usize> = indices.into_iter()
+            .filter(|idx| self.monitor.entries()[*idx].as_trace().is_some())
+            .collect();
+        if trace_indices.is_empty() {
+            return CommandResult::Error("No traces selected".to_string());
+        }
+        
+        let agent = match self.agent() {
+            Ok(agent) => agent,
+            Err(e) => return CommandResult::Error(format!("Failed to untrace: {}", e)),
+        };
+        for idx in &trace_indices {
+            if let Some(trace) = self.monitor.entries()[*idx].as_trace() {
+                if let Err(e) = agent.remove_trace(trace.id) {
+                    return CommandResult::Error(format!("Failed to untrace {}: {}", trace.name, e));
+                }
+            }
+        }
+        
+        let removed = self.monitor.remove(&trace_indices);
+        CommandResult::Success(format!("Removed {} traces", removed.len()))
+    }
+
+    fn execute_mem_events(&mut self, command: &Command) -> CommandResult {
+        if let Err(e) = self.sync_watch_events() {
+            return CommandResult::Error(format!("Failed to fetch watch events: {}", e));
+        }
+        
+        let indices = match command.get_arg(0).and_then(|arg| arg.to_selector()) {
+            Some(selector) => match self.monitor.select_indices(&selector) {
+                Ok(indices) => Some(indices),
+                Err(e) => return CommandResult::Error(format!("Selection error: {}", e)),
+            },
+            None => None,
+        };
+        
+        let mut result = String::new();
+        for (idx, watch) in self.monitor.watches() {
+            if indices.as_ref().is_some_and(|indices| !indices.contains(&idx)) {
+                continue;
+            }
+            
+            result.push_str(&format!("[{}] {} ({} events)\n", idx, addr_to_hex(watch.address), watch.events.len()));
+            for event in &watch.events {
+                result.push_str(&format!("  {}\n", event.summary()));
+                for frame in &event.backtrace {
+                    result.push_str(&format!("      {}\n", frame));
+                }
+            }
+        }
+        
+        if result.is_empty() {
+            return CommandResult::Success("No watches".to_string());
+        }
+        CommandResult::Success(result.trim_end().to_string())
+    }
+
+    fn execute_preset_list(&mut self, _command: &Command) -> CommandResult {
+        let mut result = String::new();
+        for (preset, applied) in self.presets.list() {
+            let state = if applied { "[applied]" } else { "" };
+            result.push_str(&format!("{:<12} {:<10} {}\n", preset.name(), state, preset.description()));
+        }
+        
+        if result.is_empty() {
+            return CommandResult::Success("No presets".to_string());
+        }
+        CommandResult::Success(result.trim_end().to_string())
+    }
+
+    fn execute_preset_apply(&mut self, command: &Command) -> CommandResult {
+        let name = match command.get_raw_arg(0) {
+            Some(name) => name,
+            None => return CommandResult::Error("Preset name argument required".to_string()),
+        };
+        let overrides = match parse_overrides(&command.raw_args[1..]) {
+            Ok(overrides) => overrides,
+            Err(e) => return CommandResult::Error(e.to_string()),
+        };
+        let backend = match self.backend.as_deref_mut() {
+            Some(backend) => backend,
+            None => return CommandResult::Error("Not attached to a process".to_string()),
+        };
+        
+        match self.presets.apply(name, backend, &overrides) {
+            Ok(report) => CommandResult::Success(format!(
+                "Applied preset {} ({}/{} hooks installed)\n{}",
+                name, report.installed(), report.hooks.len(), report.format()
+            ).trim_end().to_string()),
+            Err(e) => CommandResult::Error(format!("Failed to apply preset: {}", e)),
+        }
+    }
+
+    fn execute_preset_revert(&mut self, command: &Command) -> CommandResult {
+        let name = match command.get_raw_arg(0) {
+            Some(name) => name,
+            None => return CommandResult::Error("Preset name argument required".to_string()),
+        };
+        let backend = match self.backend.as_deref_mut() {
+            Some(backend) => backend,
+            None => return CommandResult::Error("Not attached to a process".to_string()),
+        };
+        
+        if name == "all" {
+            return match self.presets.revert_all(backend) {
+                Ok(names) => CommandResult::Success(format!("Reverted {} presets", names.len())),
+                Err(e) => CommandResult::Error(format!("Failed to revert presets: {}", e)),
+            };
+        }
+        match self.presets.revert(name, backend) {
+            Ok(()) => CommandResult::Success(format!("Reverted preset {}", name)),
+            Err(e) => CommandResult::Error(format!("Failed to revert preset: {}", e)),
+        }
+    }
+
+    fn execute_preset_status(&mut self, command: &Command) -> CommandResult {
+        let backend = match self.backend.as_deref() {
+            Some(backend) => backend,
+            None => return CommandResult::Error("Not attached to a process".to_string()),
+        };
+        let names = match command.get_raw_arg(0) {
+            Some(name) => vec![name.to_string()],
+            None => self.presets.applied(),
+        };
+        if names.is_empty() {
+            return CommandResult::Success("No presets applied".to_string());
+        }
+        
+        let mut result = String::new();
+        for name in names {
+            match self.presets.status(&name, backend) {
+                Ok(report) => result.push_str(&format!(
+                    "{} ({}/{} hooks installed)\n{}\n", name, report.installed(), report.hooks.len(), report.format()
+                )),
+                Err(e) => return CommandResult::Error(format!("Failed to get preset status: {}", e)),
+            }
+        }
+        CommandResult::Success(result.trim_end().to_string())
+    }
+
+    fn execute_preset_set(&mut self, command: &Command) -> CommandResult {
+        let (name, key, value) = match (command.get_raw_arg(0), command.get_raw_arg(1), command.get_raw_arg(2)) {
+            (Some(name), Some(key), Some(value)) => (name, key, value),
+            _ => return CommandResult::Error("Usage: preset set <name> <option> <value>".to_string()),
+        };
+        
+        let applied = self.presets.is_applied(name);
+        let backend = self.backend.as_deref_mut().map(|backend| backend as &mut dyn RpcBackend);
+        match self.presets.set_option(name, key, value, backend) {
+            Ok(value) if applied => CommandResult::Success(format!("Set {}.{} = {} (applied)", name, key, value)),
+            Ok(value) => CommandResult::Success(format!("Set {}.{} = {}", name, key, value)),
+            Err(e) => CommandResult::Error(format!("Failed to set option: {}", e)),
+        }
+    }
+
+    fn execute_preset_info(&mut self, command: &Command) -> CommandResult {
+        let name = match command.get_raw_arg(0) {
+            Some(name) => name,
+            None => return CommandResult::Error("Preset name argument required".to_string()),
+        };
+        let preset = match self.presets.get(name) {
+            Some(preset) => preset,
+            None => return CommandResult::Error(format!("Unknown preset: {}", name)),
+        };
+        
+        let state = if self.presets.is_applied(name) { "applied" } else { "not applied" };
+        let mut result = format!("{} ({}, {})\n{}\n", preset.name(), preset.platform(), state, preset.description());
+        if let Some(commands) = preset.commands() {
+            result.push_str(&format!("Script: {}\n", commands.apply.display()));
+            if let Some(revert) = &commands.revert {
+                result.push_str(&format!("Revert: {}\n", revert.display()));
+            }
+        }
+        
+        let options = preset.options();
+        if options.is_empty() {
+            result.push_str("No parameters");
+        } else {
+            let current = self.presets.options_for(name, &[]).unwrap_or_default();
+            result.push_str("Parameters:\n");
+            for option in &options {
+                let value = current.get(&option.name).unwrap_or(&option.default);
+                result.push_str(&format!(
+                    "  {:<16} {:<7} {:<20} {}\n", option.name, option.default.type_name(), value.to_string(), option.description
+                ));
+            }
+        }
+        CommandResult::Success(result.trim_end().to_string())
+    }
+
+    /// 적용 상태에 맞춰 프리셋이 추가하는 명령어 등록/제거
+    fn sync_preset_commands(&mut self) {
+        let applied = self.presets.applied();
+        let stale: Vec<String> = self.registry.commands().iter()
+            .filter(|spec| matches!(&spec.source, CommandSource::Preset(preset) if !applied.contains(preset)))
+            .map(CommandSpec::full_name)
+            .collect();
+        for name in stale {
+            self.unregister_command(&name);
+        }
+
+        for preset in applied {
+            let extra = match self.presets.get(&preset) {
+                Some(found) => found.extra_commands(),
+                None => continue,
+            };
+            let source = CommandSource::Preset(preset.clone());
+            for extra in extra {
+                if self.registry.find(&extra.name, None).is_some_and(|spec| spec.source == source) {
+                    continue;
+                }
+                let spec = CommandSpec::new(&extra.name, CommandType::Registered)
+                    .args(&extra.args)
+                    .description(&extra.description)
+                    .source(source.clone());
+                let (owner, name) = (preset.clone(), extra.name.clone());
+                let handler: CommandHandler = Rc::new(move |executor, command| executor.run_preset_command(&owner, &name, command));
+                if let Err(e) = self.register_command(spec, handler) {
+                    log::warn!("Failed to add command '{}' of preset {}: {}", extra.name, preset, e);
+                }
+            }
+        }
+    }
+
+    fn run_preset_command(&mut self, preset: &str, name: &str, command: &Command) -> CommandResult {
+        let backend = match self.backend.as_deref_mut() {
+            Some(backend) => backend,
+            None => return CommandResult::Error("Not attached to a process".to_string()),
+        };
+        match self.presets.run_command(preset, name, &command.raw_args, backend) {
+            Ok(message) => CommandResult::Success(message),
+            Err(e) => CommandResult::Error(format!("Failed to run {}: {}", name, e)),
+        }
+    }
+
+    fn execute_help(&mut self, command: &Command) -> CommandResult {
+        let words: Vec<&str> = command.raw_args.iter().map(String::as_str).collect();
+        match self.registry.help_for(&words) {
+            Ok(help) => CommandResult::Success(help),
+            Err(e) => CommandResult::Error(e),
+        }
+    }
+
+    fn execute_registered(&mut self, command: &Command) -> CommandResult {
+        let handler = self.registry.resolve(command)
+            .and_then(|spec| self.handlers.get(&spec.full_name()))
+            .cloned();
+        match handler {
+            Some(handler) => handler(self, command),
+            None => CommandResult::Error(format!("No handler registered for '{}'", command.name)),
+        }
+    }
+
+    /// 에이전트 이벤트 버스
+    fn event_bus(&self) -> Result<&EventBus, String> {
+        let backend = self.backend.as_deref().ok_or_else(|| "Not attached to a process".to_string())?;
+        backend.events().ok_or_else(|| "Event log is not available for this backend".to_string())
+    }
+
+    fn execute_event_list(&mut self, command: &Command) -> CommandResult {
+        let bus = match self.event_bus() {
+            Ok(bus) => bus,
+            Err(e) => return CommandResult::Error(e),
+        };
+        let filter = match parse_event_filter(&command.raw_args) {
+            Ok(filter) => filter,
+            Err(e) => return CommandResult::Error(e),
+        };
+        
+        let events = bus.log().query(&filter);
+        if events.is_empty() {
+            return CommandResult::Success("No events".to_string());
+        }
+        
+        let lines: Vec<String> = events.iter().map(|event| event.to_string()).collect();
+        CommandResult::Success(lines.join("\n"))
+    }
+
+    fn execute_event_clear(&mut self, _command: &Command) -> CommandResult {
+        match self.event_bus() {
+            Ok(bus) => CommandResult::Success(format!("Cleared {} events", bus.log().clear())),
+            Err(e) => CommandResult::Error(e),
+        }
+    }
+
+    /// 셀렉터 인자 또는 내비게이터의 대상 데이터 (주소 인자는 포인터로 취급)
+    ///
+    /// 반환값: (대상 데이터, 다음 인자 위치)
+    fn resolve_data(&self, command: &Command) -> Result<(VzData, usize), String> {
+        match command.get_arg(0) {
+            Some(CommandArg::Address(addr)) => return Ok((VzData::new_pointer(*addr, MemoryType::Pointer, 8), 1)),
+            Some(arg) => if let Some(selector) = arg.to_selector() {
+                let (_, data) = self.select_one(&selector, "item")?;
+                return Ok((data, 1));
+            },
+            None => {},
+        }
+        
+        match self.navigator.get_selected() {
+            Some(data) => Ok((data.clone(), 0)),
+            None => Err("Address or selector argument required".to_string()),
+        }
+    }
+
+    // Trace 명령어 실행 메서드
+    fn execute_trace_tree(&mut self, command: &Command) -> CommandResult {
+        if let Err(e) = self.sync_trace_events() {
+            return CommandResult::Error(format!("Failed to fetch trace events: {}", e));
+        }
+        
+        let thread_id = match command.get_arg(0) {
+            Some(CommandArg::Number(tid)) if *tid >= 0 => Some(*tid as u64),
+            Some(_) => return CommandResult::Error("Invalid thread id argument".to_string()),
+            None => None,
+        };
+        let limit = if command.has_flag("-a", "--all") { usize::MAX } else { TRACE_TREE_LIMIT };
+        
+        let lines = self.trace.format_tree(thread_id, limit);
+        if lines.is_empty() {
+            return CommandResult::Success("No calls recorded".to_string());
+        }
+        
+        let mut result = lines.join("\n");
+        if self.trace.dropped() > 0 {
+            result.push_str(&format!("\n({} events dropped)", self.trace.dropped()));
+        }
+        CommandResult::Success(result)
+    }
+
+    fn execute_trace_stats(&mut self, _command: &Command) -> CommandResult {
+        if let Err(e) = self.sync_trace_events() {
+            return CommandResult::Error(format!("Failed to fetch trace events: {}", e));
+        }
+        
+        let stats = self.trace.stats();
+        if stats.is_empty() {
+            return CommandResult::Success("No calls recorded".to_string());
+        }
+        
+        let mut result = format!("{:<40} {:>8} {:>12} {:>12} {:>12} {:>12}\n", "function", "calls", "total(ms)", "avg(ms)", "min(ms)", "max(ms)");
+        for entry in stats {
+            result.push_str(&format!(
+                "{:<40} {:>8} {:>12.3} {:>12.3} {:>12.3} {:>12.3}\n",
+                entry.name, entry.calls, entry.total, entry.average(), entry.min, entry.max
+            ));
+        }
+        CommandResult::Success(result.trim_end().to_string())
+    }
+
+    fn execute_trace_export(&mut self, command: &Command) -> CommandResult {
+        let path = match command.get_raw_arg(0) {
+            Some(path) => path,
+            None => return CommandResult::Error("File path argument required".to_string()),
+        };
+        if let Err(e) = self.sync_trace_events() {
+            return CommandResult::Error(format!("Failed to fetch trace events: {}", e));
+        }
+        
+        let json = match self.trace.export_json() {
+            Ok(json) => json,
+            Err(e) => return CommandResult::Error(format!("Failed to export trace: {}", e)),
+        };
+        match std::fs::write(path, json) {
+            Ok(_) => CommandResult::Success(format!("Exported {} calls to {}", self.trace.len(), path)),
+            Err(e) => CommandResult::Error(format!("Failed to export trace: {}", e)),
+        }
+    }
+
+    fn execute_trace_clear(&mut self, _command: &Command) -> CommandResult {
+        // 버퍼에 남은 이벤트까지 비워 이후 기록이 새로 시작되도록 함
+        if let Err(e) = self.sync_trace_events() {
+            return CommandResult::Error(format!("Failed to fetch trace events: {}", e));
+        }
+        
+        if let Err(e) = self.sync_coverage() {
+            return CommandResult::Error(format!("Failed to fetch coverage: {}", e));
+        }
+        
+        let (calls, blocks) = (self.trace.len(), self.coverage.block_count());
+        self.trace.clear();
+        self.coverage.clear();
+        self.monitor.update_trace_calls(|_| 0);
+        CommandResult::Success(format!("Cleared {} calls and {} blocks", calls, blocks))
+    }
+
+    fn execute_trace_stalk(&mut self, command: &Command) -> CommandResult {
+        let result = if command.get_raw_arg(0) == Some("thread") {
+            let thread_id = match command.get_arg(1) {
+                Some(CommandArg::Number(tid)) if *tid >= 0 => *tid as u64,
+                _ => return CommandResult::Error("Thread id argument required".to_string()),
+            };
+            self.agent().and_then(|agent| agent.stalk_thread(thread_id))
+        } else {
+            let data = match self.resolve_data(command) {
+                Ok((data, _)) => data,
+                Err(e) => return CommandResult::Error(e),
+            };
+            let address = match data.get_address() {
+                Some(address) if data.as_module().is_none() => address,
+                _ => return CommandResult::Error(format!("{} is not a function", data.get_display_name())),
+            };
+            let name = data.get_name().map(|name| name.to_string()).unwrap_or_else(|| addr_to_hex(address));
+            self.agent().and_then(|agent| agent.stalk_function(address, &name))
+        };
+        
+        match result {
+            Ok(handle) => {
+                let message = format!("Collecting coverage for {} (stalk #{})", handle.target, handle.id);
+                self.coverage.add_session(handle);
+                CommandResult::Success(message)
+            },
+            Err(e) => CommandResult::Error(format!("Failed to stalk: {}", e)),
+        }
+    }
+
+    fn execute_trace_unstalk(&mut self, command: &Command) -> CommandResult {
+        let ids = match command.get_arg(0) {
+            Some(CommandArg::Number(id)) if *id >= 0 => vec![*id as u64],
+            Some(_) => return CommandResult::Error("Invalid stalk id argument".to_string()),
+            None => self.coverage.active_ids(),
+        };
+        if ids.is_empty() {
+            return CommandResult::Error("No active stalks".to_string());
+        }
+        
+        let result = self.agent().and_then(|agent| {
+            ids.iter().try_for_each(|id| agent.stop_stalk(*id))
+        });
+        if let Err(e) = result {
+            return CommandResult::Error(format!("Failed to stop stalk: {}", e));
+        }
+        for id in &ids {
+            self.coverage.stop_session(*id);
+        }
+        
+        // 중지 시 flush된 블록까지 가져옴
+        if let Err(e) = self.sync_coverage() {
+            return CommandResult::Error(format!("Failed to fetch coverage: {}", e));
+        }
+        CommandResult::Success(format!("Stopped {} stalks ({} blocks collected)", ids.len(), self.coverage.block_count()))
+    }
+
+    fn execute_trace_coverage(&mut self, _command: &Command) -> CommandResult {
+        if self.coverage.sessions().is_empty() {
+            return CommandResult::Success("No coverage collected".to_string());
+        }
+        if let Err(e) = self.sync_coverage() {
+            return CommandResult::Error(format!("Failed to fetch coverage: {}", e));
+        }
+        let modules = match self.agent().and_then(|agent| agent.module_infos(None)) {
+            Ok(modules) => modules,
+            Err(e) => return CommandResult::Error(format!("Failed to list modules: {}", e)),
+        };
+        
+        let mut result = format!("Coverage: {} blocks\n", self.coverage.block_count());
+        for session in self.coverage.sessions() {
+            let state = if session.active { "active" } else { "stopped" };
+            result.push_str(&format!("  #{} {} ({})\n", session.id, session.target, state));
+        }
+        
+        let (summary, unmapped) = self.coverage.summary(&modules);
+        for module in summary {
+            result.push_str(&format!("{:<40} {:>8} blocks {:>10} bytes\n", module.name, module.blocks, module.bytes));
+        }
+        if unmapped > 0 {
+            result.push_str(&format!("({} blocks outside modules)", unmapped));
+        }
+        CommandResult::Success(result.trim_end().to_string())
+    }
+
+    fn execute_trace_drcov(&mut self, command: &Command) -> CommandResult {
+        let path = match command.get_raw_arg(0) {
+            Some(path) => path,
+            None => return CommandResult::Error("File path argument required".to_string()),
+        };
+        if let Err(e) = self.sync_coverage() {
+            return CommandResult::Error(format!("Failed to fetch coverage: {}", e));
+        }
+        let modules = match self.agent().and_then(|agent| agent.module_infos(None)) {
+            Ok(modules) => modules,
+            Err(e) => return CommandResult::Error(format!("Failed to list modules: {}", e)),
+        };
+        
+        match std::fs::write(path, self.coverage.to_drcov(&modules)) {
+            Ok(_) => CommandResult::Success(format!("Exported {} blocks to {}", self.coverage.block_count(), path)),
+            Err(e) => CommandResult::Error(format!("Failed to export coverage: {}", e)),
+        }
+    }
+}
+
+/// trace tree 기본 출력 개수 (최근 최상위 호출 기준)
+const TRACE_TREE_LIMIT: usize = 50;
+
+/// back/forward 이동 횟수 (기본 1)
+fn history_count(command: &Command) -> Result<usize, String> {
+    match command.get_arg(0) {
+        None => Ok(1),
+        Some(CommandArg::Number(n)) if *n > 0 => Ok(*n as usize),
+        Some(_) => Err("Count must be a positive number".to_string()),
+    }
+}
+
+/// 북마크 이름 검사 (셀렉터와 자동완성에서 `@name`으로 쓸 수 있는 글자만)
+fn is_bookmark_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// 첫 인자의 셀렉터 (셀렉터가 아니면 필터 식으로 선택, 필터 식도 함께 반환)
+///
+/// `whole_line`이면 인자 전체(`sav name:open & type=Function`), 아니면 첫 인자만 필터 식으로 본다.
+fn selector_arg(command: &Command, whole_line: bool) -> Result<(Selector, Option<String>), String> {
+    let arg = command.get_arg(0).ok_or_else(|| "Selector argument required".to_string())?;
+    if let Some(selector) = arg.to_selector() {
+        return Ok((selector, None));
+    }
+    if !matches!(arg, CommandArg::FilterExpr(_) | CommandArg::String(_)) {
+        return Err("Invalid selector argument".to_string());
+    }
+
+    let expr = match whole_line {
+        true => command.raw_args.join(" "),
+        false => command.raw_args[0].clone(),
+    };
+    let selector = Selector::filter(&expr).map_err(|e| format!("Filter error: {}", e))?;
+    Ok((selector, Some(expr)))
+}
+
+/// 필터로 선택한 경우 결과 메시지에 붙일 설명
+fn matching(filter: Option<&str>) -> String {
+    filter.map(|expr| format!(" matching '{}'", expr)).unwrap_or_default()
+}
+
+/// 필터의 메모리 값 조건에 쓸 항목 값 (포인터 항목만 읽음)
+fn read_item(agent: &Agent, data: &VzData, memory_type: MemoryType) -> Option<MemoryValue> {
+    let pointer = data.as_pointer()?;
+    // 항목 타입으로 읽을 때는 항목 크기 (문자열, 바이트 배열 길이)
+    let size = if memory_type == pointer.memory_type { pointer.size } else { memory_type.size() };
+    agent.read_value(pointer.address, memory_type, size).ok()
+}
+
+/// `event list` 조건 해석
+///
+/// `hook`처럼 종류만 쓰거나 `id=hook_0x10`, `script=vlitz`, `grep=text`, `after=N`, `last=N` 형식으로 지정한다.
+fn parse_event_filter(args: &[String]) -> Result<EventFilter, String> {
+    let mut filter = EventFilter::default();
+    let number = |key: &str, value: &str| value.parse::<u64>()
+        .map_err(|_| format!("Invalid {} value: {}", key, value));
+    
+    for arg in args {
+        match arg.split_once('=') {
+            Some(("id", value)) => filter.source_id = Some(value.to_string()),
+            Some(("script", value)) => filter.script = Some(value.to_string()),
+            Some(("grep", value)) => filter.text = Some(value.to_string()),
+            Some(("after", value)) => filter.after = Some(number("after", value)?),
+            Some(("last", value)) => filter.last = Some(number("last", value)? as usize),
+            Some((key, _)) => return Err(format!("Unknown event filter: {} (expected id, script, grep, after or last)", key)),
+            None => filter.category = Some(arg.parse()?),
+        }
+    }
+    Ok(filter)
+}
+
+/// 메모리 보호 속성 문자열 검사 (rwx 형식)
+fn is_protection(value: &str) -> bool {
+    let chars: Vec<char> = value.chars().collect();
+    chars.len() == 3
+        && matches!(chars[0], 'r' | '-')
+        && matches!(chars[1], 'w' | '-')
+        && matches!(chars[2], 'x' | '-')
+}
+
+/// 타입이 지정되지 않은 값의 메모리 타입 추론 (정수 > 실수 > 문자열)
+fn infer_memory_type(value: &str) -> MemoryType {
+    if value.parse::<i32>().is_ok() {
+        MemoryType::Int
+    } else if value.parse::<f64>().is_ok() {
+        MemoryType::Float
+    } else {
+        MemoryType::String
+    }
+}
+
+/// list method 결과 헤더 (클래스 계층 정보 포함)
+fn format_class_header(class_name: &str, method_count: usize, hierarchy: &ClassHierarchy) -> String {
+    let mut header = format!("{} ({} methods)", class_name, method_count);
+    
+    if !hierarchy.superclasses.is_empty() {
+        header.push_str(&format!("\n  extends: {}", hierarchy.superclasses.join(" -> ")));
+    }
+    
+    if !hierarchy.interfaces.is_empty() {
+        header.push_str(&format!("\n  implements: {}", hierarchy.interfaces.join(", ")));
+    }
+    
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use vlitz_runtime::{MessageKind, MockBackend};
+
+    fn execute(executor: &mut CommandExecutor, input: &str) -> CommandResult {
+        executor.execute(&Command::parse(input).unwrap())
+    }
+
+    #[test]
+    fn test_list_class_populates_log() {
+        let mut executor = CommandExecutor::new(20);
+        executor.set_backend(Box::new(MockBackend::new().with_query("queryClasses", Some("java"), vec![
+            json!("com.example.MainActivity"),
+            json!("com.example.LoginActivity"),
+        ])));
+
+        match execute(&mut executor, "list class Activity") {
+            CommandResult::Success(msg) => assert!(msg.starts_with("Found 2 classes")),
+            _ => panic!("Expected success"),
+        }
+        assert_eq!(executor.store.get_log()[1].as_class().unwrap().name, "com.example.LoginActivity");
+    }
+
+    #[test]
+    fn test_list_method_uses_navigator_class() {
+        let mut executor = CommandExecutor::new(20);
+        executor.store.replace_log(vec![VzData::new_class("com.example.MainActivity".to_string())]);
+        executor.set_backend(Box::new(
+            MockBackend::new().with_query("queryMethods", Some("java"), vec![
+                json!({ "className": "com.example.MainActivity", "name": "onCreate", "returnType": "void", "argumentTypes": ["android.os.Bundle"] }),
+            ])
+            .on_value("classHierarchy", json!({ "superclasses": ["android.app.Activity"], "interfaces": [] }))
+        ));
+
+        execute(&mut executor, "sel 0");
+        match execute(&mut executor, "list method") {
+            CommandResult::Success(msg) => {
+                assert!(msg.contains("com.example.MainActivity (1 methods)"));
+                assert!(msg.contains("extends: android.app.Activity"));
+            },
+            _ => panic!("Expected success"),
+        }
+        assert_eq!(executor.store.get_log()[0].as_method().unwrap().name, "onCreate");
+    }
+
+    #[test]
+    fn test_list_exports_uses_selected_module() {
+        let mut executor = CommandExecutor::new(20);
+        executor.store.replace_log(vec![VzData::new_module("libc.so".to_string(), 0x7f000000, 0x1000)]);
+        executor.set_backend(Box::new(MockBackend::new().with_query("queryExports", None, vec![
+            json!({ "type": "function", "name": "open", "address": "0x7f000100" }),
+        ])));
+
+        execute(&mut executor, "sel 0");
+        match execute(&mut executor, "exports open") {
+            CommandResult::Success(msg) => assert!(msg.starts_with("Found 1 exports in libc.so")),
+            _ => panic!("Expected success"),
+        }
+        assert_eq!(executor.store.get_log()[0].as_function().unwrap().address, 0x7f000100);
+    }
+
+    #[test]
+    fn test_list_exports_module_name_overrides_selection() {
+        let mut executor = CommandExecutor::new(20);
+        executor.store.replace_log(vec![VzData::new_module("libgame.so".to_string(), 0x70000000, 0x1000)]);
+        executor.set_backend(Box::new(MockBackend::new()
+            .with_query("queryModules", None, vec![
+                json!({ "name": "libc.so", "base": "0x7f000000", "size": 4096, "path": "/system/lib/libc.so" }),
+            ])
+            .with_query("queryExports", None, vec![
+                json!({ "type": "function", "name": "open", "address": "0x7f000100" }),
+            ])));
+
+        execute(&mut executor, "sel 0");
+        match execute(&mut executor, "exports libc.so") {
+            CommandResult::Success(msg) => assert!(msg.starts_with("Found 1 exports in libc.so")),
+            _ => panic!("Expected success"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_and_redirect() {
+        let mut executor = CommandExecutor::new(20);
+        executor.set_backend(Box::new(MockBackend::new().with_query("queryExports", None, vec![
+            json!({ "type": "function", "name": "open", "address": "0x7f000100" }),
+            json!({ "type": "function", "name": "close", "address": "0x7f000200" }),
+        ])));
+        executor.store.replace_log(vec![VzData::new_class("com.example.Saved".to_string())]);
+        execute(&mut executor, "sav log:0");
+        let mut run = |input: &str| executor.execute_pipeline(&Pipeline::parse(input).unwrap());
+
+        // 앞 명령어가 채운 로그 항목이 암시적 셀렉터가 됨 (lib 항목이 있어도 log: 인덱스)
+        assert!(matches!(run("exports libc.so | sav"), CommandResult::Success(_)));
+        assert!(matches!(run("ls | meta tag hot"), CommandResult::Success(msg) if msg == "Added tag 'hot' to 3 items"));
+
+        let path = std::env::temp_dir().join(format!("vlitz-pipe-{}.json", std::process::id()));
+        let redirect = format!("ls > {}", path.display());
+        assert!(matches!(run(&redirect), CommandResult::Success(msg) if msg.starts_with("Wrote 3 items to")));
+        let written: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written[2], json!({
+            "index": 2, "type": "Function", "name": "close", "address": "0x7f000200",
+            "display": "close @ 0x7f000200", "label": null, "tags": ["hot"],
+        }));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(run("lg | lg"), CommandResult::Error(msg) if msg == "'log list' does not take piped input"));
+        assert!(matches!(run("help | sav"), CommandResult::Error(msg) if msg == "'help' produces no items to pipe"));
+        assert!(matches!(run("lg | rm"), CommandResult::Error(_)));
+        assert_eq!(executor.store.get_lib().len(), 3);
+    }
+
+    #[test]
+    fn test_grep_filters_log() {
+        let mut executor = CommandExecutor::new(20);
+        executor.set_backend(Box::new(MockBackend::new().on("readMemory", |args| {
+            assert_eq!(args[2], json!("float"));
+            Ok(match args[0].as_str().unwrap() {
+                "0x1000" => json!(12.5),
+                _ => json!(47.29999923706055),
+            })
+        })));
+        executor.store.replace_log(vec![
+            VzData::new_function("open".to_string(), 0x7f000100),
+            VzData::new_function("close".to_string(), 0x7f000200),
+            VzData::new_pointer(0x1000, MemoryType::Float, 4),
+            VzData::new_pointer(0x2000, MemoryType::Float, 4),
+        ]);
+        let indices = |result: CommandResult| match result {
+            CommandResult::DataList(items) => items.into_iter().map(|(idx, _)| idx).collect::<Vec<_>>(),
+            _ => panic!("Expected data list"),
+        };
+
+        // 원래 로그 인덱스 유지
+        assert_eq!(indices(execute(&mut executor, "grep name:close")), vec![1]);
+        assert_eq!(indices(execute(&mut executor, "grep -v type=Function")), vec![2, 3]);
+        assert_eq!(indices(execute(&mut executor, "grep float<30")), vec![2]);
+        assert_eq!(indices(execute(&mut executor, "grep float=47.3|name:open")), vec![0, 3]);
+        assert_eq!(indices(execute(&mut executor, "lg (name:open|name:close) & address>0x7f000100")), vec![1]);
+        assert!(matches!(execute(&mut executor, "grep nope=1"), CommandResult::Error(msg) if msg.contains("Unknown filter field or memory type: nope")));
+
+        assert!(matches!(execute(&mut executor, "sav name:open"), CommandResult::Success(msg) if msg == "Saved 1 items matching 'name:open' to library"));
+        let pipeline = Pipeline::parse("lg | grep float>30 | sav").unwrap();
+        assert!(matches!(executor.execute_pipeline(&pipeline), CommandResult::Success(_)));
+        assert_eq!(executor.store.get_lib()[1].get_address(), Some(0x2000));
+        assert!(matches!(execute(&mut executor, "rm type=Pointer"), CommandResult::Success(msg) if msg.starts_with("Removed 1 items")));
+
+        assert!(matches!(execute(&mut executor, "grep! type=Pointer"), CommandResult::Success(msg) if msg.starts_with("Kept 2 of 4 items")));
+        assert_eq!(executor.store.get_log()[0].get_address(), Some(0x1000));
+    }
+
+    #[test]
+    fn test_selector_sources() {
+        let mut executor = CommandExecutor::new(20);
+        executor.store.replace_log((0..6).map(|i| VzData::new_function(format!("f{}", i), 0x1000 + i)).collect());
+        let selected = |executor: &CommandExecutor, input: &str| {
+            let selector: Selector = input.parse().unwrap();
+            executor.select(&selector, SelectorSource::Log)
+                .map(|(_, items)| items.into_iter().map(|(idx, _)| idx).collect::<Vec<_>>())
+                .map_err(|e| e.to_string())
+        };
+
+        // 숫자 인자도 셀렉터이며, 저장한 개수를 보고
+        assert!(matches!(execute(&mut executor, "sav 0"), CommandResult::Success(msg) if msg == "Saved 1 items to library"));
+        assert!(matches!(execute(&mut executor, "sav 4-"), CommandResult::Success(msg) if msg == "Saved 2 items to library"));
+        assert_eq!(executor.store.get_lib().len(), 3);
+
+        // lib:과 log:는 각자의 인덱스, 접두사가 없으면 명령어 기본 목록 (sel은 log)
+        assert_eq!(executor.select(&"lib:1".parse().unwrap(), SelectorSource::Log).unwrap().1[0].1.get_name(), Some("f4"));
+        assert_eq!(selected(&executor, "log:1"), Ok(vec![1]));
+        assert_eq!(selected(&executor, "all"), Ok(vec![0, 1, 2, 3, 4, 5]));
+        assert_eq!(selected(&executor, "-1"), Ok(vec![5]));
+        assert_eq!(selected(&executor, "-2-"), Ok(vec![4, 5]));
+        assert_eq!(selected(&executor, "4-1"), Ok(vec![4, 3, 2, 1]));
+        assert_eq!(selected(&executor, "3-100,0,3"), Ok(vec![3, 4, 5, 0]));
+        assert!(selected(&executor, "lib:9-").unwrap_err().contains("No data found for selector"));
+        assert!(selected(&executor, "log:6").unwrap_err().contains("Index 6 out of bounds (6 items)"));
+
+        // 라벨, 태그, 필터
+        execute(&mut executor, "meta label lib:-1 main");
+        execute(&mut executor, "meta tag lib:0,1 hot");
+        execute(&mut executor, "meta tag name:f2 hot");
+        assert_eq!(selected(&executor, "lib:@main"), Ok(vec![2]));
+        assert_eq!(selected(&executor, "lib:#hot"), Ok(vec![0, 1]));
+        assert_eq!(selected(&executor, "#hot"), Ok(vec![2]));
+        assert_eq!(selected(&executor, "log:name:f3|name:f1"), Ok(vec![1, 3]));
+        assert!(selected(&executor, "@main").unwrap_err().contains("No items labeled 'main'"));
+
+        // nav:는 내비게이터 항목
+        assert!(matches!(execute(&mut executor, "sel lib:@main"), CommandResult::Success(msg) if msg.starts_with("Selected: [2] [Function] f5")));
+        assert_eq!(selected(&executor, "nav:0"), Ok(vec![0]));
+        assert!(matches!(execute(&mut executor, "meta tag nav:0 x"), CommandResult::Error(msg) if msg.contains("Navigator items cannot be modified")));
+
+        // rm은 접두사가 없으면 lib 인덱스
+        assert!(matches!(execute(&mut executor, "rm -1"), CommandResult::Success(msg) if msg == "Removed 1 items from library"));
+        assert!(matches!(execute(&mut executor, "rm log:0"), CommandResult::Error(_)));
+        assert!(matches!(execute(&mut executor, "rm #hot"), CommandResult::Success(msg) if msg == "Removed 2 items from library"));
+        assert!(executor.store.get_lib().is_empty());
+    }
+
+    #[test]
+    fn test_navigator_history_and_bookmarks() {
+        let mut executor = CommandExecutor::new(20);
+        executor.store.replace_log(vec![VzData::new_function("open".to_string(), 0x7f000100)]);
+        execute(&mut executor, ": 0x1000");
+        execute(&mut executor, "+ 0x10");
+        execute(&mut executor, ": log:0");
+
+        assert!(matches!(execute(&mut executor, "back"), CommandResult::Success(msg) if msg == "Back to 0x1010"));
+        assert!(matches!(execute(&mut executor, "back 5"), CommandResult::Success(msg) if msg == "Back to 0x1000"));
+        assert!(matches!(execute(&mut executor, "back"), CommandResult::Error(_)));
+        assert!(matches!(execute(&mut executor, "forward"), CommandResult::Success(_)));
+        assert_eq!(executor.get_prompt(), "vlitz:Pointer:0x1010>");
+        match execute(&mut executor, "nav history") {
+            CommandResult::Success(msg) => assert_eq!(msg, "  [0] [Pointer] 0x1000\n* [1] [Pointer] 0x1010\n  [2] [Pointer] 0x7f000100"),
+            _ => panic!("Expected success"),
+        }
+
+        // 뒤로 간 상태에서 새로 이동하면 앞쪽 기록은 버림
+        execute(&mut executor, "sel log:0");
+        assert!(matches!(execute(&mut executor, "forward"), CommandResult::Error(_)));
+        assert_eq!(executor.navigator.items().len(), 3);
+        assert_eq!(executor.navigator.items()[2].get_name(), Some("open"));
+
+        // 북마크는 파일에 저장되고 새 세션에서 다시 불러옴
+        let path = std::env::temp_dir().join(format!("vlitz-bookmarks-{}.json", std::process::id()));
+        executor.load_bookmarks(path.clone()).unwrap();
+        assert!(matches!(execute(&mut executor, "nav mark open_fn"), CommandResult::Success(msg) if msg == "Bookmarked open @ 0x7f000100 as @open_fn"));
+        assert!(matches!(execute(&mut executor, "nav mark a/b"), CommandResult::Error(_)));
+
+        let mut restored = CommandExecutor::new(20);
+        assert_eq!(restored.load_bookmarks(path.clone()).unwrap(), 1);
+        assert!(matches!(execute(&mut restored, "nav go @open_fn"), CommandResult::Success(_)));
+        assert_eq!(restored.get_prompt(), "vlitz:Function:open>");
+        assert!(matches!(execute(&mut restored, "nav unmark @open_fn"), CommandResult::Success(_)));
+        assert_eq!(std::fs::read_to_string(&path).unwrap().trim(), "{}");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_list_range_validates_protection() {
+        let mut executor = CommandExecutor::new(20);
+        assert!(matches!(execute(&mut executor, "list range rwz"), CommandResult::Error(_)));
+    }
+
+    #[test]
+    fn test_list_without_session() {
+        let mut executor = CommandExecutor::new(20);
+        assert!(matches!(execute(&mut executor, "list class"), CommandResult::Error(_)));
+    }
+
+    #[test]
+    fn test_mem_watch_collects_events() {
+        let mut executor = CommandExecutor::new(20);
+        let drained = std::cell::Cell::new(false);
+        executor.set_backend(Box::new(MockBackend::new()
+            .on("addWatch", |args| {
+                assert_eq!(args[1], json!("float"));
+                Ok(json!({ "id": 7, "mode": "hardware", "value": 100.0 }))
+            })
+            .on("drainWatchEvents", move |_| Ok(if drained.replace(true) {
+                json!([])
+            } else {
+                json!([{
+                    "watchId": 7, "address": "0x1000", "from": "0x7f00abcd", "symbol": "libgame.so!takeHit+0x1c",
+                    "threadId": 4242, "backtrace": ["0x7f00abcd libgame.so!takeHit+0x1c"],
+                    "oldValue": 100.0, "newValue": 90.0, "changed": true, "timestamp": 1
+                }])
+            }))
+        ));
+
+        assert!(matches!(execute(&mut executor, "mem watch 0x1000 float"), CommandResult::Success(_)));
+        match execute(&mut executor, "mem events") {
+            CommandResult::Success(msg) => {
+                assert!(msg.contains("100.0 -> 90.0 by 0x7f00abcd (libgame.so!takeHit+0x1c)"));
+                assert!(msg.contains("[tid 4242]"));
+            },
+            _ => panic!("Expected success"),
+        }
+        match execute(&mut executor, "mem list") {
+            CommandResult::Success(msg) => assert!(msg.contains("[0] [Watch] 0x1000 (Float) hardware #7 (1 events)")),
+            _ => panic!("Expected success"),
+        }
+    }
+
+    #[test]
+    fn test_mem_unwatch_removes_agent_watch() {
+        let mut executor = CommandExecutor::new(20);
+        executor.set_backend(Box::new(MockBackend::new()
+            .on_value("addWatch", json!({ "id": 1, "mode": "access", "value": 0 }))
+            .on("removeWatch", |args| {
+                assert_eq!(args[0], json!(1));
+                Ok(json!(true))
+            })
+            .on_value("drainWatchEvents", json!([]))
+        ));
+
+        execute(&mut executor, "mem watch 0x2000");
+        assert!(matches!(execute(&mut executor, "mem unwatch 0"), CommandResult::Success(_)));
+        assert!(executor.monitor.entries().is_empty());
+        assert!(matches!(execute(&mut executor, "mem unwatch 0"), CommandResult::Error(_)));
+    }
+
+    #[test]
+    fn test_mem_lock_below_threshold() {
+        let mut executor = CommandExecutor::new(20);
+        executor.set_backend(Box::new(MockBackend::new()
+            .on("addLock", |args| {
+                assert_eq!(args[1], json!("float"));
+                assert_eq!(args[4], json!({ "mode": "below", "threshold": 50.0, "intervalMs": 100 }));
+                Ok(json!({
+                    "id": 1, "address": args[0], "type": args[1], "value": args[3], "mode": "below",
+                    "threshold": 50.0, "intervalMs": 100, "writes": 0, "original": 42.0
+                }))
+            })
+            .on_value("listLocks", json!([{
+                "id": 1, "address": "0x1000", "type": "float", "value": 100.0, "mode": "below",
+                "threshold": 50.0, "intervalMs": 100, "writes": 3
+            }]))
+        ));
+
+        match execute(&mut executor, "mem lock 0x1000 100 float below 50 every 100") {
+            CommandResult::Success(msg) => assert!(msg.ends_with("(was 42.0)")),
+            _ => panic!("Expected success"),
+        }
+        match execute(&mut executor, "mem locks") {
+            CommandResult::Success(msg) => assert_eq!(msg, "[0] [Lock] 0x1000 (Float) = 100.0 below 50 every 100ms #1 (3 writes)"),
+            _ => panic!("Expected success"),
+        }
+        assert!(matches!(execute(&mut executor, "mem lock 0x1000 hello string below 5"), CommandResult::Error(_)));
+    }
+
+    #[test]
+    fn test_mem_unlock_all_clears_agent_table() {
+        let mut executor = CommandExecutor::new(20);
+        executor.navigator.select(VzData::new_pointer(0x3000, MemoryType::Int, 4));
+        executor.set_backend(Box::new(MockBackend::new()
+            .on("addLock", |args| Ok(json!({
+                "id": 1, "address": args[0], "type": args[1], "value": args[3], "mode": "always",
+                "intervalMs": 50, "writes": 1
+            })))
+            .on_value("removeAllLocks", json!(1))
+        ));
+
+        assert!(matches!(execute(&mut executor, "mem lock 999"), CommandResult::Success(_)));
+        assert_eq!(executor.monitor.locks()[0].1.memory_type, MemoryType::Int);
+        match execute(&mut executor, "mem unlock") {
+            CommandResult::Success(msg) => assert_eq!(msg, "Removed 1 locks"),
+            _ => panic!("Expected success"),
+        }
+        assert!(executor.monitor.entries().is_empty());
+    }
+
+    #[test]
+    fn test_mem_trace_builds_call_tree() {
+        let mut executor = CommandExecutor::new(20);
+        let drained = std::cell::Cell::new(false);
+        executor.store.replace_log(vec![VzData::new_function("open".to_string(), 0x7f000100)]);
+        executor.set_backend(Box::new(MockBackend::new()
+            .on("addTrace", |args| {
+                assert_eq!(args[1], json!("open"));
+                Ok(json!({ "id": 1, "address": args[0], "name": args[1] }))
+            })
+            .on("drainTraceEvents", move |_| Ok(if drained.replace(true) {
+                json!({ "events": [], "dropped": 0 })
+            } else {
+                json!({
+                    "events": [
+                        { "traceId": 1, "event": "enter", "threadId": 7, "depth": 0, "timestamp": 1.0, "args": ["0x1234"] },
+                        { "traceId": 1, "event": "leave", "threadId": 7, "depth": 0, "timestamp": 3.5, "retval": "0x3" }
+                    ],
+                    "dropped": 0
+                })
+            }))
+        ));
+
+        assert!(matches!(execute(&mut executor, "mem trace 0"), CommandResult::Success(_)));
+        match execute(&mut executor, "trace tree") {
+            CommandResult::Success(msg) => assert_eq!(msg, "open(0x1234) = 0x3 [2.500ms] [tid 7]"),
+            _ => panic!("Expected success"),
+        }
+        match execute(&mut executor, "mem list") {
+            CommandResult::Success(msg) => assert_eq!(msg, "[0] [Trace] 0x7f000100 open #1 (1 calls)"),
+            _ => panic!("Expected success"),
+        }
+    }
+
+    #[test]
+    fn test_trace_stalk_thread_summary() {
+        let mut executor = CommandExecutor::new(20);
+        executor.set_backend(Box::new(
+            MockBackend::new().with_query("queryModules", None, vec![
+                json!({ "name": "libgame.so", "base": "0x70000000", "size": 65536, "path": "/data/app/lib/libgame.so" }),
+            ])
+            .on("stalkThread", |args| Ok(json!({ "id": 1, "kind": "thread", "target": format!("thread {}", args[0]) })))
+            .on_value("drainCoverage", json!([{ "stalkId": 1, "blocks": [["0x70000100", 12], ["0x70000200", 8], ["0x1000", 4]] }]))
+        ));
+
+        match execute(&mut executor, "trace stalk thread 4242") {
+            CommandResult::Success(msg) => assert_eq!(msg, "Collecting coverage for thread 4242 (stalk #1)"),
+            _ => panic!("Expected success"),
+        }
+        match execute(&mut executor, "trace coverage") {
+            CommandResult::Success(msg) => {
+                assert!(msg.starts_with("Coverage: 3 blocks"));
+                assert!(msg.contains("libgame.so"));
+                assert!(msg.contains("2 blocks"));
+                assert!(msg.ends_with("(1 blocks outside modules)"));
+            },
+            _ => panic!("Expected success"),
+        }
+    }
+
+    #[test]
+    fn test_event_list_filters_log() {
+        let events = EventBus::new();
+        let mut executor = CommandExecutor::new(20);
+        executor.set_backend(Box::new(MockBackend::new().with_events(events.clone())));
+
+        let send = |payload: Value| ScriptMessage { script: "vlitz".to_string(), kind: MessageKind::Send(payload) };
+        events.publish(send(json!({ "type": "functionHook", "id": "hook_0x10", "event": "enter", "args": ["0x1"] })));
+        events.publish(send(json!({ "type": "memoryWatch", "watchId": 2, "address": "0x2000", "oldValue": 1, "newValue": 2 })));
+        events.publish(send(json!({ "type": "functionHook", "id": "hook_0x10", "event": "leave", "retval": "0x0" })));
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while events.log().len() < 3 && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        match execute(&mut executor, "ev ls hook last=1") {
+            CommandResult::Success(msg) => assert!(msg.starts_with("#3 ") && msg.ends_with("native hook_0x10 leave retval=0x0"), "{}", msg),
+            _ => panic!("Expected success"),
+        }
+        match execute(&mut executor, "event list id=2") {
+            CommandResult::Success(msg) => assert!(msg.ends_with("watch #2 0x2000 1 -> 2"), "{}", msg),
+            _ => panic!("Expected success"),
+        }
+        assert!(matches!(execute(&mut executor, "ev ls bogus"), CommandResult::Error(_)));
+        match execute(&mut executor, "ev clear") {
+            CommandResult::Success(msg) => assert_eq!(msg, "Cleared 3 events"),
+            _ => panic!("Expected success"),
+        }
+    }
+
+    #[test]
+    fn test_preset_apply_and_revert() {
+        let mut executor = CommandExecutor::new(20);
+        assert!(matches!(execute(&mut executor, "preset apply antiroot"), CommandResult::Error(_)));
+
+        executor.set_backend(Box::new(MockBackend::new()
+            .on_value("preset-antiroot.apply", json!([
+                { "name": "native access", "installed": true },
+                { "name": "Build.TAGS", "installed": false, "detail": "Java runtime not available" },
+            ]))
+            .on_value("preset-antiroot.revert", json!(1))
+        ));
+
+        match execute(&mut executor, "preset apply antiroot exec=false") {
+            CommandResult::Success(msg) => assert_eq!(msg, "Applied preset antiroot (1/2 hooks installed)\n  [+] native access\n  [-] Build.TAGS: Java runtime not available"),
+            CommandResult::Error(e) => panic!("{}", e),
+            _ => panic!("Expected success"),
+        }
+        match execute(&mut executor, "preset list") {
+            CommandResult::Success(msg) => assert!(msg.starts_with("antiroot     [applied]"), "{}", msg),
+            _ => panic!("Expected success"),
+        }
+        assert!(matches!(execute(&mut executor, "preset apply antiroot bogus"), CommandResult::Error(_)));
+        match execute(&mut executor, "preset revert all") {
+            CommandResult::Success(msg) => assert_eq!(msg, "Reverted 1 presets"),
+            _ => panic!("Expected success"),
+        }
+        assert!(matches!(execute(&mut executor, "preset revert antiroot"), CommandResult::Error(_)));
+    }
+
+    #[test]
+    fn test_preset_info_shows_parameters() {
+        let mut executor = CommandExecutor::new(20);
+        assert!(matches!(execute(&mut executor, "preset set speedhack factor 0.5"), CommandResult::Success(_)));
+
+        match execute(&mut executor, "preset info speedhack") {
+            CommandResult::Success(msg) => {
+                assert!(msg.starts_with("speedhack (any, not applied)\n"), "{}", msg);
+                assert!(msg.contains("\n  factor           number  0.5                  Speed multiplier"), "{}", msg);
+            },
+            _ => panic!("Expected success"),
+        }
+        assert!(matches!(execute(&mut executor, "preset info nope"), CommandResult::Error(_)));
+    }
+
+    #[test]
+    fn test_help_and_argument_validation() {
+        let mut executor = CommandExecutor::new(20);
+        match execute(&mut executor, "help mv") {
+            CommandResult::Success(msg) => assert!(msg.starts_with("Usage: lib move <from> <to>\n"), "{}", msg),
+            _ => panic!("Expected success"),
+        }
+        match execute(&mut executor, "meta tag lib:0") {
+            CommandResult::Error(msg) => assert!(msg.starts_with("Missing argument <tag> for 'meta tag'"), "{}", msg),
+            _ => panic!("Expected error"),
+        }
+        assert!(matches!(execute(&mut executor, "help nope"), CommandResult::Error(_)));
+    }
+
+    #[test]
+    fn test_preset_commands_follow_applied_state() {
+        let mut executor = CommandExecutor::new(20);
+        executor.set_backend(Box::new(MockBackend::new()
+            .on_value("preset-speedhack.apply", json!([{ "name": "clock_gettime", "installed": true }]))
+            .on_value("preset-speedhack.update", json!(true))
+            .on_value("preset-speedhack.revert", json!(1))));
+        assert!(matches!(execute(&mut executor, "speed"), CommandResult::Error(_)));
+
+        assert!(matches!(execute(&mut executor, "preset apply speedhack factor=2"), CommandResult::Success(_)));
+        match execute(&mut executor, "speed 4") {
+            CommandResult::Success(msg) => assert_eq!(msg, "Speed factor set to 4"),
+            _ => panic!("Expected success"),
+        }
+        match execute(&mut executor, "speed") {
+            CommandResult::Success(msg) => assert_eq!(msg, "Speed factor: 4"),
+            _ => panic!("Expected success"),
+        }
+        match execute(&mut executor, "help speed") {
+            CommandResult::Success(msg) => assert!(msg.contains("Added by preset speedhack"), "{}", msg),
+            _ => panic!("Expected success"),
+        }
+
+        assert!(matches!(execute(&mut executor, "preset revert speedhack"), CommandResult::Success(_)));
+        assert!(executor.registry().find("speed", None).is_none());
+        assert!(matches!(execute(&mut executor, "speed 2"), CommandResult::Error(_)));
+    }
+}
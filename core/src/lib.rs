@@ -1,9 +1,21 @@
 pub mod command;
+pub mod registry;
+pub mod pipeline;
+pub mod output;
 pub mod executor;
 pub mod store;
 pub mod navigator;
+pub mod monitor;
+pub mod trace;
+pub mod coverage;
 
 pub use command::*;
+pub use registry::*;
+pub use pipeline::*;
+pub use output::*;
 pub use executor::*;
 pub use store::*;
-pub use navigator::*; 
\ No newline at end of file
+pub use navigator::*;
+pub use monitor::*;
+pub use trace::*;
+pub use coverage::*; 
\ No newline at end of file
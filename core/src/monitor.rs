@@ -0,0 +1,250 @@
+use vlitz_shared::{MemoryType, Selector, VlitzError, VlitzResult};
+use vlitz_shared::utils::addr_to_hex;
+use vlitz_runtime::{LockInfo, LockOptions, WatchEvent, WatchMode};
+
+/// 메모리 워치 항목
+#[derive(Debug, Clone)]
+pub struct WatchEntry {
+    /// 에이전트 워치 ID
+    pub id: u64,
+    pub address: u64,
+    pub memory_type: MemoryType,
+    pub mode: WatchMode,
+    /// 수집된 히트 이벤트
+    pub events: Vec<WatchEvent>,
+}
+
+/// 메모리 잠금 항목
+#[derive(Debug, Clone)]
+pub struct LockEntry {
+    /// 에이전트 잠금 ID
+    pub id: u64,
+    pub address: u64,
+    pub memory_type: MemoryType,
+    /// 고정할 값 (에이전트에 전달된 JSON 값)
+    pub value: serde_json::Value,
+    pub options: LockOptions,
+    /// 마지막으로 조회한 쓰기 횟수
+    pub writes: u64,
+    /// 쓰기 실패로 잠금이 멈춘 경우의 오류
+    pub error: Option<String>,
+}
+
+/// 함수 추적 항목
+#[derive(Debug, Clone)]
+pub struct TraceEntry {
+    /// 에이전트 추적 ID
+    pub id: u64,
+    pub address: u64,
+    pub name: String,
+    /// 마지막으로 집계한 호출 수
+    pub calls: usize,
+}
+
+/// mem list 항목 (lock, watch, trace 등 에이전트에 걸린 감시 대상)
+#[derive(Debug, Clone)]
+pub enum MonitorEntry {
+    Watch(WatchEntry),
+    Lock(LockEntry),
+    Trace(TraceEntry),
+}
+
+impl MonitorEntry {
+    /// 항목의 대상 주소
+    pub fn address(&self) -> u64 {
+        match self {
+            MonitorEntry::Watch(w) => w.address,
+            MonitorEntry::Lock(l) => l.address,
+            MonitorEntry::Trace(t) => t.address,
+        }
+    }
+
+    /// 워치 항목이면 반환
+    pub fn as_watch(&self) -> Option<&WatchEntry> {
+        match self {
+            MonitorEntry::Watch(w) => Some(w),
+            _ => None,
+        }
+    }
+
+    fn as_watch_mut(&mut self) -> Option<&mut WatchEntry> {
+        match self {
+            MonitorEntry::Watch(w) => Some(w),
+            _ => None,
+        }
+    }
+
+    /// 잠금 항목이면 반환
+    pub fn as_lock(&self) -> Option<&LockEntry> {
+        match self {
+            MonitorEntry::Lock(l) => Some(l),
+            _ => None,
+        }
+    }
+
+    /// 추적 항목이면 반환
+    pub fn as_trace(&self) -> Option<&TraceEntry> {
+        match self {
+            MonitorEntry::Trace(t) => Some(t),
+            _ => None,
+        }
+    }
+
+    /// mem list 출력 형식
+    pub fn format(&self, index: usize) -> String {
+        match self {
+            MonitorEntry::Watch(w) => format!(
+                "[{}] [Watch] {} ({}) {} #{} ({} events)",
+                index,
+                addr_to_hex(w.address),
+                w.memory_type,
+                match w.mode {
+                    WatchMode::Hardware => "hardware",
+                    WatchMode::Access => "access",
+                },
+                w.id,
+                w.events.len()
+            ),
+            MonitorEntry::Lock(l) => {
+                let status = match &l.error {
+                    Some(error) => format!("stopped: {}", error),
+                    None => format!("{} writes", l.writes),
+                };
+                format!(
+                    "[{}] [Lock] {} ({}) = {} {} #{} ({})",
+                    index,
+                    addr_to_hex(l.address),
+                    l.memory_type,
+                    l.value,
+                    l.options.describe(),
+                    l.id,
+                    status
+                )
+            },
+            MonitorEntry::Trace(t) => format!(
+                "[{}] [Trace] {} {} #{} ({} calls)",
+                index,
+                addr_to_hex(t.address),
+                t.name,
+                t.id,
+                t.calls
+            ),
+        }
+    }
+}
+
+/// mem list 테이블
+///
+/// lib/log와 달리 VzData가 아닌 에이전트 측 감시 대상을 관리하며,
+/// unwatch 등의 셀렉터는 이 테이블의 인덱스를 기준으로 한다.
+#[derive(Debug, Default)]
+pub struct MemoryMonitor {
+    entries: Vec<MonitorEntry>,
+}
+
+impl MemoryMonitor {
+    /// 새 테이블 생성
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 항목 추가 후 인덱스 반환
+    pub fn add(&mut self, entry: MonitorEntry) -> usize {
+        self.entries.push(entry);
+        self.entries.len() - 1
+    }
+
+    /// 전체 항목
+    pub fn entries(&self) -> &[MonitorEntry] {
+        &self.entries
+    }
+
+    /// 셀렉터에 해당하는 인덱스 목록 (범위 밖 인덱스는 오류)
+    pub fn select_indices(&self, selector: &Selector) -> VlitzResult<Vec<usize>> {
+        if let Some(source) = selector.source {
+            return Err(VlitzError::Selector(format!("mem list entries take plain indices, not '{}:'", source)));
+        }
+        let mut indices = selector.indices(self.entries.len())?;
+        indices.sort_unstable();
+        Ok(indices)
+    }
+
+    /// 인덱스 목록에 해당하는 항목 제거 후 반환
+    pub fn remove(&mut self, indices: &[usize]) -> Vec<MonitorEntry> {
+        let mut sorted = indices.to_vec();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        sorted.dedup();
+
+        sorted.retain(|&idx| idx < self.entries.len());
+
+        let mut removed: Vec<MonitorEntry> = sorted.into_iter()
+            .map(|idx| self.entries.remove(idx))
+            .collect();
+        removed.reverse();
+        removed
+    }
+
+    /// 워치 항목 목록 (인덱스 포함)
+    pub fn watches(&self) -> Vec<(usize, &WatchEntry)> {
+        self.entries.iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| entry.as_watch().map(|w| (idx, w)))
+            .collect()
+    }
+
+    /// 잠금 항목 목록 (인덱스 포함)
+    pub fn locks(&self) -> Vec<(usize, &LockEntry)> {
+        self.entries.iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| entry.as_lock().map(|l| (idx, l)))
+            .collect()
+    }
+
+    /// 모든 잠금 항목 제거 후 개수 반환
+    pub fn clear_locks(&mut self) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.as_lock().is_none());
+        before - self.entries.len()
+    }
+
+    /// 에이전트 잠금 테이블 상태(쓰기 횟수, 오류) 반영
+    pub fn update_locks(&mut self, infos: &[LockInfo]) {
+        for entry in &mut self.entries {
+            if let MonitorEntry::Lock(lock) = entry {
+                if let Some(info) = infos.iter().find(|info| info.id == lock.id) {
+                    lock.writes = info.writes;
+                    lock.error = info.error.clone();
+                }
+            }
+        }
+    }
+
+    /// 추적 항목 목록 (인덱스 포함)
+    pub fn traces(&self) -> Vec<(usize, &TraceEntry)> {
+        self.entries.iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| entry.as_trace().map(|t| (idx, t)))
+            .collect()
+    }
+
+    /// 추적 항목의 호출 수 갱신
+    pub fn update_trace_calls(&mut self, call_count: impl Fn(u64) -> usize) {
+        for entry in &mut self.entries {
+            if let MonitorEntry::Trace(trace) = entry {
+                trace.calls = call_count(trace.id);
+            }
+        }
+    }
+
+    /// 에이전트에서 가져온 워치 이벤트를 각 워치에 분배
+    pub fn dispatch_watch_events(&mut self, events: Vec<WatchEvent>) {
+        for event in events {
+            let watch = self.entries.iter_mut()
+                .filter_map(|entry| entry.as_watch_mut())
+                .find(|w| w.id == event.watch_id);
+            if let Some(watch) = watch {
+                watch.events.push(event);
+            }
+        }
+    }
+}
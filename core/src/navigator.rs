@@ -1,11 +1,24 @@
-use vlitz_shared::{VzData, VlitzError, VlitzResult, VzDataContent, VzDataType};
-use vlitz_shared::utils::hex_to_addr;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use vlitz_shared::{VzData, VlitzError, VlitzResult, VzDataContent};
 use vlitz_shared::memory::MemoryType;
 
-/// 내비게이터 구조체 (선택된 VzData 관리)
+/// 이동 기록 최대 개수 (넘으면 오래된 것부터 버림)
+const HISTORY_LIMIT: usize = 100;
+
+/// 내비게이터 구조체 (선택된 VzData, 이동 기록, 북마크 관리)
 pub struct Navigator {
     /// 현재 선택된 데이터
     selected: Option<VzData>,
+    /// 이동 기록 (선택하거나 이동할 때마다 추가, `nav:` 셀렉터 대상)
+    history: Vec<VzData>,
+    /// 이동 기록에서 현재 위치
+    position: usize,
+    /// 이름 붙인 북마크 (`nav mark`)
+    bookmarks: BTreeMap<String, VzData>,
+    /// 북마크를 저장할 파일 (`load_bookmarks` 전에는 없음)
+    bookmark_path: Option<PathBuf>,
 }
 
 impl Navigator {
@@ -13,73 +26,126 @@ impl Navigator {
     pub fn new() -> Self {
         Self {
             selected: None,
+            history: Vec::new(),
+            position: 0,
+            bookmarks: BTreeMap::new(),
+            bookmark_path: None,
         }
     }
 
-    /// 데이터 선택
+    /// 데이터 선택 (현재 위치 뒤의 기록은 버리고 새 기록으로 추가)
     pub fn select(&mut self, data: VzData) {
+        if !self.history.is_empty() {
+            self.history.truncate(self.position + 1);
+        }
+        self.history.push(data.clone());
+        if self.history.len() > HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+        self.position = self.history.len() - 1;
         self.selected = Some(data);
     }
 
-    /// 선택 해제
+    /// 선택 해제 (이동 기록은 유지)
     pub fn unselect(&mut self) {
         self.selected = None;
     }
 
     /// 주소 오프셋 추가
     pub fn add_offset(&mut self, offset: u64) -> VlitzResult<()> {
-        if let Some(data) = &self.selected {
-            if let Some(address) = data.get_address() {
-                let new_address = address.checked_add(offset)
-                    .ok_or_else(|| VlitzError::General("Address overflow".to_string()))?;
-                
-                // 주소 포인터로 변환
-                let pointer = VzData::new_pointer(new_address, MemoryType::UInt, 4);
-                self.selected = Some(pointer);
-                return Ok(());
-            }
-            
-            return Err(VlitzError::General("Selected data has no address field".to_string()));
-        }
-        
-        Err(VlitzError::General("No data selected".to_string()))
+        let address = self.selected_address()?.checked_add(offset)
+            .ok_or_else(|| VlitzError::General("Address overflow".to_string()))?;
+        self.goto(address);
+        Ok(())
     }
 
     /// 주소 오프셋 빼기
     pub fn sub_offset(&mut self, offset: u64) -> VlitzResult<()> {
-        if let Some(data) = &self.selected {
-            if let Some(address) = data.get_address() {
-                let new_address = address.checked_sub(offset)
-                    .ok_or_else(|| VlitzError::General("Address underflow".to_string()))?;
-                
-                // 주소 포인터로 변환
-                let pointer = VzData::new_pointer(new_address, MemoryType::UInt, 4);
-                self.selected = Some(pointer);
-                return Ok(());
-            }
-            
-            return Err(VlitzError::General("Selected data has no address field".to_string()));
-        }
-        
-        Err(VlitzError::General("No data selected".to_string()))
-    }
-
-    /// 지정된 주소로 이동
-    pub fn goto(&mut self, address_or_selector: &str) -> VlitzResult<()> {
-        // 셀렉터가 오면 store에서 VzData를 가져온 후 그 주소로 이동해야 함
-        // 이 함수에서는 간단하게 주소 문자열만 처리
-        
-        let address = hex_to_addr(address_or_selector)?;
-        let pointer = VzData::new_pointer(address, MemoryType::UInt, 4);
-        self.selected = Some(pointer);
+        let address = self.selected_address()?.checked_sub(offset)
+            .ok_or_else(|| VlitzError::General("Address underflow".to_string()))?;
+        self.goto(address);
         Ok(())
     }
 
+    /// 지정된 주소로 이동 (셀렉터, 북마크는 실행기가 주소나 항목으로 바꿔서 넘김)
+    pub fn goto(&mut self, address: u64) {
+        self.select(VzData::new_pointer(address, MemoryType::UInt, 4));
+    }
+
+    /// 이동 기록에서 `count`만큼 뒤로 이동
+    pub fn back(&mut self, count: usize) -> VlitzResult<&VzData> {
+        // 선택을 해제한 뒤에는 해제 직전 위치부터 되돌림
+        if self.selected.is_none() && !self.history.is_empty() {
+            return self.move_to(self.position.saturating_sub(count - 1));
+        }
+        if self.position == 0 || self.history.is_empty() {
+            return Err(VlitzError::General("No earlier location in navigation history".to_string()));
+        }
+        self.move_to(self.position.saturating_sub(count))
+    }
+
+    /// 이동 기록에서 `count`만큼 앞으로 이동
+    pub fn forward(&mut self, count: usize) -> VlitzResult<&VzData> {
+        if self.position + 1 >= self.history.len() {
+            return Err(VlitzError::General("No later location in navigation history".to_string()));
+        }
+        self.move_to((self.position + count).min(self.history.len() - 1))
+    }
+
+    /// 이동 기록과 현재 위치 (선택이 없으면 None)
+    pub fn history(&self) -> (&[VzData], Option<usize>) {
+        (&self.history, self.selected.as_ref().map(|_| self.position))
+    }
+
     /// 선택된 데이터 가져오기
     pub fn get_selected(&self) -> Option<&VzData> {
         self.selected.as_ref()
     }
 
+    /// `nav:` 셀렉터 대상 항목 (이동 기록, `nav history` 인덱스)
+    pub fn items(&self) -> &[VzData] {
+        &self.history
+    }
+
+    /// 북마크 파일을 불러오고 이후 변경을 저장 (불러온 북마크 수 반환, 파일이 없으면 0)
+    pub fn load_bookmarks(&mut self, path: PathBuf) -> VlitzResult<usize> {
+        self.bookmarks = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .map_err(|e| VlitzError::General(format!("Invalid bookmark file {}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(VlitzError::General(format!("Failed to read {}: {}", path.display(), e))),
+        };
+        self.bookmark_path = Some(path);
+        Ok(self.bookmarks.len())
+    }
+
+    /// 현재 선택된 데이터를 이름으로 북마크 (같은 이름은 덮어씀)
+    pub fn mark(&mut self, name: &str) -> VlitzResult<&VzData> {
+        let data = self.selected.clone()
+            .ok_or_else(|| VlitzError::General("No data selected".to_string()))?;
+        self.bookmarks.insert(name.to_string(), data);
+        self.save_bookmarks()?;
+        Ok(&self.bookmarks[name])
+    }
+
+    /// 북마크 제거
+    pub fn unmark(&mut self, name: &str) -> VlitzResult<VzData> {
+        let data = self.bookmarks.remove(name)
+            .ok_or_else(|| VlitzError::General(format!("Unknown bookmark: {}", name)))?;
+        self.save_bookmarks()?;
+        Ok(data)
+    }
+
+    /// 이름으로 북마크 찾기
+    pub fn bookmark(&self, name: &str) -> Option<&VzData> {
+        self.bookmarks.get(name)
+    }
+
+    /// 모든 북마크 (이름 순)
+    pub fn bookmarks(&self) -> &BTreeMap<String, VzData> {
+        &self.bookmarks
+    }
+
     /// 프롬프트 문자열 생성
     pub fn get_prompt(&self) -> String {
         if let Some(data) = &self.selected {
@@ -96,4 +162,30 @@ impl Navigator {
             "vlitz>".to_string()
         }
     }
-} 
\ No newline at end of file
+
+    fn selected_address(&self) -> VlitzResult<u64> {
+        let data = self.selected.as_ref()
+            .ok_or_else(|| VlitzError::General("No data selected".to_string()))?;
+        data.get_address()
+            .ok_or_else(|| VlitzError::General("Selected data has no address field".to_string()))
+    }
+
+    fn move_to(&mut self, position: usize) -> VlitzResult<&VzData> {
+        self.position = position;
+        Ok(self.selected.insert(self.history[position].clone()))
+    }
+
+    fn save_bookmarks(&self) -> VlitzResult<()> {
+        let Some(path) = &self.bookmark_path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| VlitzError::General(format!("Failed to create {}: {}", dir.display(), e)))?;
+        }
+        // 맵 직렬화는 실패하지 않음
+        let text = serde_json::to_string_pretty(&self.bookmarks).unwrap_or_default();
+        fs::write(path, text + "\n")
+            .map_err(|e| VlitzError::General(format!("Failed to save bookmarks to {}: {}", path.display(), e)))
+    }
+}
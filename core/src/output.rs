@@ -0,0 +1,248 @@
+use std::str::FromStr;
+use serde_json::{json, Map, Value};
+use vlitz_shared::{VlitzError, VzData};
+use vlitz_shared::utils::{addr_to_hex, format_vzdata};
+use crate::executor::CommandResult;
+
+/// 명령어 결과 출력 형식 (`--output`, 리디렉션 파일)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// 화면 출력과 같은 텍스트
+    #[default]
+    Plain,
+    /// 필드별 열로 맞춘 표
+    Table,
+    /// 항목 배열 (메시지는 `{"status": ..., "message": ...}`)
+    Json,
+    /// 항목 한 줄씩, 첫 줄은 헤더
+    Csv,
+}
+
+impl OutputFormat {
+    /// 쓸 수 있는 형식 이름
+    pub const NAMES: &'static [&'static str] = &["plain", "table", "json", "csv"];
+
+    /// 사람이 읽는 텍스트 형식인지 (JSON/CSV는 도구가 읽는 형식)
+    pub fn is_text(&self) -> bool {
+        matches!(self, OutputFormat::Plain | OutputFormat::Table)
+    }
+
+    /// 파일 확장자로 형식 결정 (`.json`, `.csv`, 그 외는 텍스트)
+    pub fn from_path(path: &str) -> Self {
+        let extension = path.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("json") => OutputFormat::Json,
+            Some("csv") => OutputFormat::Csv,
+            _ => OutputFormat::Plain,
+        }
+    }
+
+    /// 명령어 결과 전체 출력 (색 없음, 종료 요청은 빈 문자열)
+    pub fn render_result(&self, result: &CommandResult) -> String {
+        match result {
+            CommandResult::DataList(items) => self.render_items(items),
+            CommandResult::Success(message) => self.render_message(message),
+            CommandResult::Error(message) => self.render_error(message),
+            CommandResult::Exit => String::new(),
+        }
+    }
+
+    /// 데이터 항목 목록 출력 (인덱스는 lib/log 인덱스)
+    pub fn render_items(&self, items: &[(usize, VzData)]) -> String {
+        match self {
+            OutputFormat::Plain => items.iter()
+                .map(|(idx, data)| format_vzdata(*idx, data) + "\n")
+                .collect(),
+            OutputFormat::Table => render_table(items),
+            OutputFormat::Json => {
+                let records: Vec<Value> = items.iter().map(|(idx, data)| item_record(*idx, data)).collect();
+                // Value 직렬화는 실패하지 않음
+                serde_json::to_string_pretty(&records).unwrap_or_default() + "\n"
+            },
+            OutputFormat::Csv => {
+                let mut text = String::from("index,type,name,address,label,tags,display\n");
+                for (idx, data) in items {
+                    let fields = [
+                        idx.to_string(),
+                        data.data_type.to_string(),
+                        data.get_name().unwrap_or_default().to_string(),
+                        data.get_address().map(addr_to_hex).unwrap_or_default(),
+                        data.label.clone().unwrap_or_default(),
+                        sorted_tags(data).join(";"),
+                        data.get_display_name(),
+                    ];
+                    let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+                    text.push_str(&row.join(","));
+                    text.push('\n');
+                }
+                text
+            },
+        }
+    }
+
+    /// 메시지 출력 (데이터가 없는 명령어 결과)
+    pub fn render_message(&self, message: &str) -> String {
+        self.render_status("ok", message)
+    }
+
+    /// 오류 출력 (텍스트 형식은 `Error: ` 접두사)
+    pub fn render_error(&self, message: &str) -> String {
+        match self {
+            OutputFormat::Plain | OutputFormat::Table => format!("Error: {}\n", message),
+            OutputFormat::Json | OutputFormat::Csv => self.render_status("error", message),
+        }
+    }
+
+    fn render_status(&self, status: &str, message: &str) -> String {
+        match self {
+            OutputFormat::Json => json!({ "status": status, "message": message.trim_end() }).to_string() + "\n",
+            OutputFormat::Csv => format!("status,message\n{},{}\n", status, csv_field(message.trim_end())),
+            OutputFormat::Plain | OutputFormat::Table if message.trim().is_empty() => String::new(),
+            OutputFormat::Plain | OutputFormat::Table => format!("{}\n", message.trim_end()),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = VlitzError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" | "text" => Ok(OutputFormat::Plain),
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(VlitzError::General(format!("Unknown output format: {} (expected {})", s, Self::NAMES.join(", ")))),
+        }
+    }
+}
+
+/// 항목의 내용 필드 (serde 직렬화 결과, 주소는 16진수 문자열)
+fn content_fields(data: &VzData) -> Map<String, Value> {
+    // VzDataContent는 {"Function": {...}}처럼 variant 이름으로 감싸서 직렬화됨
+    let mut fields = match serde_json::to_value(&data.content) {
+        Ok(Value::Object(variant)) => variant.into_iter()
+            .find_map(|(_, value)| match value {
+                Value::Object(fields) => Some(fields),
+                _ => None,
+            })
+            .unwrap_or_default(),
+        _ => Map::new(),
+    };
+    if let Some(address) = data.get_address() {
+        fields.insert("address".to_string(), json!(addr_to_hex(address)));
+    }
+    fields
+}
+
+/// JSON 출력 항목 하나
+fn item_record(idx: usize, data: &VzData) -> Value {
+    let mut record = content_fields(data);
+    record.insert("index".to_string(), json!(idx));
+    record.insert("type".to_string(), json!(data.data_type.to_string()));
+    record.insert("display".to_string(), json!(data.get_display_name()));
+    record.insert("label".to_string(), json!(data.label));
+    record.insert("tags".to_string(), json!(sorted_tags(data)));
+    Value::Object(record)
+}
+
+/// 표 출력 (열은 인덱스, 타입, 항목 타입별 필드 순, 라벨/태그는 있는 경우만)
+fn render_table(items: &[(usize, VzData)]) -> String {
+    let mut columns: Vec<&str> = vec!["index", "type"];
+    for (_, data) in items {
+        for field in data.data_type.fields() {
+            if !columns.contains(field) {
+                columns.push(field);
+            }
+        }
+    }
+    if items.iter().any(|(_, data)| data.label.is_some()) {
+        columns.push("label");
+    }
+    if items.iter().any(|(_, data)| !data.tags.is_empty()) {
+        columns.push("tags");
+    }
+
+    let rows: Vec<Vec<String>> = items.iter().map(|(idx, data)| {
+        let fields = content_fields(data);
+        columns.iter().map(|column| match *column {
+            "index" => idx.to_string(),
+            "type" => data.data_type.to_string(),
+            "label" => data.label.clone().unwrap_or_default(),
+            "tags" => sorted_tags(data).join(", "),
+            field => fields.get(field).map(cell_text).unwrap_or_default(),
+        }).collect()
+    }).collect();
+
+    let widths: Vec<usize> = columns.iter().enumerate()
+        .map(|(i, column)| rows.iter().map(|row| row[i].chars().count()).fold(column.len(), usize::max))
+        .collect();
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells.iter().zip(&widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+        padded.join("  ").trim_end().to_string() + "\n"
+    };
+
+    let mut text = line(columns.clone());
+    for row in &rows {
+        text.push_str(&line(row.iter().map(String::as_str).collect()));
+    }
+    text
+}
+
+/// 표 칸에 쓸 필드 값 (문자열은 따옴표 없이, 목록은 쉼표로 연결)
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(cell_text).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+/// 정렬된 태그 목록 (파일 출력이 실행마다 달라지지 않도록)
+fn sorted_tags(data: &VzData) -> Vec<&str> {
+    let mut tags: Vec<&str> = data.tags.iter().map(String::as_str).collect();
+    tags.sort_unstable();
+    tags
+}
+
+/// CSV 필드 (쉼표, 따옴표, 줄바꿈이 있으면 따옴표로 감쌈)
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vlitz_shared::MemoryType;
+
+    #[test]
+    fn test_render_formats() {
+        let mut open = VzData::new_function("open".to_string(), 0x7f000100);
+        open.set_label("libc_open".to_string());
+        let items = vec![(0, open), (3, VzData::new_pointer(0x1000, MemoryType::Float, 4))];
+
+        assert_eq!(OutputFormat::Table.render_items(&items), "\
+index  type      name  address     memory_type  size  label
+0      Function  open  0x7f000100                     libc_open
+3      Pointer         0x1000      Float        4
+");
+
+        let records: Value = serde_json::from_str(&OutputFormat::Json.render_items(&items)).unwrap();
+        assert_eq!(records[1], json!({
+            "index": 3, "type": "Pointer", "address": "0x1000", "memory_type": "Float", "size": 4,
+            "display": "0x1000", "label": null, "tags": [],
+        }));
+
+        let error = CommandResult::Error("No items match 'a,b'".to_string());
+        assert_eq!(OutputFormat::Json.render_result(&error), "{\"message\":\"No items match 'a,b'\",\"status\":\"error\"}\n");
+        assert_eq!(OutputFormat::Csv.render_result(&error), "status,message\nerror,\"No items match 'a,b'\"\n");
+        assert_eq!(OutputFormat::Table.render_result(&CommandResult::Success("Done\n".to_string())), "Done\n");
+        assert_eq!("table".parse::<OutputFormat>().unwrap(), OutputFormat::Table);
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+}
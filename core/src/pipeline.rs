@@ -0,0 +1,152 @@
+use vlitz_shared::{VlitzError, VlitzResult, VzData};
+use crate::command::{split_tokens, Command, Token};
+use crate::output::OutputFormat;
+
+/// 출력 리디렉션 (`> file`, `>> file`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Redirect {
+    pub path: String,
+    /// `>>`면 파일 끝에 이어 씀
+    pub append: bool,
+    /// 파일 확장자로 정한 형식
+    pub format: OutputFormat,
+}
+
+/// 파이프라인 (`list exports libc.so | grep name:str | sav`, `lg > out.json`)
+///
+/// 앞 명령어의 데이터 목록이 다음 명령어의 입력(암시적 셀렉터)이 된다.
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    pub stages: Vec<Command>,
+    pub redirect: Option<Redirect>,
+    /// 이 줄에만 쓸 출력 형식 (`--output json`, 리디렉션 파일 형식보다 우선)
+    pub output: Option<OutputFormat>,
+}
+
+impl Pipeline {
+    /// 문자열에서 파이프라인 파싱
+    pub fn parse(input: &str) -> VlitzResult<Self> {
+        let mut stages = Vec::new();
+        let mut words = Vec::new();
+        let mut output = None;
+        let mut tokens = split_tokens(input.trim()).into_iter();
+
+        while let Some(token) = tokens.next() {
+            match token {
+                Token::Word(word) if word == "--output" => match tokens.next() {
+                    Some(Token::Word(name)) => output = Some(name.parse()?),
+                    _ => return Err(VlitzError::CommandParse("Missing format after '--output'".to_string())),
+                },
+                Token::Word(word) if word.starts_with("--output=") => {
+                    output = Some(word["--output=".len()..].parse()?);
+                },
+                Token::Word(word) => words.push(word),
+                Token::Pipe => {
+                    if words.is_empty() {
+                        return Err(VlitzError::CommandParse("Missing command before '|'".to_string()));
+                    }
+                    stages.push(Command::from_words(std::mem::take(&mut words))?);
+                },
+                Token::Redirect { append } => {
+                    if words.is_empty() {
+                        return Err(VlitzError::CommandParse(format!("Missing command before '{}'", Token::Redirect { append })));
+                    }
+                    stages.push(Command::from_words(std::mem::take(&mut words))?);
+
+                    let path = match tokens.next() {
+                        Some(Token::Word(path)) => path,
+                        _ => return Err(VlitzError::CommandParse(format!("Missing file after '{}'", Token::Redirect { append }))),
+                    };
+                    if let Some(extra) = tokens.next() {
+                        return Err(VlitzError::CommandParse(format!("Unexpected '{}' after redirection", extra)));
+                    }
+                    let format = output.unwrap_or_else(|| OutputFormat::from_path(&path));
+                    return Ok(Self { stages, redirect: Some(Redirect { path, append, format }), output });
+                },
+            }
+        }
+
+        if words.is_empty() {
+            let message = if stages.is_empty() { "Empty command" } else { "Missing command after '|'" };
+            return Err(VlitzError::CommandParse(message.to_string()));
+        }
+        stages.push(Command::from_words(words)?);
+        Ok(Self { stages, redirect: None, output })
+    }
+
+    /// 파이프도 리디렉션도 없는 명령어 하나인지
+    pub fn is_single(&self) -> bool {
+        self.stages.len() == 1 && self.redirect.is_none()
+    }
+}
+
+/// 파이프 입력 항목이 있는 저장소
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipeSource {
+    Log,
+    Lib,
+}
+
+/// 파이프라인에서 다음 명령어로 넘어가는 데이터
+#[derive(Debug, Clone)]
+pub struct PipeInput {
+    pub source: PipeSource,
+    /// (lib/log 인덱스, 데이터)
+    pub items: Vec<(usize, VzData)>,
+}
+
+impl PipeInput {
+    /// 입력 항목을 가리키는 셀렉터 문자열 (예: `log:3,5,8`)
+    pub fn selector(&self) -> String {
+        let prefix = match self.source {
+            PipeSource::Log => "log",
+            PipeSource::Lib => "lib",
+        };
+        let indices: Vec<String> = self.items.iter().map(|(idx, _)| idx.to_string()).collect();
+        format!("{}:{}", prefix, indices.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pipeline_and_redirect() {
+        let pipeline = Pipeline::parse("list exports libc.so | sav").unwrap();
+        assert_eq!(pipeline.stages.len(), 2);
+        assert_eq!(pipeline.stages[0].subcommand.as_deref(), Some("exports"));
+        assert_eq!(pipeline.stages[1].name, "sav");
+        assert!(pipeline.redirect.is_none());
+
+        let pipeline = Pipeline::parse("mem dump 0x1000 256 >> dump.txt").unwrap();
+        assert!(pipeline.stages[0].get_arg(0).unwrap().as_address().is_ok());
+        assert_eq!(pipeline.redirect, Some(Redirect { path: "dump.txt".to_string(), append: true, format: OutputFormat::Plain }));
+        assert_eq!(Pipeline::parse("lg > out.json").unwrap().redirect.unwrap().format, OutputFormat::Json);
+
+        // 출력 형식은 어느 단계에 써도 줄 전체에 적용되고, 리디렉션 파일 형식보다 우선
+        let pipeline = Pipeline::parse("lg --output table").unwrap();
+        assert!(pipeline.is_single());
+        assert_eq!(pipeline.output, Some(OutputFormat::Table));
+        assert!(pipeline.stages[0].flags.is_empty());
+        let pipeline = Pipeline::parse("lg --output=csv > out.txt").unwrap();
+        assert_eq!(pipeline.redirect.unwrap().format, OutputFormat::Csv);
+        assert!(Pipeline::parse("lg --output yaml").is_err());
+
+        // 붙여 쓴 필터 연산자와 따옴표 안의 연산자는 인자
+        let pipeline = Pipeline::parse("clr name:a|name:b \">\"").unwrap();
+        assert!(pipeline.is_single());
+        assert_eq!(pipeline.stages[0].raw_args, vec!["name:a|name:b", ">"]);
+
+        for (input, error) in [
+            ("| sav", "Missing command before '|'"),
+            ("lg |", "Missing command after '|'"),
+            ("lg >", "Missing file after '>'"),
+            ("lg > a.txt | sav", "Unexpected '|' after redirection"),
+            ("lg --output", "Missing format after '--output'"),
+        ] {
+            assert!(Pipeline::parse(input).unwrap_err().to_string().contains(error), "{}", input);
+        }
+        assert!(Command::parse("lg | sav").is_err());
+    }
+}
@@ -0,0 +1,690 @@
+use std::sync::OnceLock;
+use vlitz_shared::{VlitzError, VlitzResult};
+use crate::command::{Command, CommandType};
+
+/// 명령어를 등록한 곳
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandSource {
+    /// 내장 명령어
+    Builtin,
+    /// 적용 중인 프리셋이 추가한 명령어 (프리셋 이름)
+    Preset(String),
+    /// 스크립트 프로시저
+    Script,
+}
+
+/// 명령어 정의
+///
+/// 파싱(`Command::get_type`), 실행기 분배, `help`, REPL 자동완성/힌트, 인자 검사가 모두 이 정의를 사용한다.
+#[derive(Debug, Clone)]
+pub struct CommandSpec {
+    /// 그룹 이름 (예: `lib`), 단독 명령어는 None
+    pub group: Option<String>,
+    /// 이름과 별칭 (그룹 명령어는 하위 명령어 이름)
+    pub names: Vec<String>,
+    /// 그룹 없이 바로 쓰는 단축 이름 (예: `sav`)
+    pub shortcuts: Vec<String>,
+    /// 인자 형식 (`<필수>`, `[선택]`, `a|b`는 선택지, `...`은 여러 개)
+    pub args: String,
+    pub description: String,
+    pub examples: Vec<String>,
+    pub command_type: CommandType,
+    pub source: CommandSource,
+    /// 파이프라인 앞 명령어의 데이터 목록을 입력으로 받는지 (셀렉터 인자를 생략하면 입력 항목을 선택)
+    pub input: bool,
+}
+
+impl CommandSpec {
+    pub fn new(name: &str, command_type: CommandType) -> Self {
+        Self {
+            group: None,
+            names: vec![name.to_string()],
+            shortcuts: Vec::new(),
+            args: String::new(),
+            description: String::new(),
+            examples: Vec::new(),
+            command_type,
+            source: CommandSource::Builtin,
+            input: false,
+        }
+    }
+
+    /// 그룹의 하위 명령어로 등록
+    pub fn group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.names.push(alias.to_string());
+        self
+    }
+
+    pub fn shortcut(mut self, shortcut: &str) -> Self {
+        self.shortcuts.push(shortcut.to_string());
+        self
+    }
+
+    pub fn args(mut self, args: &str) -> Self {
+        self.args = args.to_string();
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    pub fn example(mut self, example: &str) -> Self {
+        self.examples.push(example.to_string());
+        self
+    }
+
+    pub fn source(mut self, source: CommandSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// 파이프 입력을 받는 명령어로 표시
+    pub fn input(mut self) -> Self {
+        self.input = true;
+        self
+    }
+
+    /// 대표 이름
+    pub fn name(&self) -> &str {
+        &self.names[0]
+    }
+
+    /// 표시용 전체 이름 (예: `lib save`)
+    pub fn full_name(&self) -> String {
+        match &self.group {
+            Some(group) => format!("{} {}", group, self.name()),
+            None => self.name().to_string(),
+        }
+    }
+
+    /// 사용법 한 줄 (예: `lib save <selector>`)
+    pub fn usage(&self) -> String {
+        if self.args.is_empty() {
+            self.full_name()
+        } else {
+            format!("{} {}", self.full_name(), self.args)
+        }
+    }
+
+    /// 위치 인자 자리 이름 (괄호와 `...` 제거, 플래그 제외)
+    pub fn arg_slots(&self) -> Vec<&str> {
+        self.arg_tokens()
+            .map(|(slot, _)| slot)
+            .filter(|slot| !slot.starts_with('-'))
+            .collect()
+    }
+
+    /// 받을 수 있는 플래그 (예: `-i`, `--inherited`)
+    pub fn flags(&self) -> Vec<&str> {
+        self.arg_tokens()
+            .map(|(slot, _)| slot)
+            .filter(|slot| slot.starts_with('-'))
+            .flat_map(|slot| slot.split('|'))
+            .collect()
+    }
+
+    /// 인자 검사 (모르는 플래그, 빠진 필수 인자)
+    pub fn validate(&self, command: &Command) -> Result<(), String> {
+        let flags = self.flags();
+        if let Some(flag) = command.flags.iter().find(|flag| !flags.contains(&flag.as_str())) {
+            return Err(format!("Unknown flag '{}' for '{}' (usage: {})", flag, self.full_name(), self.usage()));
+        }
+
+        let required: Vec<&str> = self.arg_tokens()
+            .filter(|(slot, required)| *required && !slot.starts_with('-'))
+            .map(|(slot, _)| slot)
+            .collect();
+        match required.get(command.raw_args.len()) {
+            Some(missing) => Err(format!("Missing argument <{}> for '{}' (usage: {})", missing, self.full_name(), self.usage())),
+            None => Ok(()),
+        }
+    }
+
+    /// 인자 형식 토큰 (자리 이름, 필수 여부)
+    fn arg_tokens(&self) -> impl Iterator<Item = (&str, bool)> {
+        self.args.split_whitespace().map(|token| {
+            let slot = token.trim_matches(|c| matches!(c, '<' | '>' | '[' | ']')).trim_end_matches("...");
+            (slot, token.starts_with('<'))
+        })
+    }
+
+    fn all_names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().chain(&self.shortcuts).map(String::as_str)
+    }
+}
+
+/// 하위 명령어를 가지는 명령어 그룹
+#[derive(Debug, Clone)]
+pub struct CommandGroup {
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub description: String,
+}
+
+impl CommandGroup {
+    fn matches(&self, name: &str) -> bool {
+        self.name == name || self.aliases.iter().any(|alias| alias == name)
+    }
+}
+
+/// 명령어 등록부
+///
+/// 내장 명령어 표로 시작하고, 프리셋과 스크립트가 명령어를 추가/제거할 수 있다.
+/// 추가한 명령어는 기존 그룹에 들어가거나 단독 명령어가 된다 (새 그룹은 만들 수 없음).
+#[derive(Debug, Clone)]
+pub struct CommandRegistry {
+    groups: Vec<CommandGroup>,
+    commands: Vec<CommandSpec>,
+}
+
+impl CommandRegistry {
+    /// 내장 명령어만 있는 공유 등록부 (`Command::parse`/`Command::get_type`에서 사용)
+    pub fn builtin() -> &'static CommandRegistry {
+        static BUILTIN: OnceLock<CommandRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(|| CommandRegistry {
+            groups: builtin_groups(),
+            commands: builtin_commands(),
+        })
+    }
+
+    /// 그룹만 있고 명령어는 없는 등록부
+    pub fn empty() -> Self {
+        Self { groups: builtin_groups(), commands: Vec::new() }
+    }
+
+    pub fn groups(&self) -> &[CommandGroup] {
+        &self.groups
+    }
+
+    pub fn commands(&self) -> &[CommandSpec] {
+        &self.commands
+    }
+
+    /// 그룹 이름 또는 별칭으로 그룹 찾기
+    pub fn find_group(&self, name: &str) -> Option<&CommandGroup> {
+        self.groups.iter().find(|group| group.matches(name))
+    }
+
+    /// 명령어 이름(과 하위 명령어)으로 정의 찾기
+    pub fn find(&self, name: &str, subcommand: Option<&str>) -> Option<&CommandSpec> {
+        match self.find_group(name) {
+            Some(group) => {
+                let subcommand = subcommand?;
+                self.commands.iter().find(|spec| {
+                    spec.group.as_deref() == Some(group.name.as_str()) && spec.names.iter().any(|n| n == subcommand)
+                })
+            },
+            None => self.commands.iter().find(|spec| {
+                (spec.group.is_none() && spec.names.iter().any(|n| n == name)) || spec.shortcuts.iter().any(|s| s == name)
+            }),
+        }
+    }
+
+    /// 파싱된 명령어의 정의
+    pub fn resolve(&self, command: &Command) -> Option<&CommandSpec> {
+        self.find(&command.name, command.subcommand.as_deref())
+    }
+
+    /// 정의를 찾고 인자 검사 (실패하면 사용자에게 보여줄 오류)
+    pub fn check(&self, command: &Command) -> Result<&CommandSpec, String> {
+        let spec = match (self.resolve(command), self.find_group(&command.name)) {
+            (Some(spec), _) => spec,
+            (None, Some(group)) => {
+                let expected = self.subcommands(&group.name).map(CommandSpec::name).collect::<Vec<_>>().join(", ");
+                return Err(match &command.subcommand {
+                    Some(subcommand) => format!("Unknown subcommand '{}' for '{}' (expected {})", subcommand, group.name, expected),
+                    None => format!("Subcommand required for '{}' (expected {})", group.name, expected),
+                });
+            },
+            (None, None) => return Err("Unknown command".to_string()),
+        };
+        spec.validate(command)?;
+        Ok(spec)
+    }
+
+    /// 그룹의 하위 명령어 (등록 순)
+    pub fn subcommands<'a>(&'a self, group: &'a str) -> impl Iterator<Item = &'a CommandSpec> {
+        self.commands.iter().filter(move |spec| spec.group.as_deref() == Some(group))
+    }
+
+    /// 첫 단어로 쓸 수 있는 이름 (그룹, 단독 명령어, 단축 이름)
+    pub fn top_level_names(&self) -> Vec<&str> {
+        let groups = self.groups.iter()
+            .flat_map(|group| std::iter::once(&group.name).chain(&group.aliases))
+            .map(String::as_str);
+        let commands = self.commands.iter().flat_map(|spec| {
+            let names = if spec.group.is_none() { &spec.names[..] } else { &[] };
+            names.iter().chain(&spec.shortcuts).map(String::as_str)
+        });
+        groups.chain(commands).collect()
+    }
+
+    /// 명령어 추가 (이름이 겹치거나 그룹이 없으면 오류)
+    pub fn register(&mut self, spec: CommandSpec) -> VlitzResult<()> {
+        if spec.names.iter().chain(&spec.shortcuts).any(|name| name.is_empty() || name.contains(char::is_whitespace)) {
+            return Err(VlitzError::CommandParse(format!("Invalid command name '{}'", spec.full_name())));
+        }
+        if let Some(group) = &spec.group {
+            let group = self.find_group(group)
+                .ok_or_else(|| VlitzError::CommandParse(format!("Unknown command group '{}'", group)))?;
+            if let Some(name) = spec.names.iter().find(|name| self.find(&group.name, Some(name)).is_some()) {
+                return Err(VlitzError::CommandParse(format!("Command '{} {}' is already registered", group.name, name)));
+            }
+        }
+
+        let top_level = self.top_level_names();
+        let new_names: Vec<&str> = match spec.group {
+            Some(_) => spec.shortcuts.iter().map(String::as_str).collect(),
+            None => spec.all_names().collect(),
+        };
+        if let Some(name) = new_names.iter().find(|name| top_level.contains(name)) {
+            return Err(VlitzError::CommandParse(format!("Command '{}' is already registered", name)));
+        }
+
+        self.commands.push(spec);
+        Ok(())
+    }
+
+    /// 추가한 명령어 제거 (내장 명령어는 제거하지 않음)
+    pub fn unregister(&mut self, full_name: &str) -> Option<CommandSpec> {
+        let index = self.commands.iter()
+            .position(|spec| spec.source != CommandSource::Builtin && spec.full_name() == full_name)?;
+        Some(self.commands.remove(index))
+    }
+
+    /// 등록한 곳이 같은 명령어를 모두 제거 (제거한 정의 반환)
+    pub fn unregister_source(&mut self, source: &CommandSource) -> Vec<CommandSpec> {
+        if *source == CommandSource::Builtin {
+            return Vec::new();
+        }
+        let (removed, kept) = std::mem::take(&mut self.commands).into_iter().partition(|spec| spec.source == *source);
+        self.commands = kept;
+        removed
+    }
+
+    /// `help` 출력 (그룹별 명령어 목록)
+    pub fn help(&self) -> String {
+        let mut result = String::new();
+        for group in &self.groups {
+            let aliases = match group.aliases.is_empty() {
+                true => String::new(),
+                false => format!(" ({})", group.aliases.join(", ")),
+            };
+            result.push_str(&format!("{}{} - {}\n", group.name, aliases, group.description));
+            for spec in self.subcommands(&group.name) {
+                result.push_str(&help_line(spec));
+            }
+            result.push('\n');
+        }
+
+        result.push_str("Other commands\n");
+        for spec in self.commands.iter().filter(|spec| spec.group.is_none()) {
+            result.push_str(&help_line(spec));
+        }
+        result.push_str("\nType 'help <command>' for details");
+        result
+    }
+
+    /// `help <명령어>` 출력 (그룹 이름이면 그룹 명령어 목록)
+    pub fn help_for(&self, words: &[&str]) -> Result<String, String> {
+        let (name, subcommand) = match words {
+            [name] => (*name, None),
+            [name, subcommand, ..] => (*name, Some(*subcommand)),
+            [] => return Ok(self.help()),
+        };
+        let spec = match (self.find_group(name), subcommand) {
+            (Some(group), None) => {
+                let mut result = format!("{} - {}\n", group.name, group.description);
+                for spec in self.subcommands(&group.name) {
+                    result.push_str(&help_line(spec));
+                }
+                return Ok(result.trim_end().to_string());
+            },
+            (Some(_), Some(_)) => self.find(name, subcommand),
+            (None, _) => self.find(name, None),
+        };
+        let spec = spec.ok_or_else(|| format!("Unknown command: {}", words.join(" ")))?;
+
+        let mut result = format!("Usage: {}\n", spec.usage());
+        if !spec.description.is_empty() {
+            result.push_str(&format!("{}\n", spec.description));
+        }
+        let aliases: Vec<String> = spec.names[1..].iter()
+            .map(|alias| match &spec.group {
+                Some(group) => format!("{} {}", group, alias),
+                None => alias.clone(),
+            })
+            .chain(spec.shortcuts.iter().cloned())
+            .collect();
+        if !aliases.is_empty() {
+            result.push_str(&format!("Aliases: {}\n", aliases.join(", ")));
+        }
+        if spec.input {
+            result.push_str("Accepts piped items as the selector\n");
+        }
+        match &spec.source {
+            CommandSource::Builtin => {},
+            CommandSource::Preset(preset) => result.push_str(&format!("Added by preset {}\n", preset)),
+            CommandSource::Script => result.push_str("Defined by a script\n"),
+        }
+        if !spec.examples.is_empty() {
+            result.push_str("Examples:\n");
+            for example in &spec.examples {
+                result.push_str(&format!("  {}\n", example));
+            }
+        }
+        Ok(result.trim_end().to_string())
+    }
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        Self::builtin().clone()
+    }
+}
+
+/// 명령어 목록 한 줄 (이름과 별칭, 인자 형식, 설명)
+fn help_line(spec: &CommandSpec) -> String {
+    let mut names = spec.names.clone();
+    names.extend(spec.shortcuts.iter().filter(|shortcut| !spec.names.contains(shortcut)).cloned());
+    format!("  {:<18} {:<28} {}\n", names.join(", "), spec.args, spec.description)
+}
+
+fn builtin_groups() -> Vec<CommandGroup> {
+    let group = |name: &str, aliases: &[&str], description: &str| CommandGroup {
+        name: name.to_string(),
+        aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+        description: description.to_string(),
+    };
+    vec![
+        group("nav", &["navigator"], "Navigator (current target)"),
+        group("log", &[], "Query results"),
+        group("lib", &[], "Saved items"),
+        group("meta", &[], "Labels and tags"),
+        group("list", &[], "Classes, modules and memory ranges"),
+        group("mem", &[], "Memory access and monitoring"),
+        group("trace", &[], "Call tracing and coverage"),
+        group("attach", &[], "Hooks and calls"),
+        group("scan", &[], "Memory scanner"),
+        group("preset", &[], "Hook presets"),
+        group("event", &["ev"], "Agent event log"),
+        group("on", &[], "Script event handlers"),
+    ]
+}
+
+macro_rules! spec {
+    ($group:expr, [$name:expr $(, $alias:expr)*], [$($shortcut:expr),*], $args:expr, $command_type:ident, $description:expr, [$($example:expr),*]) => {
+        {
+            let spec = CommandSpec::new($name, CommandType::$command_type)$(.alias($alias))*$(.shortcut($shortcut))*
+                .args($args)
+                .description($description)$(.example($example))*;
+            match $group {
+                Some(group) => spec.group(group),
+                None => spec,
+            }
+        }
+    };
+}
+
+/// 내장 명령어 표
+fn builtin_commands() -> Vec<CommandSpec> {
+    vec![
+        // Navigator 명령어
+        spec!(Some("nav"), ["select", "sel"], ["sel"], "<selector>", NavSelect,
+            "Select an item as the current target", ["sel lib:0", "sel 3", "sel -1", "sel lib:@player_hp"]).input(),
+        spec!(Some("nav"), ["unselect", "unsel"], ["unsel"], "", NavUnselect,
+            "Clear the current target", []),
+        spec!(Some("nav"), ["add", "+"], ["+"], "<offset>", NavAdd,
+            "Move the target address forward", ["+ 0x10"]),
+        spec!(Some("nav"), ["sub", "-"], ["-"], "<offset>", NavSub,
+            "Move the target address backward", ["- 8"]),
+        spec!(Some("nav"), ["goto", "go", ":"], [":"], "<address|bookmark>", NavGoto,
+            "Go to an address, the address of an item, or a bookmark", [": 0x7f001000", ": lib:2", ": lib:@player_hp", "nav go @player"]),
+        spec!(Some("nav"), ["back"], ["back"], "[count]", NavBack,
+            "Go back in the navigation history", ["back", "back 2"]),
+        spec!(Some("nav"), ["forward", "fwd"], ["forward"], "[count]", NavForward,
+            "Go forward in the navigation history", ["forward"]),
+        spec!(Some("nav"), ["history", "hist"], [], "", NavHistory,
+            "Show the navigation history", ["nav history"]),
+        spec!(Some("nav"), ["mark"], [], "[name]", NavMark,
+            "Bookmark the current target, or list bookmarks", ["nav mark player", "nav mark"]),
+        spec!(Some("nav"), ["unmark"], [], "<bookmark>", NavUnmark,
+            "Remove a bookmark", ["nav unmark player"]),
+
+        // Log 명령어
+        spec!(Some("log"), ["list", "lg"], ["lg"], "[filter...]", LogList,
+            "Show the current page of query results, or those matching a filter", ["lg type=function"]),
+        spec!(Some("log"), ["next", "nxt"], ["nxt"], "[count]", LogNext,
+            "Show the next page", ["nxt 2"]),
+        spec!(Some("log"), ["prev", "prv"], ["prv"], "[count]", LogPrev,
+            "Show the previous page", []),
+        spec!(Some("log"), ["sort"], [], "[field]", LogSort,
+            "Sort query results by name, address or type", ["log sort address"]),
+        spec!(Some("log"), ["grep"], ["grep"], "<filter...> [-v|--invert]", LogGrep,
+            "Show query results matching a filter", ["grep name:open", "grep -v type=function", "grep float<30"]).input(),
+        spec!(Some("log"), ["filter", "grep!"], ["grep!"], "<filter...> [-v|--invert]", LogFilter,
+            "Keep only query results matching a filter", ["grep! size>4096 & protection:x"]),
+
+        // Library 명령어
+        spec!(Some("lib"), ["list", "ls"], ["ls"], "", LibList,
+            "Show saved items", []),
+        spec!(Some("lib"), ["save", "sav"], ["sav"], "<selector|filter>", LibSave,
+            "Save query results to the library", ["sav 0-3", "sav 5-", "sav all", "sav name:open", "exports libc.so | sav"]).input(),
+        spec!(Some("lib"), ["move", "mv"], ["mv"], "<from> <to>", LibMove,
+            "Move a library item to another index", ["mv 4 0"]),
+        spec!(Some("lib"), ["remove", "rm"], ["rm"], "<selector|filter>", LibRemove,
+            "Remove library items", ["rm 2", "rm 1,3", "rm -1", "rm #unused", "rm tags=unused"]).input(),
+        spec!(Some("lib"), ["clear", "clr"], ["clr"], "[filter]", LibClear,
+            "Remove all library items, or those matching a filter", ["clr type=function"]),
+
+        // Meta 명령어
+        spec!(Some("meta"), ["label"], [], "<selector> <label>", MetaLabel,
+            "Set the label of items", ["meta label lib:0 player_hp"]).input(),
+        spec!(Some("meta"), ["tag"], [], "<selector> <tag>", MetaTag,
+            "Add a tag to items", ["meta tag lib:0-2 ammo"]).input(),
+        spec!(Some("meta"), ["untag"], [], "<selector> <tag>", MetaUntag,
+            "Remove a tag from items", []).input(),
+        spec!(Some("meta"), ["tags"], [], "<selector>", MetaTags,
+            "Show the tags of items", []).input(),
+
+        // List 명령어
+        spec!(Some("list"), ["class"], ["class"], "[pattern]", ListClass,
+            "List loaded classes (/regex/ supported)", ["class Activity", "class /^com\\.example/"]),
+        spec!(Some("list"), ["method"], ["method"], "[selector] [pattern] [-i|--inherited]", ListMethod,
+            "List methods of a class", ["method lib:0 onCreate", "method -i"]).input(),
+        spec!(Some("list"), ["module"], ["module"], "[pattern]", ListModule,
+            "List loaded modules", ["module libc"]),
+        spec!(Some("list"), ["exports"], ["exports"], "[module] [export]", ListExports,
+            "List module exports", ["exports libc.so open"]),
+        spec!(Some("list"), ["imports"], ["imports"], "[module] [pattern]", ListImports,
+            "List module imports", []),
+        spec!(Some("list"), ["symbols"], ["symbols"], "[module] [pattern]", ListSymbols,
+            "List module symbols", []),
+        spec!(Some("list"), ["range"], ["range"], "[protection]", ListRange,
+            "List memory ranges by protection", ["range rw-"]),
+
+        // Memory 명령어
+        spec!(Some("mem"), ["dump", "d"], ["d"], "[address] [size]", MemDump,
+            "Hex dump memory", ["d 0x7f001000 64"]),
+        spec!(Some("mem"), ["read", "r"], ["r"], "[address] [type]", MemRead,
+            "Read a value", ["r lib:0 float"]),
+        spec!(Some("mem"), ["write", "w"], ["w"], "[address] <value> [type]", MemWrite,
+            "Write a value", ["w lib:0 100 int"]),
+        spec!(Some("mem"), ["list", "lm"], ["lm"], "", MemList,
+            "Show watches, locks and traces", []),
+        spec!(Some("mem"), ["watch"], [], "[address] [type]", MemWatch,
+            "Report writes to an address", ["mem watch lib:0 int"]),
+        spec!(Some("mem"), ["lock"], [], "[address] <value> [type] [below|above] [threshold] [every] [ms]", MemLock,
+            "Keep a value fixed", ["mem lock lib:0 100 int", "mem lock lib:0 100 int below 50"]),
+        spec!(Some("mem"), ["locks"], [], "", MemLocks,
+            "Show active locks", []),
+        spec!(Some("mem"), ["trace"], [], "[address|module] [pattern] [-m|--module]", MemTrace,
+            "Trace calls to a function or module exports", ["mem trace lib:1", "mem trace -m libc.so open*"]),
+        spec!(Some("mem"), ["unwatch"], [], "<selector>", MemUnwatch,
+            "Stop watching", []),
+        spec!(Some("mem"), ["unlock"], [], "[selector]", MemUnlock,
+            "Remove locks", []),
+        spec!(Some("mem"), ["untrace"], [], "[selector]", MemUntrace,
+            "Stop tracing", []),
+        spec!(Some("mem"), ["events"], [], "[selector]", MemEvents,
+            "Show recorded watch and trace events", []),
+        spec!(Some("mem"), ["type"], [], "[address] <type>", MemType,
+            "Change the value type of an item", []),
+        spec!(Some("mem"), ["disas"], [], "[address] [count]", MemDisas,
+            "Disassemble instructions", []),
+
+        // Trace 명령어
+        spec!(Some("trace"), ["tree"], [], "[thread] [-a|--all]", TraceTree,
+            "Show traced calls as a call tree", []),
+        spec!(Some("trace"), ["stats"], [], "", TraceStats,
+            "Show call counts per function", []),
+        spec!(Some("trace"), ["export"], [], "<file>", TraceExport,
+            "Export traced calls to a file", ["trace export calls.json"]),
+        spec!(Some("trace"), ["clear"], [], "", TraceClear,
+            "Clear traced calls", []),
+        spec!(Some("trace"), ["stalk"], [], "[address|thread] [id]", TraceStalk,
+            "Collect basic block coverage with Stalker", ["trace stalk thread 1234"]),
+        spec!(Some("trace"), ["unstalk"], [], "[id]", TraceUnstalk,
+            "Stop collecting coverage", []),
+        spec!(Some("trace"), ["coverage", "cov"], [], "", TraceCoverage,
+            "Show collected coverage", []),
+        spec!(Some("trace"), ["drcov"], [], "<file>", TraceDrcov,
+            "Export coverage in drcov format", ["trace drcov game.drcov"]),
+
+        // Attacher 명령어
+        spec!(Some("attach"), ["hook"], [], "[address]", AttachHook,
+            "Hook a function", []),
+        spec!(Some("attach"), ["unhook"], [], "[id]", AttachUnhook,
+            "Remove a hook", []),
+        spec!(Some("attach"), ["call"], [], "[address] [args...]", AttachCall,
+            "Call a function", []),
+        spec!(Some("attach"), ["list", "la"], ["la"], "", AttachList,
+            "Show hooks", []),
+
+        // Scanner 명령어
+        spec!(Some("scan"), ["search"], ["search"], "<value> [type]", ScanSearch,
+            "Start a value scan", ["search 100 int"]),
+        spec!(Some("scan"), ["exact"], ["exact"], "<value>", ScanExact,
+            "Keep results equal to a value", []),
+        spec!(Some("scan"), ["min"], ["min"], "", ScanMin,
+            "Keep results with the smallest value", []),
+        spec!(Some("scan"), ["max"], ["max"], "", ScanMax,
+            "Keep results with the largest value", []),
+        spec!(Some("scan"), ["inc"], ["inc"], "", ScanInc,
+            "Keep results that increased", []),
+        spec!(Some("scan"), ["dec"], ["dec"], "", ScanDec,
+            "Keep results that decreased", []),
+        spec!(Some("scan"), ["ch"], ["ch"], "", ScanCh,
+            "Keep results that changed", []),
+        spec!(Some("scan"), ["unch"], ["unch"], "", ScanUnch,
+            "Keep results that did not change", []),
+
+        // Preset 명령어
+        spec!(Some("preset"), ["list", "ls"], [], "", PresetList,
+            "Show presets", []),
+        spec!(Some("preset"), ["apply"], [], "<preset> [option=value...]", PresetApply,
+            "Apply a preset", ["preset apply antiroot", "preset apply speedhack factor=0.5"]),
+        spec!(Some("preset"), ["revert"], [], "<preset|all>", PresetRevert,
+            "Revert a preset", ["preset revert all"]),
+        spec!(Some("preset"), ["status"], [], "[preset]", PresetStatus,
+            "Show hook status of applied presets", []),
+        spec!(Some("preset"), ["set"], [], "<preset> <option> <value>", PresetSet,
+            "Change a preset option", ["preset set speedhack factor 3"]),
+        spec!(Some("preset"), ["info"], [], "<preset>", PresetInfo,
+            "Show preset details and parameters", []),
+
+        // 에이전트 이벤트 로그
+        spec!(Some("event"), ["list", "ls"], [], "[category] [key=value...]", EventList,
+            "Show agent events (filters: id, script, grep, after, last)", ["ev ls hook last=20"]),
+        spec!(Some("event"), ["clear", "clr"], [], "", EventClear,
+            "Clear the event log", []),
+
+        // 스크립트 이벤트 핸들러
+        spec!(Some("on"), ["list", "ls"], [], "", OnList,
+            "Show script event handlers", []),
+        spec!(Some("on"), ["remove", "rm"], [], "<id|all>", OnRemove,
+            "Remove script event handlers", ["on rm all"]),
+
+        // Utilities
+        spec!(None, ["fields"], [], "", Fields,
+            "Show filter fields", []),
+        spec!(None, ["help"], [], "[command] [subcommand]", Help,
+            "Show commands, or details of one command", ["help", "help lib save", "help sav"]),
+        spec!(None, ["run"], [], "<file> [-c|--continue]", Run,
+            "Run a .vzs script", ["run setup.vzs -c"]),
+        spec!(None, ["exit", "quit"], [], "", Exit,
+            "Exit vlitz", []),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_table_has_no_conflicts() {
+        let mut registry = CommandRegistry::empty();
+        for spec in CommandRegistry::builtin().commands() {
+            registry.register(spec.clone()).unwrap();
+            for name in &spec.names {
+                let found = match &spec.group {
+                    Some(group) => registry.find(group, Some(name)),
+                    None => registry.find(name, None),
+                };
+                assert_eq!(found.unwrap().command_type, spec.command_type, "{}", spec.full_name());
+            }
+        }
+
+        let spec = registry.find("ls", None).unwrap();
+        assert_eq!(spec.full_name(), "lib list");
+        let spec = registry.find("list", Some("method")).unwrap();
+        assert_eq!(spec.arg_slots(), vec!["selector", "pattern"]);
+        assert_eq!(spec.flags(), vec!["-i", "--inherited"]);
+    }
+
+    #[test]
+    fn test_register_and_validate() {
+        let mut registry = CommandRegistry::default();
+        assert!(registry.register(CommandSpec::new("sav", CommandType::Registered)).is_err());
+        assert!(registry.register(CommandSpec::new("save", CommandType::Registered).group("lib")).is_err());
+        assert!(registry.register(CommandSpec::new("x", CommandType::Registered).group("nope")).is_err());
+
+        let speed = CommandSpec::new("speed", CommandType::Registered)
+            .args("<factor>")
+            .source(CommandSource::Preset("speedhack".to_string()));
+        registry.register(speed).unwrap();
+        assert_eq!(registry.find("speed", None).unwrap().usage(), "speed <factor>");
+        assert_eq!(registry.unregister_source(&CommandSource::Preset("speedhack".to_string())).len(), 1);
+        assert!(registry.find("speed", None).is_none());
+        assert!(registry.unregister("lib save").is_none());
+
+        let check = |input: &str| registry.check(&Command::parse(input).unwrap()).map(|spec| spec.full_name());
+        assert_eq!(check("sav 0").unwrap(), "lib save");
+        assert_eq!(check("meta label 0").unwrap_err(), "Missing argument <label> for 'meta label' (usage: meta label <selector> <label>)");
+        assert_eq!(check("w").unwrap_err(), "Missing argument <value> for 'mem write' (usage: mem write [address] <value> [type])");
+        assert!(check("method --all").unwrap_err().starts_with("Unknown flag '--all' for 'list method'"));
+        assert_eq!(check("lib nope").unwrap_err(), "Unknown subcommand 'nope' for 'lib' (expected list, save, move, remove, clear)");
+        assert_eq!(check("foo").unwrap_err(), "Unknown command");
+    }
+
+    #[test]
+    fn test_help_output() {
+        let registry = CommandRegistry::builtin();
+        let help = registry.help();
+        assert!(help.contains("lib - Saved items\n  list, ls"));
+        assert!(help.contains("  save, sav          <selector|filter>            Save query results to the library\n"));
+
+        let detail = registry.help_for(&["sav"]).unwrap();
+        assert_eq!(detail, "Usage: lib save <selector|filter>\nSave query results to the library\nAliases: lib sav, sav\nAccepts piped items as the selector\nExamples:\n  sav 0-3\n  sav 5-\n  sav all\n  sav name:open\n  exports libc.so | sav");
+        assert_eq!(registry.help_for(&["lib", "save"]).unwrap(), detail);
+        assert!(registry.help_for(&["ev"]).unwrap().starts_with("event - Agent event log\n  list, ls"));
+        assert_eq!(registry.help_for(&["nope"]).unwrap_err(), "Unknown command: nope");
+    }
+}
@@ -1,4 +1,4 @@
-use vlitz_shared::{VzData, VlitzError, VlitzResult, Selector, FilterCondition};
+use vlitz_shared::{VzData, VlitzError, VlitzResult, SelectorSource};
 use vlitz_shared::filter::FilterParser;
 
 /// 데이터 스토어 구조체 (log, lib 데이터 관리)
@@ -11,6 +11,8 @@ pub struct DataStore {
     current_page: usize,
     /// 페이지당 항목 수
     items_per_page: usize,
+    /// 로그가 교체될 때마다 증가하는 세대 번호
+    log_generation: u64,
 }
 
 impl DataStore {
@@ -21,6 +23,7 @@ impl DataStore {
             log: Vec::new(),
             current_page: 0,
             items_per_page,
+            log_generation: 0,
         }
     }
 
@@ -38,17 +41,33 @@ impl DataStore {
         start_index
     }
 
-    /// 로그에서 라이브러리로 데이터 이동
-    pub fn save_to_lib(&mut self, selector: &Selector) -> VlitzResult<usize> {
-        let data = self.select_data(selector)?;
-        let start_index = self.lib.len();
-        
-        // 데이터 복제
-        for item in data {
-            self.lib.push(item.clone());
-        }
-        
-        Ok(start_index)
+    /// 로그를 새 데이터로 교체 (list 명령어 결과)
+    pub fn replace_log(&mut self, data: Vec<VzData>) {
+        self.log = data;
+        self.current_page = 0;
+        self.log_generation += 1;
+    }
+
+    /// 로그에서 주어진 인덱스의 항목만 남김 (순서 유지, 새 로그로 취급)
+    pub fn retain_log(&mut self, indices: &[usize]) -> usize {
+        let data: Vec<VzData> = indices.iter()
+            .filter_map(|&idx| self.log.get(idx).cloned())
+            .collect();
+        let kept = data.len();
+        self.replace_log(data);
+        kept
+    }
+
+    /// 로그 세대 번호 (명령어가 로그를 새로 채웠는지 확인할 때 사용)
+    pub fn log_generation(&self) -> u64 {
+        self.log_generation
+    }
+
+    /// 라이브러리 끝에 데이터 추가 (추가한 항목 수 반환)
+    pub fn save_to_lib(&mut self, data: Vec<VzData>) -> usize {
+        let count = data.len();
+        self.lib.extend(data);
+        count
     }
 
     /// 라이브러리 내에서 데이터 이동
@@ -68,27 +87,20 @@ impl DataStore {
         Ok(())
     }
 
-    /// 라이브러리에서 데이터 제거
-    pub fn remove_from_lib(&mut self, selector: &Selector) -> VlitzResult<usize> {
-        let indices = selector.get_indices(self.lib.len(), self.log.len());
-        
-        // 내림차순으로 정렬 (제거 시 인덱스 변화 방지)
-        let mut indices: Vec<_> = indices.into_iter()
+    /// 라이브러리에서 주어진 인덱스의 항목 제거 (제거한 항목 수 반환)
+    pub fn remove_from_lib(&mut self, indices: &[usize]) -> usize {
+        // 내림차순으로 제거 (제거 시 인덱스 변화 방지)
+        let mut indices: Vec<_> = indices.iter()
+            .copied()
             .filter(|&idx| idx < self.lib.len())
             .collect();
         indices.sort_unstable_by(|a, b| b.cmp(a));
-        
-        let mut removed = 0;
-        for idx in indices {
+        indices.dedup();
+
+        for &idx in &indices {
             self.lib.remove(idx);
-            removed += 1;
-        }
-        
-        if removed == 0 {
-            return Err(VlitzError::Selector("No data found for selector".to_string()));
         }
-        
-        Ok(removed)
+        indices.len()
     }
 
     /// 라이브러리 초기화
@@ -105,45 +117,13 @@ impl DataStore {
         Ok(removed)
     }
 
-    /// 셀렉터로 데이터 선택
-    pub fn select_data<'a>(&'a self, selector: &Selector) -> VlitzResult<Vec<&'a VzData>> {
-        let indices = selector.get_indices(self.lib.len(), self.log.len());
-        let mut result = Vec::new();
-        
-        for idx in indices {
-            if idx < self.lib.len() {
-                result.push(&self.lib[idx]);
-            } else if idx < self.log.len() {
-                result.push(&self.log[idx]);
-            }
-        }
-        
-        if result.is_empty() {
-            return Err(VlitzError::Selector("No data found for selector".to_string()));
-        }
-        
-        Ok(result)
-    }
-
-    /// 필터로 데이터 필터링
-    pub fn filter_data<'a>(&'a self, filter: &FilterCondition) -> Vec<(usize, &'a VzData)> {
-        let mut result = Vec::new();
-        
-        // 라이브러리 데이터 필터링
-        for (idx, data) in self.lib.iter().enumerate() {
-            if filter.apply(data) {
-                result.push((idx, data));
-            }
-        }
-        
-        // 로그 데이터 필터링
-        for (idx, data) in self.log.iter().enumerate() {
-            if filter.apply(data) {
-                result.push((idx + self.lib.len(), data));
-            }
+    /// 셀렉터 대상 목록 (내비게이터 항목은 저장소에 없으므로 빈 목록)
+    pub fn items(&self, source: SelectorSource) -> &[VzData] {
+        match source {
+            SelectorSource::Lib => &self.lib,
+            SelectorSource::Log => &self.log,
+            SelectorSource::Nav => &[],
         }
-        
-        result
     }
 
     /// 로그 현재 페이지 가져오기
@@ -176,6 +156,12 @@ impl DataStore {
             .collect()
     }
 
+    /// 로그 페이지 정보 (현재 페이지, 전체 페이지 수)
+    pub fn log_page_info(&self) -> (usize, usize) {
+        let page_count = self.log.len().div_ceil(self.items_per_page);
+        (self.current_page, page_count)
+    }
+
     /// 로그 다음 페이지로 이동
     pub fn next_log_page(&mut self, count: usize) -> VlitzResult<usize> {
         let page_count = (self.log.len() + self.items_per_page - 1) / self.items_per_page;
@@ -205,6 +191,9 @@ impl DataStore {
         Ok(self.current_page)
     }
 
+    /// 로그 정렬 기준으로 쓸 수 있는 필드
+    pub const SORT_FIELDS: &'static [&'static str] = &["name", "address", "type"];
+
     /// 로그 정렬
     pub fn sort_log(&mut self, field: &str) -> VlitzResult<()> {
         match field {
@@ -243,28 +232,22 @@ impl DataStore {
         &self.log
     }
 
-    /// 데이터 변경
-    pub fn get_data_mut<'a>(&'a mut self, selector: &Selector) -> VlitzResult<Vec<&'a mut VzData>> {
-        let indices = selector.get_indices(self.lib.len(), self.log.len());
-        let mut result = Vec::new();
-        
-        // 인덱스 중복 제거
-        let mut unique_indices = indices;
-        unique_indices.sort_unstable();
-        unique_indices.dedup();
-        
-        for idx in unique_indices {
-            if idx < self.lib.len() {
-                result.push(&mut self.lib[idx]);
-            } else if idx - self.lib.len() < self.log.len() {
-                result.push(&mut self.log[idx - self.lib.len()]);
-            }
-        }
-        
+    /// 주어진 목록의 인덱스에 해당하는 항목 (변경용)
+    pub fn get_data_mut(&mut self, source: SelectorSource, indices: &[usize]) -> VlitzResult<Vec<&mut VzData>> {
+        let items = match source {
+            SelectorSource::Lib => &mut self.lib,
+            SelectorSource::Log => &mut self.log,
+            SelectorSource::Nav => return Err(VlitzError::Selector("Navigator items cannot be modified (use lib: or log:)".to_string())),
+        };
+        let result: Vec<&mut VzData> = items.iter_mut()
+            .enumerate()
+            .filter(|(idx, _)| indices.contains(idx))
+            .map(|(_, data)| data)
+            .collect();
+
         if result.is_empty() {
             return Err(VlitzError::Selector("No data found for selector".to_string()));
         }
-        
         Ok(result)
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use vlitz_runtime::{TraceBatch, TraceEventKind};
+
+/// 함수 호출 한 건의 기록
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallRecord {
+    pub trace_id: u64,
+    pub name: String,
+    pub thread_id: u64,
+    /// 스레드 내 중첩 깊이
+    pub depth: usize,
+    /// 진입 시각 (밀리초)
+    pub start: f64,
+    /// 실행 시간 (밀리초, 아직 반환되지 않았으면 None)
+    pub duration: Option<f64>,
+    pub args: Vec<String>,
+    pub retval: Option<String>,
+    /// 호출한 기록의 인덱스 (추적 중인 함수끼리의 호출 관계)
+    pub parent: Option<usize>,
+    #[serde(skip)]
+    children: Vec<usize>,
+}
+
+/// 함수별 호출 통계
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionStats {
+    pub name: String,
+    pub calls: usize,
+    /// 반환까지 확인된 호출 수 (시간 통계 기준)
+    pub completed: usize,
+    pub total: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl FunctionStats {
+    /// 평균 실행 시간 (밀리초)
+    pub fn average(&self) -> f64 {
+        if self.completed == 0 {
+            0.0
+        } else {
+            self.total / self.completed as f64
+        }
+    }
+}
+
+/// 추적 이벤트를 스레드별로 짝지어 만든 호출 기록
+///
+/// enter 이벤트는 해당 스레드의 열린 호출 스택에 쌓이고,
+/// leave 이벤트는 스택에서 같은 추적 ID의 가장 가까운 호출을 닫는다.
+/// 그 위에 남은 호출(예외 등으로 반환되지 않은 호출)은 닫히지 않은 채로 버려진다.
+#[derive(Debug, Default)]
+pub struct CallTrace {
+    records: Vec<CallRecord>,
+    /// 추적 ID -> 함수 이름
+    names: HashMap<u64, String>,
+    /// 스레드 ID -> 열린 호출 인덱스 스택
+    stacks: HashMap<u64, Vec<usize>>,
+    /// 에이전트 버퍼 초과로 버려진 이벤트 수
+    dropped: usize,
+}
+
+impl CallTrace {
+    /// 새 호출 기록 생성
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 추적 ID의 함수 이름 등록
+    pub fn register(&mut self, trace_id: u64, name: String) {
+        self.names.insert(trace_id, name);
+    }
+
+    /// 에이전트에서 가져온 이벤트 반영
+    pub fn ingest(&mut self, batch: TraceBatch) {
+        self.dropped += batch.dropped;
+
+        for event in batch.events {
+            let stack = self.stacks.entry(event.thread_id).or_default();
+
+            match event.kind {
+                TraceEventKind::Enter => {
+                    let index = self.records.len();
+                    let parent = stack.last().copied();
+                    stack.push(index);
+
+                    if let Some(parent) = parent {
+                        self.records[parent].children.push(index);
+                    }
+                    self.records.push(CallRecord {
+                        trace_id: event.trace_id,
+                        name: self.names.get(&event.trace_id)
+                            .cloned()
+                            .unwrap_or_else(|| format!("trace#{}", event.trace_id)),
+                        thread_id: event.thread_id,
+                        depth: event.depth,
+                        start: event.timestamp,
+                        duration: None,
+                        args: event.args,
+                        retval: None,
+                        parent,
+                        children: Vec::new(),
+                    });
+                },
+                TraceEventKind::Leave => {
+                    // 추적 시작 전에 진입한 호출의 leave는 짝이 없으므로 무시
+                    let records = &self.records;
+                    if let Some(pos) = stack.iter().rposition(|&idx| records[idx].trace_id == event.trace_id) {
+                        let index = stack[pos];
+                        stack.truncate(pos);
+
+                        let record = &mut self.records[index];
+                        record.duration = Some(event.timestamp - record.start);
+                        record.retval = event.retval;
+                    }
+                },
+            }
+        }
+    }
+
+    /// 기록된 호출 수
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// 기록이 비었는지 여부
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// 버려진 이벤트 수
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// 특정 추적의 호출 수
+    pub fn call_count(&self, trace_id: u64) -> usize {
+        self.records.iter().filter(|record| record.trace_id == trace_id).count()
+    }
+
+    /// 호출 기록 초기화 (추적 이름은 유지)
+    pub fn clear(&mut self) {
+        self.records.clear();
+        self.stacks.clear();
+        self.dropped = 0;
+    }
+
+    /// 호출 트리 출력 (최근 `limit`개의 최상위 호출)
+    pub fn format_tree(&self, thread_id: Option<u64>, limit: usize) -> Vec<String> {
+        let roots: Vec<usize> = self.records.iter()
+            .enumerate()
+            .filter(|(_, record)| record.parent.is_none())
+            .filter(|(_, record)| thread_id.is_none_or(|tid| record.thread_id == tid))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let mut lines = Vec::new();
+        for &root in &roots[roots.len().saturating_sub(limit)..] {
+            self.format_node(root, 0, &mut lines);
+        }
+        lines
+    }
+
+    fn format_node(&self, index: usize, level: usize, lines: &mut Vec<String>) {
+        let record = &self.records[index];
+        let mut line = format!("{}{}({})", "  ".repeat(level), record.name, record.args.join(", "));
+
+        if let Some(retval) = &record.retval {
+            line.push_str(&format!(" = {}", retval));
+        }
+        match record.duration {
+            Some(duration) => line.push_str(&format!(" [{:.3}ms]", duration)),
+            None => line.push_str(" [no return]"),
+        }
+        if level == 0 {
+            line.push_str(&format!(" [tid {}]", record.thread_id));
+        }
+        lines.push(line);
+
+        for &child in &record.children {
+            self.format_node(child, level + 1, lines);
+        }
+    }
+
+    /// 함수별 통계 (총 실행 시간 내림차순)
+    pub fn stats(&self) -> Vec<FunctionStats> {
+        let mut stats: Vec<FunctionStats> = Vec::new();
+
+        for record in &self.records {
+            let index = match stats.iter().position(|s| s.name == record.name) {
+                Some(index) => index,
+                None => {
+                    stats.push(FunctionStats {
+                        name: record.name.clone(),
+                        calls: 0,
+                        completed: 0,
+                        total: 0.0,
+                        min: f64::MAX,
+                        max: 0.0,
+                    });
+                    stats.len() - 1
+                },
+            };
+
+            let entry = &mut stats[index];
+            entry.calls += 1;
+            if let Some(duration) = record.duration {
+                entry.completed += 1;
+                entry.total += duration;
+                entry.min = entry.min.min(duration);
+                entry.max = entry.max.max(duration);
+            }
+        }
+
+        for entry in &mut stats {
+            if entry.completed == 0 {
+                entry.min = 0.0;
+            }
+        }
+        stats.sort_by(|a, b| b.total.total_cmp(&a.total).then_with(|| b.calls.cmp(&a.calls)));
+        stats
+    }
+
+    /// 분석용 JSON 내보내기 (호출 목록 + 버려진 이벤트 수)
+    pub fn export_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&serde_json::json!({
+            "dropped": self.dropped,
+            "calls": self.records,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vlitz_runtime::TraceEvent;
+
+    fn event(trace_id: u64, kind: TraceEventKind, thread_id: u64, timestamp: f64) -> TraceEvent {
+        TraceEvent {
+            trace_id,
+            kind,
+            thread_id,
+            depth: 0,
+            timestamp,
+            args: Vec::new(),
+            retval: (kind == TraceEventKind::Leave).then(|| "0x0".to_string()),
+        }
+    }
+
+    fn sample_trace() -> CallTrace {
+        let mut trace = CallTrace::new();
+        trace.register(1, "outer".to_string());
+        trace.register(2, "inner".to_string());
+        trace.ingest(TraceBatch {
+            events: vec![
+                event(1, TraceEventKind::Enter, 7, 0.0),
+                event(2, TraceEventKind::Enter, 8, 0.5),
+                event(2, TraceEventKind::Enter, 7, 1.0),
+                event(2, TraceEventKind::Leave, 7, 3.0),
+                event(2, TraceEventKind::Leave, 8, 1.5),
+                event(1, TraceEventKind::Leave, 7, 10.0),
+            ],
+            dropped: 0,
+        });
+        trace
+    }
+
+    #[test]
+    fn test_pairs_calls_per_thread() {
+        let trace = sample_trace();
+        let tree = trace.format_tree(None, 10);
+
+        assert_eq!(tree, vec![
+            "outer() = 0x0 [10.000ms] [tid 7]",
+            "  inner() = 0x0 [2.000ms]",
+            "inner() = 0x0 [1.000ms] [tid 8]",
+        ]);
+        assert_eq!(trace.format_tree(Some(8), 10).len(), 1);
+    }
+
+    #[test]
+    fn test_unreturned_call_is_abandoned() {
+        let mut trace = CallTrace::new();
+        trace.ingest(TraceBatch {
+            events: vec![
+                event(1, TraceEventKind::Enter, 7, 0.0),
+                event(2, TraceEventKind::Enter, 7, 1.0),
+                event(1, TraceEventKind::Leave, 7, 5.0),
+                event(3, TraceEventKind::Leave, 7, 6.0),
+            ],
+            dropped: 2,
+        });
+
+        assert_eq!(trace.records[0].duration, Some(5.0));
+        assert_eq!(trace.records[1].duration, None);
+        assert_eq!(trace.dropped(), 2);
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = sample_trace().stats();
+
+        assert_eq!(stats[0].name, "outer");
+        assert_eq!(stats[1].calls, 2);
+        assert_eq!(stats[1].average(), 1.5);
+        assert_eq!((stats[1].min, stats[1].max), (1.0, 2.0));
+    }
+}
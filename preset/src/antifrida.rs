@@ -0,0 +1,269 @@
+use vlitz_runtime::RpcBackend;
+use vlitz_shared::{VlitzError, VlitzResult};
+use crate::options::{OptionValue, PresetOption, PresetOptions};
+use crate::preset::{Platform, Preset, PresetReport};
+use crate::script::PresetScript;
+
+/// 기본으로 숨기는 Frida 흔적 (대소문자 무시, 부분 일치)
+const FRIDA_PATTERNS: &[&str] = &["frida", "gum-js-loop", "gmain", "gdbus", "linjector", "pool-frida"];
+
+/// Frida 서버 기본 포트
+const FRIDA_PORTS: &[&str] = &["27042", "27043"];
+
+/// 각 대응책을 켜는 옵션 (하나는 켜져 있어야 함)
+const COUNTERMEASURES: &[&str] = &["maps", "threads", "ptrace", "port"];
+
+const ANTIFRIDA_SCRIPT: &str = r#"
+    // /proc/<pid>/maps, /proc/<pid>/task/<tid>/status 등
+    const PROC_FILE = /^\/proc\/(?:self|\d+)\/(?:task\/\d+\/)?(maps|smaps|status|comm)$/;
+    // maps/smaps 항목 시작 줄 (smaps는 이어지는 필드 줄도 같은 항목)
+    const MAPS_HEADER = /^[0-9a-f]+-[0-9a-f]+ /;
+    const FAKE_THREAD_NAME = "Thread-1";
+
+    // 경로 인자 위치 (bionic FORTIFY 변형 포함)
+    const OPEN_FUNCTIONS = [["open", 0], ["openat", 1], ["__open_2", 0], ["__openat_2", 1], ["open64", 0], ["openat64", 1]];
+
+    const PTRACE_TRACEME = 0;
+    const PTRACE_ATTACH = 16;
+    const PTRACE_SEIZE = 0x4206;
+
+    const AF_INET = 2;
+    const AF_INET6 = 10;
+    const ECONNREFUSED = 111;
+
+    // 걸러낼 /proc 파일의 fd -> { kind, skipping }
+    const files = {};
+    // Interceptor.replace에 넘긴 콜백 (GC되지 않도록 스크립트 수명 동안 유지)
+    const callbacks = [];
+
+    const utf8Length = text => unescape(encodeURIComponent(text)).length;
+
+    const setup = options => {
+        const patterns = options.patterns.map(pattern => pattern.toLowerCase());
+        const ports = options.ports.map(Number);
+        const isArtifact = text => {
+            const lower = text.toLowerCase();
+            return patterns.some(pattern => lower.indexOf(pattern) !== -1);
+        };
+
+        const procKind = path => {
+            const match = path === null ? null : PROC_FILE.exec(path);
+            if (match === null) return null;
+            if (match[1] === "maps" || match[1] === "smaps") return options.maps ? "maps" : null;
+            if (match[1] === "comm") return options.threads ? "comm" : null;
+            return options.threads || options.ptrace ? "status" : null;
+        };
+
+        // 걸러낸 내용 (바뀐 것이 없으면 null)
+        const filterMaps = (file, text) => {
+            const trailing = text.endsWith("\n");
+            const lines = (trailing ? text.slice(0, -1) : text).split("\n");
+            const kept = lines.filter(line => {
+                if (MAPS_HEADER.test(line)) file.skipping = isArtifact(line);
+                return !file.skipping;
+            });
+            if (kept.length === lines.length) return null;
+            return kept.length === 0 ? "" : kept.join("\n") + (trailing ? "\n" : "");
+        };
+
+        const filterStatus = text => {
+            let filtered = text;
+            if (options.threads) {
+                filtered = filtered.replace(/^Name:\t(.*)$/m, (line, name) => isArtifact(name) ? "Name:\t" + FAKE_THREAD_NAME : line);
+            }
+            if (options.ptrace) {
+                filtered = filtered.replace(/^TracerPid:\t[1-9]\d*$/m, "TracerPid:\t0");
+            }
+            return filtered === text ? null : filtered;
+        };
+
+        const filterComm = text => isArtifact(text) ? FAKE_THREAD_NAME + "\n" : null;
+
+        const filterChunk = (file, text) => {
+            switch (file.kind) {
+                case "maps":
+                    return filterMaps(file, text);
+                case "status":
+                    return filterStatus(text);
+                default:
+                    return filterComm(text);
+            }
+        };
+
+        if (options.maps || options.threads || options.ptrace) {
+            OPEN_FUNCTIONS.forEach(([name, index]) => {
+                install(name + " /proc", () => attachExport(name, {
+                    onEnter(args) {
+                        this.kind = args[index].isNull() ? null : procKind(args[index].readCString());
+                    },
+                    onLeave(retval) {
+                        const fd = retval.toInt32();
+                        if (this.kind !== null && fd >= 0) files[fd] = { kind: this.kind, skipping: false };
+                    }
+                }));
+            });
+
+            install("read /proc", () => {
+                const readAddress = Module.findExportByName(null, "read");
+                if (readAddress === null) unavailable("read not found");
+                const realRead = new NativeFunction(readAddress, "int", ["int", "pointer", "uint"]);
+
+                const closeListener = Interceptor.attach(Module.getExportByName(null, "close"), {
+                    onEnter(args) {
+                        delete files[args[0].toInt32()];
+                    }
+                });
+                const readListener = Interceptor.attach(readAddress, {
+                    onEnter(args) {
+                        this.fd = args[0].toInt32();
+                        this.file = files[this.fd];
+                        this.buf = args[1];
+                        this.count = args[2].toInt32();
+                    },
+                    onLeave(retval) {
+                        if (this.file === undefined) return;
+                        let length = retval.toInt32();
+                        while (length > 0) {
+                            let filtered;
+                            try {
+                                filtered = filterChunk(this.file, this.buf.readUtf8String(length));
+                            } catch (e) {
+                                return;
+                            }
+                            if (filtered === null) {
+                                if (length !== retval.toInt32()) retval.replace(ptr(length));
+                                return;
+                            }
+                            const size = utf8Length(filtered);
+                            if (size > this.count) return;
+                            if (size > 0) {
+                                Memory.copy(this.buf, Memory.allocUtf8String(filtered), size);
+                                retval.replace(ptr(size));
+                                hit("read /proc", this.file.kind);
+                                return;
+                            }
+                            // 덩어리가 통째로 걸러지면 EOF로 보이지 않도록 다음 덩어리를 읽음
+                            // (콜백 안의 호출은 다시 가로채지 않음)
+                            hit("read /proc", this.file.kind);
+                            length = realRead(this.fd, this.buf, this.count);
+                        }
+                        retval.replace(ptr(length));
+                    }
+                });
+                return () => {
+                    readListener.detach();
+                    closeListener.detach();
+                };
+            });
+        }
+
+        if (options.ptrace) {
+            install("ptrace", () => {
+                const address = Module.findExportByName(null, "ptrace");
+                if (address === null) unavailable("ptrace not found");
+                const original = new NativeFunction(address, "long", ["int", "int", "pointer", "pointer"]);
+                const replacement = new NativeCallback((request, pid, addr, data) => {
+                    if (request === PTRACE_TRACEME || ((request === PTRACE_ATTACH || request === PTRACE_SEIZE) && pid === Process.id)) {
+                        hit("ptrace", "request " + request);
+                        return 0;
+                    }
+                    return original(request, pid, addr, data);
+                }, "long", ["int", "int", "pointer", "pointer"]);
+                callbacks.push(replacement);
+                Interceptor.replace(address, replacement);
+                return () => Interceptor.revert(address);
+            });
+        }
+
+        if (options.port) {
+            install("connect", () => attachExport("connect", {
+                onEnter(args) {
+                    this.blocked = false;
+                    const address = args[1];
+                    if (address.isNull()) return;
+                    const family = address.readU16();
+                    if (family !== AF_INET && family !== AF_INET6) return;
+                    // sin_port/sin6_port는 네트워크 바이트 순서
+                    const port = (address.add(2).readU8() << 8) | address.add(3).readU8();
+                    if (ports.indexOf(port) === -1) return;
+                    this.blocked = true;
+                    hit("connect", "port " + port);
+                    // 주소 길이를 0으로 바꿔 실제 연결 없이 실패시킴
+                    args[2] = ptr(0);
+                },
+                onLeave(retval) {
+                    if (!this.blocked) return;
+                    this.errno = ECONNREFUSED;
+                    retval.replace(ptr(-1));
+                }
+            }));
+        }
+    };
+"#;
+
+/// Frida/디버거 탐지 우회 프리셋
+///
+/// `/proc/self/maps`의 frida-agent 항목, 스레드 이름(`gum-js-loop` 등)과 `TracerPid`를 숨기고,
+/// `ptrace` 자기 자신 attach를 성공한 것처럼 만들고, Frida 서버 포트 연결을 거부한다.
+/// 대응책마다 옵션으로 켜고 끌 수 있다.
+pub struct AntiFridaPreset {
+    script: PresetScript,
+}
+
+impl AntiFridaPreset {
+    pub fn new() -> Self {
+        Self { script: PresetScript::new("antifrida", ANTIFRIDA_SCRIPT) }
+    }
+}
+
+impl Default for AntiFridaPreset {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Preset for AntiFridaPreset {
+    fn name(&self) -> &str {
+        "antifrida"
+    }
+
+    fn description(&self) -> &str {
+        "Hide Frida and debuggers (/proc maps and thread names, TracerPid, ptrace, frida-server port)"
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::Linux
+    }
+
+    fn options(&self) -> Vec<PresetOption> {
+        let list = |items: &[&str]| OptionValue::List(items.iter().map(|item| item.to_string()).collect());
+        vec![
+            PresetOption::new("maps", "Remove Frida entries from /proc/*/maps and smaps", OptionValue::Bool(true)),
+            PresetOption::new("threads", "Rename Frida threads in /proc/*/task/*/status and comm", OptionValue::Bool(true)),
+            PresetOption::new("ptrace", "Fake ptrace(PTRACE_TRACEME) and self-attach, report TracerPid 0", OptionValue::Bool(true)),
+            PresetOption::new("port", "Refuse connect() to the frida-server ports", OptionValue::Bool(true)),
+            PresetOption::new("ports", "Ports refused by the port countermeasure", list(FRIDA_PORTS)),
+            PresetOption::new("patterns", "Substrings that mark a maps entry or thread name as Frida", list(FRIDA_PATTERNS)),
+        ]
+    }
+
+    fn apply(&mut self, session: &mut dyn RpcBackend, options: &PresetOptions) -> VlitzResult<PresetReport> {
+        if !COUNTERMEASURES.iter().any(|name| options.get(name) == Some(&OptionValue::Bool(true))) {
+            return Err(VlitzError::Preset(format!("Enable at least one of {}", COUNTERMEASURES.join(", "))));
+        }
+        if let Some(OptionValue::List(ports)) = options.get("ports") {
+            if let Some(port) = ports.iter().find(|port| port.parse::<u16>().is_err()) {
+                return Err(VlitzError::Preset(format!("Invalid port '{}'", port)));
+            }
+        }
+        self.script.apply(session, options)
+    }
+
+    fn revert(&mut self, session: &mut dyn RpcBackend) -> VlitzResult<()> {
+        self.script.revert(session)
+    }
+
+    fn status(&self, session: &dyn RpcBackend) -> VlitzResult<PresetReport> {
+        self.script.status(session)
+    }
+}
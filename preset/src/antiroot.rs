@@ -0,0 +1,206 @@
+use vlitz_runtime::RpcBackend;
+use vlitz_shared::VlitzResult;
+use crate::options::{OptionValue, PresetOption, PresetOptions};
+use crate::preset::{Platform, Preset, PresetReport};
+use crate::script::PresetScript;
+
+/// 기본으로 숨기는 루팅 관련 경로
+const ROOT_PATHS: &[&str] = &[
+    "/system/bin/su",
+    "/system/xbin/su",
+    "/sbin/su",
+    "/su/bin/su",
+    "/system/su",
+    "/system/bin/.ext/.su",
+    "/system/usr/we-need-root/su-backup",
+    "/system/xbin/mu",
+    "/data/local/su",
+    "/data/local/bin/su",
+    "/data/local/xbin/su",
+    "/cache/su",
+    "/dev/su",
+    "/system/app/Superuser.apk",
+    "/system/xbin/busybox",
+    "/system/bin/busybox",
+    "/sbin/magisk",
+    "/data/adb/magisk",
+];
+
+/// 기본으로 숨기는 루팅 관리 앱 패키지
+const ROOT_PACKAGES: &[&str] = &[
+    "com.topjohnwu.magisk",
+    "eu.chainfire.supersu",
+    "com.noshufou.android.su",
+    "com.koushikdutta.superuser",
+    "com.thirdparty.superuser",
+    "com.yellowes.su",
+    "com.kingroot.kinguser",
+    "com.kingo.root",
+    "com.zhiqupk.root.global",
+    "me.phh.superuser",
+];
+
+/// RootBeer 검사 메서드 (모두 false 반환)
+const ROOTBEER_METHODS: &[&str] = &[
+    "isRooted",
+    "isRootedWithoutBusyBoxCheck",
+    "isRootedWithBusyBoxCheck",
+    "detectRootManagementApps",
+    "detectPotentiallyDangerousApps",
+    "detectRootCloakingApps",
+    "detectTestKeys",
+    "checkForBusyBoxBinary",
+    "checkForSuBinary",
+    "checkForMagiskBinary",
+    "checkSuExists",
+    "checkForRWPaths",
+    "checkForDangerousProps",
+    "checkForRootNative",
+];
+
+const ANTIROOT_SCRIPT: &str = r#"
+    const MISSING_PATH = Memory.allocUtf8String("/vlitz/nonexistent");
+
+    // 네이티브 파일 검사 함수와 경로 인자 위치
+    const FILE_FUNCTIONS = [
+        ["open", 0], ["openat", 1], ["fopen", 0], ["access", 0], ["faccessat", 1],
+        ["stat", 0], ["lstat", 0], ["stat64", 0], ["lstat64", 0], ["fstatat", 1], ["fstatat64", 1]
+    ];
+
+    const setup = options => {
+        const isBlocked = path => path !== null
+            && options.paths.some(blocked => path === blocked || path.startsWith(blocked + "/"));
+        const isSuCommand = command => command !== null
+            && /^(\S*\/)?su(\s|$)|\bwhich\s+su\b/.test(command.toString().trim());
+
+        FILE_FUNCTIONS.forEach(([name, index]) => {
+            install("native " + name, () => attachExport(name, {
+                onEnter(args) {
+                    const path = args[index].readCString();
+                    if (isBlocked(path)) {
+                        hit("native " + name, path);
+                        args[index] = MISSING_PATH;
+                    }
+                }
+            }));
+        });
+
+        install("File.exists", () => replaceJava("java.io.File", "exists", overload => function () {
+            const path = this.getAbsolutePath();
+            if (isBlocked(path)) {
+                hit("File.exists", path);
+                return false;
+            }
+            return overload.call(this);
+        }));
+
+        install("Build.TAGS", () => {
+            if (!Java.available) unavailable("Java runtime not available");
+            let undo = null;
+            Java.performNow(() => {
+                const build = Java.use("android.os.Build");
+                const original = build.TAGS.value;
+                if (original !== null && original.indexOf("test-keys") !== -1) {
+                    hit("Build.TAGS", original);
+                    build.TAGS.value = original.replace("test-keys", "release-keys");
+                }
+                undo = () => Java.performNow(() => {
+                    build.TAGS.value = original;
+                });
+            });
+            return undo;
+        });
+
+        install("RootBeer", () => replaceJavaMethods("com.scottyab.rootbeer.RootBeer", options.rootbeerMethods, overload => function () {
+            hit("RootBeer", overload.methodName);
+            return false;
+        }));
+
+        install("PackageManager.getPackageInfo", () => replaceJava("android.app.ApplicationPackageManager", "getPackageInfo", overload => function (...args) {
+            const name = args[0] !== null ? args[0].toString() : null;
+            if (options.packages.indexOf(name) !== -1) {
+                hit("PackageManager.getPackageInfo", name);
+                throw Java.use("android.content.pm.PackageManager$NameNotFoundException").$new(name);
+            }
+            return overload.apply(this, args);
+        }));
+
+        if (!options.exec) return;
+
+        install("Runtime.exec", () => replaceJava("java.lang.Runtime", "exec", overload => function (...args) {
+            const command = args[0] !== null && args[0].length !== undefined && typeof args[0] !== "string"
+                ? Array.from(args[0]).join(" ")
+                : args[0];
+            if (isSuCommand(command)) {
+                hit("Runtime.exec", command);
+                throw Java.use("java.io.IOException").$new("Cannot run program \"su\": error=2, No such file or directory");
+            }
+            return overload.apply(this, args);
+        }));
+
+        install("ProcessBuilder.start", () => replaceJava("java.lang.ProcessBuilder", "start", overload => function () {
+            const command = this.command().toArray().join(" ");
+            if (isSuCommand(command)) {
+                hit("ProcessBuilder.start", command);
+                throw Java.use("java.io.IOException").$new("Cannot run program \"su\": error=2, No such file or directory");
+            }
+            return overload.call(this);
+        }));
+    };
+"#;
+
+/// Android 루팅 탐지 우회 프리셋
+///
+/// su 바이너리 파일 검사(`open`/`access`/`stat` 등, `File.exists`), `Build.TAGS`,
+/// RootBeer 검사 메서드, 루팅 앱 패키지 조회, `Runtime.exec("su")`를 막는다.
+pub struct AntiRootPreset {
+    script: PresetScript,
+}
+
+impl AntiRootPreset {
+    pub fn new() -> Self {
+        Self { script: PresetScript::new("antiroot", ANTIROOT_SCRIPT) }
+    }
+}
+
+impl Default for AntiRootPreset {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Preset for AntiRootPreset {
+    fn name(&self) -> &str {
+        "antiroot"
+    }
+
+    fn description(&self) -> &str {
+        "Hide root from Android apps (su files, Build.TAGS, RootBeer, root packages, Runtime.exec)"
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::Android
+    }
+
+    fn options(&self) -> Vec<PresetOption> {
+        let list = |items: &[&str]| OptionValue::List(items.iter().map(|item| item.to_string()).collect());
+        vec![
+            PresetOption::new("paths", "Files reported as missing", list(ROOT_PATHS)),
+            PresetOption::new("packages", "Packages reported as not installed", list(ROOT_PACKAGES)),
+            PresetOption::new("rootbeerMethods", "RootBeer methods forced to return false", list(ROOTBEER_METHODS)),
+            PresetOption::new("exec", "Fail Runtime.exec/ProcessBuilder calls that run su", OptionValue::Bool(true)),
+        ]
+    }
+
+    fn apply(&mut self, session: &mut dyn RpcBackend, options: &PresetOptions) -> VlitzResult<PresetReport> {
+        self.script.apply(session, options)
+    }
+
+    fn revert(&mut self, session: &mut dyn RpcBackend) -> VlitzResult<()> {
+        self.script.revert(session)
+    }
+
+    fn status(&self, session: &dyn RpcBackend) -> VlitzResult<PresetReport> {
+        self.script.status(session)
+    }
+}
@@ -0,0 +1,189 @@
+pub mod options;
+pub mod preset;
+pub mod script;
+pub mod manager;
+pub mod antiroot;
+pub mod unpinning;
+pub mod speedhack;
+pub mod antifrida;
+pub mod user;
+
+pub use options::*;
+pub use preset::*;
+pub use script::*;
+pub use manager::*;
+pub use antiroot::*;
+pub use unpinning::*;
+pub use speedhack::*;
+pub use antifrida::*;
+pub use user::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use vlitz_runtime::MockBackend;
+
+    fn antiroot_backend() -> MockBackend {
+        MockBackend::new()
+            .on("preset-antiroot.apply", |args| {
+                assert_eq!(args[0]["exec"], json!(false));
+                assert!(args[0]["paths"].as_array().unwrap().contains(&json!("/system/xbin/su")));
+                Ok(json!([
+                    { "name": "native open", "installed": true, "detail": null },
+                    { "name": "RootBeer", "installed": false, "detail": "com.scottyab.rootbeer.RootBeer not loaded" },
+                ]))
+            })
+            .on_value("preset-antiroot.revert", json!(1))
+    }
+
+    #[test]
+    fn test_apply_and_revert_antiroot() {
+        let mut manager = PresetManager::new();
+        let mut backend = antiroot_backend();
+        let overrides = vec![("exec".to_string(), "off".to_string())];
+
+        let report = manager.apply("antiroot", &mut backend, &overrides).unwrap();
+        assert_eq!(report.installed(), 1);
+        assert_eq!(report.format(), "  [+] native open\n  [-] RootBeer: com.scottyab.rootbeer.RootBeer not loaded");
+        assert_eq!(backend.loaded_scripts(), vec!["preset-antiroot"]);
+        assert!(backend.script_source("preset-antiroot").unwrap().contains("Build.TAGS"));
+        assert!(manager.is_applied("antiroot"));
+        assert!(manager.apply("antiroot", &mut backend, &[]).is_err());
+
+        manager.revert("antiroot", &mut backend).unwrap();
+        assert!(backend.loaded_scripts().is_empty());
+        assert_eq!(backend.call_count("preset-antiroot.revert"), 1);
+        assert!(manager.revert("antiroot", &mut backend).is_err());
+    }
+
+    #[test]
+    fn test_option_validation() {
+        let mut manager = PresetManager::new();
+        let mut backend = antiroot_backend();
+
+        let unknown = manager.apply("antiroot", &mut backend, &[("speed".to_string(), "2".to_string())]);
+        assert!(unknown.unwrap_err().to_string().contains("Unknown option 'speed'"));
+        let invalid = manager.apply("antiroot", &mut backend, &[("exec".to_string(), "maybe".to_string())]);
+        assert!(invalid.unwrap_err().to_string().contains("Expected bool value"));
+        assert!(manager.apply("nope", &mut backend, &[]).is_err());
+        assert!(backend.loaded_scripts().is_empty());
+
+        assert!(parse_overrides(&["exec=false", "paths=/a,/b"]).is_ok());
+        assert!(parse_overrides(&["exec"]).is_err());
+    }
+
+    #[test]
+    fn test_failed_apply_unloads_script() {
+        let mut manager = PresetManager::new();
+        let mut backend = MockBackend::new().on_value("preset-antiroot.apply", json!("bad"));
+
+        assert!(manager.apply("antiroot", &mut backend, &[]).is_err());
+        assert!(backend.loaded_scripts().is_empty());
+        assert!(!manager.is_applied("antiroot"));
+    }
+
+    #[test]
+    fn test_unpinning_reports_hits() {
+        let mut manager = PresetManager::new();
+        let mut backend = MockBackend::new()
+            .on("preset-unpinning.apply", |args| {
+                assert_eq!(args[0], json!({ "native": false, "sslcontext": true, "webview": true }));
+                Ok(json!([
+                    { "name": "TrustManagerImpl.verifyChain", "installed": true, "detail": null, "hits": 0 },
+                    { "name": "OkHttp3 CertificatePinner.check", "installed": false, "detail": "okhttp3.CertificatePinner not loaded", "hits": 0 },
+                ]))
+            })
+            .on_value("preset-unpinning.status", json!([
+                { "name": "TrustManagerImpl.verifyChain", "installed": true, "detail": null, "hits": 4 },
+                { "name": "OkHttp3 CertificatePinner.check", "installed": false, "detail": "okhttp3.CertificatePinner not loaded", "hits": 0 },
+            ]));
+
+        assert!(manager.status("unpinning", &backend).is_err());
+        manager.apply("unpinning", &mut backend, &[("native".to_string(), "false".to_string())]).unwrap();
+        assert!(backend.script_source("preset-unpinning").unwrap().contains("const PRESET = \"unpinning\";"));
+
+        let status = manager.status("unpinning", &backend).unwrap();
+        assert_eq!(status.format(), "  [+] TrustManagerImpl.verifyChain (4 hits)\n  [-] OkHttp3 CertificatePinner.check: okhttp3.CertificatePinner not loaded");
+    }
+
+    #[test]
+    fn test_speedhack_factor_changes() {
+        let mut manager = PresetManager::new();
+        let mut backend = MockBackend::new()
+            .on("preset-speedhack.apply", |args| {
+                assert_eq!(args[0]["factor"], json!(0.5));
+                Ok(json!([{ "name": "clock_gettime", "installed": true }]))
+            })
+            .on_value("preset-speedhack.update", json!(true));
+
+        assert!(manager.apply("speedhack", &mut backend, &[("factor".to_string(), "0".to_string())]).is_err());
+        assert!(backend.loaded_scripts().is_empty());
+
+        // 적용 전에 바꾼 값은 다음 적용에 쓰임
+        manager.set_option("speedhack", "factor", "0.5", None).unwrap();
+        manager.apply("speedhack", &mut backend, &[]).unwrap();
+
+        assert_eq!(manager.set_option("speedhack", "factor", "3", Some(&mut backend)).unwrap(), OptionValue::Number(3.0));
+        assert_eq!(backend.calls().last().unwrap().1, vec![json!("factor"), json!(3.0)]);
+        assert!(manager.set_option("speedhack", "factor", "-1", Some(&mut backend)).is_err());
+        assert!(manager.set_option("speedhack", "java", "false", Some(&mut backend)).is_err());
+        assert!(manager.set_option("speedhack", "factor", "2", None).is_err());
+        assert_eq!(backend.call_count("preset-speedhack.update"), 1);
+    }
+
+    #[test]
+    fn test_antifrida_countermeasures_toggle() {
+        let mut manager = PresetManager::new();
+        let mut backend = MockBackend::new()
+            .on("preset-antifrida.apply", |args| {
+                assert_eq!(args[0]["maps"], json!(true));
+                assert_eq!(args[0]["ptrace"], json!(false));
+                assert_eq!(args[0]["ports"], json!(["27042"]));
+                Ok(json!([{ "name": "openat /proc", "installed": true }, { "name": "connect", "installed": true }]))
+            });
+
+        let off = |names: &[&str]| names.iter().map(|name| (name.to_string(), "false".to_string())).collect::<Vec<_>>();
+        let all_off = manager.apply("antifrida", &mut backend, &off(&["maps", "threads", "ptrace", "port"]));
+        assert!(all_off.unwrap_err().to_string().contains("Enable at least one of maps, threads, ptrace, port"));
+        let bad_port = manager.apply("antifrida", &mut backend, &[("ports".to_string(), "27042,frida".to_string())]);
+        assert!(bad_port.unwrap_err().to_string().contains("Invalid port 'frida'"));
+        assert!(backend.loaded_scripts().is_empty());
+
+        let mut overrides = off(&["ptrace"]);
+        overrides.push(("ports".to_string(), "27042".to_string()));
+        assert_eq!(manager.apply("antifrida", &mut backend, &overrides).unwrap().installed(), 2);
+        assert!(backend.script_source("preset-antifrida").unwrap().contains("TracerPid"));
+    }
+
+    #[test]
+    fn test_load_user_presets() {
+        let dir = std::env::temp_dir().join(format!("vlitz-preset-dir-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("nolicense.js"), "const setup = options => install(\"License.check\", () => null);").unwrap();
+        std::fs::write(dir.join("nolicense.preset"), "name = nolicense\ndescription = Skip license check\nplatform = android\nscript = nolicense.js\nparam strict bool true\n").unwrap();
+        std::fs::write(dir.join("clash.preset"), "# 내장 프리셋과 이름이 겹침\nname = antiroot\nscript = nolicense.js\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let mut manager = PresetManager::new();
+        let errors = manager.load_dir(&dir);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().ends_with("clash.preset:2: Preset 'antiroot' is already registered"));
+
+        let preset = manager.get("nolicense").unwrap();
+        assert_eq!(preset.platform(), Platform::Android);
+        assert_eq!(preset.options()[0].default, OptionValue::Bool(true));
+
+        let mut backend = MockBackend::new()
+            .on("preset-nolicense.apply", |args| {
+                assert_eq!(args[0], json!({ "strict": false }));
+                Ok(json!([{ "name": "License.check", "installed": true }]))
+            });
+        manager.apply("nolicense", &mut backend, &[("strict".to_string(), "no".to_string())]).unwrap();
+        assert!(backend.script_source("preset-nolicense").unwrap().contains("install(\"License.check\""));
+
+        assert!(manager.load_dir(&dir.join("missing")).is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
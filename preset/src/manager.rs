@@ -0,0 +1,225 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use vlitz_runtime::RpcBackend;
+use vlitz_shared::{VlitzError, VlitzResult};
+use crate::antiroot::AntiRootPreset;
+use crate::unpinning::UnpinningPreset;
+use crate::speedhack::SpeedhackPreset;
+use crate::antifrida::AntiFridaPreset;
+use crate::options::{OptionValue, PresetOptions};
+use crate::preset::{Preset, PresetReport};
+use crate::user::{ManifestError, UserPreset, MANIFEST_EXTENSION};
+
+/// 등록된 프리셋과 적용 상태 관리
+pub struct PresetManager {
+    presets: Vec<Box<dyn Preset>>,
+    /// 현재 세션에 적용된 프리셋 이름
+    applied: BTreeSet<String>,
+    /// `preset set`으로 바꾼 옵션 (프리셋 이름 -> key=value 목록, 다음 적용에도 사용)
+    settings: HashMap<String, Vec<(String, String)>>,
+}
+
+impl PresetManager {
+    /// 내장 프리셋이 등록된 관리자 생성
+    pub fn new() -> Self {
+        let mut manager = Self::empty();
+        for preset in builtin_presets() {
+            // 내장 프리셋 이름은 겹치지 않음
+            let _ = manager.register(preset);
+        }
+        manager
+    }
+
+    /// 프리셋이 하나도 없는 관리자 생성
+    pub fn empty() -> Self {
+        Self { presets: Vec::new(), applied: BTreeSet::new(), settings: HashMap::new() }
+    }
+
+    /// 프리셋 등록 (이름이 겹치면 오류)
+    pub fn register(&mut self, preset: Box<dyn Preset>) -> VlitzResult<()> {
+        if self.get(preset.name()).is_some() {
+            return Err(VlitzError::Preset(format!("Preset '{}' is already registered", preset.name())));
+        }
+        self.presets.push(preset);
+        Ok(())
+    }
+
+    /// 디렉터리의 `*.preset` 매니페스트를 모두 등록 (잘못된 매니페스트는 건너뛰고 오류로 반환)
+    ///
+    /// 디렉터리가 없으면 아무것도 하지 않는다.
+    pub fn load_dir(&mut self, dir: &Path) -> Vec<ManifestError> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+            Err(e) => return vec![ManifestError { path: dir.to_path_buf(), line: None, message: format!("Failed to read preset directory: {}", e) }],
+        };
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == MANIFEST_EXTENSION))
+            .collect();
+        paths.sort();
+
+        let mut errors = Vec::new();
+        for path in paths {
+            let preset = match UserPreset::load(&path) {
+                Ok(preset) => preset,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                },
+            };
+            if self.get(preset.name()).is_some() {
+                let message = format!("Preset '{}' is already registered", preset.name());
+                errors.push(ManifestError { path, line: Some(preset.manifest().name_line), message });
+                continue;
+            }
+            self.presets.push(Box::new(preset));
+        }
+        errors
+    }
+
+    /// 이름으로 프리셋 찾기
+    pub fn get(&self, name: &str) -> Option<&dyn Preset> {
+        self.presets.iter().find(|preset| preset.name() == name).map(|preset| preset.as_ref())
+    }
+
+    /// 모든 프리셋과 적용 여부 (등록 순)
+    pub fn list(&self) -> Vec<(&dyn Preset, bool)> {
+        self.presets.iter()
+            .map(|preset| (preset.as_ref(), self.applied.contains(preset.name())))
+            .collect()
+    }
+
+    /// 적용 여부
+    pub fn is_applied(&self, name: &str) -> bool {
+        self.applied.contains(name)
+    }
+
+    /// 프리셋 적용 (`overrides`는 `key=value` 옵션)
+    pub fn apply(&mut self, name: &str, session: &mut dyn RpcBackend, overrides: &[(String, String)]) -> VlitzResult<PresetReport> {
+        if self.applied.contains(name) {
+            return Err(VlitzError::Preset(format!("Preset '{}' is already applied", name)));
+        }
+
+        let options = self.options_for(name, overrides)?;
+        let report = self.find_mut(name)?.apply(session, &options)?;
+        self.applied.insert(name.to_string());
+        Ok(report)
+    }
+
+    /// 적용할 때 쓸 옵션 값 (`preset set` 값과 `overrides` 반영)
+    pub fn options_for(&self, name: &str, overrides: &[(String, String)]) -> VlitzResult<PresetOptions> {
+        let preset = self.get(name).ok_or_else(|| VlitzError::Preset(format!("Unknown preset: {}", name)))?;
+        let settings = self.settings.get(name).cloned().unwrap_or_default();
+        PresetOptions::resolve(&preset.options(), &[settings.as_slice(), overrides].concat())
+    }
+
+    /// 적용 상태 직접 변경 (명령어 스크립트 프리셋을 실행한 스크립트 실행기가 사용)
+    pub fn set_applied(&mut self, name: &str, applied: bool) {
+        if applied {
+            self.applied.insert(name.to_string());
+        } else {
+            self.applied.remove(name);
+        }
+    }
+
+    /// 프리셋 되돌리기
+    pub fn revert(&mut self, name: &str, session: &mut dyn RpcBackend) -> VlitzResult<()> {
+        if !self.applied.contains(name) {
+            self.find_mut(name)?;
+            return Err(VlitzError::Preset(format!("Preset '{}' is not applied", name)));
+        }
+
+        // 되돌리다 실패해도 스크립트는 내려갔을 수 있으므로 적용 상태는 지움
+        self.applied.remove(name);
+        self.find_mut(name)?.revert(session)
+    }
+
+    /// 옵션 값 변경 (적용 중이면 바로 반영하고, 이후 적용할 때도 사용)
+    pub fn set_option(&mut self, name: &str, key: &str, input: &str, session: Option<&mut dyn RpcBackend>) -> VlitzResult<OptionValue> {
+        let applied = self.applied.contains(name);
+        let preset = self.find_mut(name)?;
+        let change = [(key.to_string(), input.to_string())];
+        let value = PresetOptions::resolve(&preset.options(), &change)?
+            .get(key)
+            .cloned()
+            .ok_or_else(|| VlitzError::Preset(format!("Unknown option '{}'", key)))?;
+
+        if applied {
+            let session = session.ok_or_else(|| VlitzError::Preset("Not attached to a process".to_string()))?;
+            preset.update(session, key, &value)?;
+        }
+
+        let settings = self.settings.entry(name.to_string()).or_default();
+        settings.retain(|(existing, _)| existing != key);
+        settings.push((key.to_string(), input.to_string()));
+        Ok(value)
+    }
+
+    /// 적용 중인 프리셋의 후크별 상태
+    pub fn status(&self, name: &str, session: &dyn RpcBackend) -> VlitzResult<PresetReport> {
+        let preset = self.get(name).ok_or_else(|| VlitzError::Preset(format!("Unknown preset: {}", name)))?;
+        if !self.applied.contains(name) {
+            return Err(VlitzError::Preset(format!("Preset '{}' is not applied", name)));
+        }
+        preset.status(session)
+    }
+
+    /// 적용 중인 프리셋이 추가한 명령어 실행
+    pub fn run_command(&mut self, name: &str, command: &str, args: &[String], session: &mut dyn RpcBackend) -> VlitzResult<String> {
+        if !self.applied.contains(name) {
+            self.find_mut(name)?;
+            return Err(VlitzError::Preset(format!("Preset '{}' is not applied", name)));
+        }
+        self.find_mut(name)?.run_command(session, command, args)
+    }
+
+    /// 적용된 프리셋 이름
+    pub fn applied(&self) -> Vec<String> {
+        self.applied.iter().cloned().collect()
+    }
+
+    /// 적용된 프리셋을 모두 되돌림 (되돌린 이름 반환, 첫 오류는 마지막에 반환)
+    pub fn revert_all(&mut self, session: &mut dyn RpcBackend) -> VlitzResult<Vec<String>> {
+        let names = self.applied();
+        let mut first_error = None;
+        for name in &names {
+            if let Err(e) = self.revert(name, session) {
+                first_error.get_or_insert(e);
+            }
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(names),
+        }
+    }
+
+    /// 세션이 끊겨 스크립트가 사라졌을 때 적용 상태만 초기화
+    pub fn forget_applied(&mut self) {
+        self.applied.clear();
+    }
+
+    fn find_mut(&mut self, name: &str) -> VlitzResult<&mut Box<dyn Preset>> {
+        self.presets.iter_mut()
+            .find(|preset| preset.name() == name)
+            .ok_or_else(|| VlitzError::Preset(format!("Unknown preset: {}", name)))
+    }
+}
+
+impl Default for PresetManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 내장 프리셋 목록
+pub fn builtin_presets() -> Vec<Box<dyn Preset>> {
+    vec![
+        Box::new(AntiRootPreset::new()),
+        Box::new(UnpinningPreset::new()),
+        Box::new(SpeedhackPreset::new()),
+        Box::new(AntiFridaPreset::new()),
+    ]
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use serde_json::{json, Value};
+use vlitz_shared::{VlitzError, VlitzResult};
+
+/// 프리셋 옵션 값
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    /// 쉼표로 구분해 입력하는 목록
+    List(Vec<String>),
+}
+
+impl OptionValue {
+    /// 값 종류 이름
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            OptionValue::Bool(_) => "bool",
+            OptionValue::Number(_) => "number",
+            OptionValue::Text(_) => "text",
+            OptionValue::List(_) => "list",
+        }
+    }
+
+    /// 같은 종류의 값으로 입력 해석
+    pub fn parse_like(&self, input: &str) -> Result<OptionValue, String> {
+        match self {
+            OptionValue::Bool(_) => match input {
+                "true" | "on" | "yes" | "1" => Ok(OptionValue::Bool(true)),
+                "false" | "off" | "no" | "0" => Ok(OptionValue::Bool(false)),
+                _ => Err(format!("Expected bool value, got '{}'", input)),
+            },
+            OptionValue::Number(_) => input.parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .map(OptionValue::Number)
+                .ok_or_else(|| format!("Expected number value, got '{}'", input)),
+            OptionValue::Text(_) => Ok(OptionValue::Text(input.to_string())),
+            OptionValue::List(_) => Ok(OptionValue::List(
+                input.split(',').map(str::trim).filter(|item| !item.is_empty()).map(str::to_string).collect()
+            )),
+        }
+    }
+
+    /// 에이전트 스크립트로 넘길 JSON 값
+    pub fn to_json(&self) -> Value {
+        match self {
+            OptionValue::Bool(b) => json!(b),
+            OptionValue::Number(n) => json!(n),
+            OptionValue::Text(s) => json!(s),
+            OptionValue::List(items) => json!(items),
+        }
+    }
+}
+
+impl fmt::Display for OptionValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionValue::Bool(b) => write!(f, "{}", b),
+            OptionValue::Number(n) => write!(f, "{}", n),
+            OptionValue::Text(s) => write!(f, "{}", s),
+            OptionValue::List(items) => write!(f, "{}", items.join(",")),
+        }
+    }
+}
+
+/// 프리셋이 받는 옵션 정의
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresetOption {
+    pub name: String,
+    pub description: String,
+    pub default: OptionValue,
+}
+
+impl PresetOption {
+    pub fn new(name: &str, description: &str, default: OptionValue) -> Self {
+        Self { name: name.to_string(), description: description.to_string(), default }
+    }
+}
+
+/// 적용할 때 쓰는 옵션 값 (정의된 옵션은 모두 값이 있음)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PresetOptions {
+    values: BTreeMap<String, OptionValue>,
+}
+
+impl PresetOptions {
+    /// 기본값에 `key=value` 입력을 덮어써서 옵션 값 생성
+    pub fn resolve(options: &[PresetOption], overrides: &[(String, String)]) -> VlitzResult<Self> {
+        let mut values: BTreeMap<String, OptionValue> = options.iter()
+            .map(|option| (option.name.clone(), option.default.clone()))
+            .collect();
+
+        for (key, input) in overrides {
+            let current = values.get(key).ok_or_else(|| {
+                let names: Vec<&str> = options.iter().map(|option| option.name.as_str()).collect();
+                VlitzError::Preset(format!("Unknown option '{}' (available: {})", key, names.join(", ")))
+            })?;
+            let value = current.parse_like(input)
+                .map_err(|e| VlitzError::Preset(format!("Invalid value for '{}': {}", key, e)))?;
+            values.insert(key.clone(), value);
+        }
+
+        Ok(Self { values })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&OptionValue> {
+        self.values.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &OptionValue)> {
+        self.values.iter()
+    }
+
+    /// 에이전트 스크립트로 넘길 JSON 객체
+    pub fn to_json(&self) -> Value {
+        Value::Object(self.values.iter().map(|(key, value)| (key.clone(), value.to_json())).collect())
+    }
+}
+
+/// `key=value` 형식 인자 분리
+pub fn parse_overrides<S: AsRef<str>>(args: &[S]) -> VlitzResult<Vec<(String, String)>> {
+    args.iter()
+        .map(|arg| {
+            let arg = arg.as_ref();
+            arg.split_once('=')
+                .filter(|(key, _)| !key.is_empty())
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .ok_or_else(|| VlitzError::Preset(format!("Expected option as key=value, got '{}'", arg)))
+        })
+        .collect()
+}
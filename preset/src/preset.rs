@@ -0,0 +1,172 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use serde::Deserialize;
+use vlitz_runtime::RpcBackend;
+use vlitz_shared::{VlitzError, VlitzResult};
+use crate::options::{OptionValue, PresetOption, PresetOptions};
+
+/// 자동 후킹 프리셋
+///
+/// `session`은 attach된 세션의 RPC 백엔드(`VlitzSession`)로, 프리셋 스크립트를 로드/언로드하는 데 쓴다.
+pub trait Preset {
+    /// 명령어에서 쓰는 이름 (예: `antiroot`)
+    fn name(&self) -> &str;
+
+    /// 한 줄 설명
+    fn description(&self) -> &str;
+
+    /// 대상 플랫폼
+    fn platform(&self) -> Platform {
+        Platform::Any
+    }
+
+    /// 받을 수 있는 옵션과 기본값
+    fn options(&self) -> Vec<PresetOption> {
+        Vec::new()
+    }
+
+    /// `.vzs` 명령어 스크립트로 동작하는 프리셋이면 스크립트 경로
+    ///
+    /// 이런 프리셋은 `apply`/`revert` 대신 스크립트 실행기가 옵션을 변수로 넘겨 실행한다.
+    fn commands(&self) -> Option<&PresetCommands> {
+        None
+    }
+
+    /// 프리셋 적용 (후크 설치 결과 반환)
+    fn apply(&mut self, session: &mut dyn RpcBackend, options: &PresetOptions) -> VlitzResult<PresetReport>;
+
+    /// 적용한 후크를 모두 되돌림
+    fn revert(&mut self, session: &mut dyn RpcBackend) -> VlitzResult<()>;
+
+    /// 적용 중에 옵션 변경 (기본은 변경할 수 없음)
+    fn update(&mut self, _session: &mut dyn RpcBackend, name: &str, _value: &OptionValue) -> VlitzResult<()> {
+        Err(VlitzError::Preset(format!(
+            "Option '{}' of preset '{}' cannot be changed while applied (revert and apply again)", name, self.name()
+        )))
+    }
+
+    /// 적용 중인 후크의 설치 여부와 동작 횟수
+    fn status(&self, _session: &dyn RpcBackend) -> VlitzResult<PresetReport> {
+        Err(VlitzError::Preset(format!("Preset '{}' does not report status", self.name())))
+    }
+
+    /// 적용 중에 추가되는 명령어 (적용하면 등록되고 되돌리면 제거됨)
+    fn extra_commands(&self) -> Vec<PresetCommand> {
+        Vec::new()
+    }
+
+    /// `extra_commands` 명령어 실행 (출력할 메시지 반환)
+    fn run_command(&mut self, _session: &mut dyn RpcBackend, name: &str, _args: &[String]) -> VlitzResult<String> {
+        Err(VlitzError::Preset(format!("Preset '{}' has no command '{}'", self.name(), name)))
+    }
+}
+
+/// 프리셋이 적용 중에 추가하는 명령어
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresetCommand {
+    pub name: String,
+    /// 인자 형식 (`<필수>`, `[선택]`)
+    pub args: String,
+    pub description: String,
+}
+
+impl PresetCommand {
+    pub fn new(name: &str, args: &str, description: &str) -> Self {
+        Self { name: name.to_string(), args: args.to_string(), description: description.to_string() }
+    }
+}
+
+/// 프리셋 대상 플랫폼
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Any,
+    Android,
+    Ios,
+    Linux,
+    Windows,
+    Macos,
+}
+
+impl Platform {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Platform::Any => "any",
+            Platform::Android => "android",
+            Platform::Ios => "ios",
+            Platform::Linux => "linux",
+            Platform::Windows => "windows",
+            Platform::Macos => "macos",
+        }
+    }
+}
+
+impl FromStr for Platform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "any" => Ok(Platform::Any),
+            "android" => Ok(Platform::Android),
+            "ios" => Ok(Platform::Ios),
+            "linux" => Ok(Platform::Linux),
+            "windows" => Ok(Platform::Windows),
+            "macos" => Ok(Platform::Macos),
+            _ => Err(format!("Unknown platform '{}' (expected any, android, ios, linux, windows or macos)", s)),
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// 명령어 스크립트 프리셋의 스크립트 경로
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresetCommands {
+    /// 적용할 때 실행
+    pub apply: PathBuf,
+    /// 되돌릴 때 실행 (없으면 적용 상태만 지움)
+    pub revert: Option<PathBuf>,
+}
+
+/// 프리셋 후크 하나의 설치 결과
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct HookStatus {
+    pub name: String,
+    pub installed: bool,
+    /// 설치하지 못한 이유
+    #[serde(default)]
+    pub detail: Option<String>,
+    /// 후크가 동작한 횟수
+    #[serde(default)]
+    pub hits: u64,
+}
+
+/// 프리셋 적용 결과
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PresetReport {
+    pub hooks: Vec<HookStatus>,
+}
+
+impl PresetReport {
+    /// 설치된 후크 수
+    pub fn installed(&self) -> usize {
+        self.hooks.iter().filter(|hook| hook.installed).count()
+    }
+
+    /// 후크별 결과 목록
+    pub fn format(&self) -> String {
+        self.hooks.iter()
+            .map(|hook| match (&hook.detail, hook.installed) {
+                (_, true) if hook.hits > 0 => format!("  [+] {} ({} hits)", hook.name, hook.hits),
+                (_, true) => format!("  [+] {}", hook.name),
+                (Some(detail), false) => format!("  [-] {}: {}", hook.name, detail),
+                (None, false) => format!("  [-] {}", hook.name),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
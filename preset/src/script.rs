@@ -0,0 +1,215 @@
+use serde_json::Value;
+use vlitz_runtime::RpcBackend;
+use vlitz_shared::{VlitzError, VlitzResult};
+use crate::options::{OptionValue, PresetOptions};
+use crate::preset::{HookStatus, PresetReport};
+
+/// 프리셋 스크립트 공통 부분
+///
+/// 본문은 `setup(options)` 함수를 정의하고 그 안에서 `install(name, fn)`으로 후크를 설치한다.
+/// `fn`은 되돌리는 함수를 반환하고, 대상이 없으면 `unavailable(이유)`를 던진다.
+/// 후크가 실제로 동작하면 `hit(name, detail)`로 알린다 (`presetHit` 메시지).
+/// 적용 중 옵션 변경을 받으려면 `onOptionChange(key, value)`를 정의한다.
+const PRESET_PRELUDE: &str = r#"
+(() => {
+    "use strict";
+
+    const PRESET = "/*NAME*/";
+    const hooks = [];
+    let currentOptions = null;
+
+    class Unavailable extends Error {}
+    const unavailable = reason => { throw new Unavailable(reason); };
+
+    const install = (name, setup) => {
+        try {
+            const undo = setup();
+            hooks.push({ name: name, installed: true, detail: null, hits: 0, undo: undo });
+        } catch (e) {
+            hooks.push({ name: name, installed: false, detail: e.message, hits: 0, undo: null });
+        }
+    };
+
+    const hit = (name, detail) => {
+        const hook = hooks.find(h => h.name === name);
+        if (hook === undefined) return;
+        hook.hits++;
+        send({
+            type: "presetHit",
+            preset: PRESET,
+            hook: name,
+            hits: hook.hits,
+            detail: detail === undefined || detail === null ? null : String(detail)
+        });
+    };
+
+    const statusOf = hook => ({ name: hook.name, installed: hook.installed, detail: hook.detail, hits: hook.hits });
+
+    // 네이티브 익스포트 후킹 (되돌리기 함수 반환)
+    const attachExport = (exportName, callbacks) => {
+        const address = Module.findExportByName(null, exportName);
+        if (address === null) unavailable(exportName + " not found");
+        const listener = Interceptor.attach(address, callbacks);
+        return () => listener.detach();
+    };
+
+    // 이 익스포트를 가진 모든 모듈 후킹 (앱에 포함된 BoringSSL 등)
+    const attachAllExports = (exportName, callbacks) => {
+        const listeners = Process.enumerateModules()
+            .map(module => Module.findExportByName(module.name, exportName))
+            .filter(address => address !== null)
+            .map(address => Interceptor.attach(address, callbacks));
+        if (listeners.length === 0) unavailable(exportName + " not found");
+        return () => listeners.forEach(listener => listener.detach());
+    };
+
+    // Java 메서드 구현 교체 (모든 오버로드, 되돌리기 함수 반환)
+    const replaceJava = (className, methodName, makeImpl) => {
+        if (!Java.available) unavailable("Java runtime not available");
+        let undo = null;
+        Java.performNow(() => {
+            let clazz;
+            try {
+                clazz = Java.use(className);
+            } catch (e) {
+                unavailable(className + " not loaded");
+            }
+            const method = clazz[methodName];
+            if (method === undefined) unavailable(className + "." + methodName + " not found");
+            method.overloads.forEach(overload => {
+                overload.implementation = makeImpl(overload);
+            });
+            undo = () => Java.performNow(() => {
+                method.overloads.forEach(overload => {
+                    overload.implementation = null;
+                });
+            });
+        });
+        return undo;
+    };
+
+    // 여러 메서드 중 있는 것만 교체 (하나도 없으면 설치 실패)
+    const replaceJavaMethods = (className, methodNames, makeImpl) => {
+        const undos = [];
+        let reason = className + " not loaded";
+        methodNames.forEach(methodName => {
+            try {
+                undos.push(replaceJava(className, methodName, makeImpl));
+            } catch (e) {
+                // 라이브러리 버전에 따라 없는 메서드는 건너뜀
+                if (!(e instanceof Unavailable)) throw e;
+                reason = e.message;
+            }
+        });
+        if (undos.length === 0) unavailable(reason);
+        return () => undos.forEach(undo => undo());
+    };
+
+    /*BODY*/
+
+    rpc.exports = {
+        apply(options) {
+            currentOptions = options;
+            setup(options);
+            return hooks.map(statusOf);
+        },
+        // 적용 중 옵션 변경 (본문에 onOptionChange(key, value)가 있을 때만)
+        update(key, value) {
+            if (typeof onOptionChange !== "function") {
+                throw new Error("Options of " + PRESET + " cannot be changed while applied");
+            }
+            onOptionChange(key, value);
+            currentOptions[key] = value;
+            return true;
+        },
+        status() {
+            return hooks.map(statusOf);
+        },
+        revert() {
+            let reverted = 0;
+            while (hooks.length > 0) {
+                const hook = hooks.pop();
+                if (hook.undo === null) continue;
+                try {
+                    hook.undo();
+                    reverted++;
+                } catch (e) {
+                    console.warn("Failed to revert " + hook.name + ": " + e.message);
+                }
+            }
+            return reverted;
+        }
+    };
+})();
+"#;
+
+/// 에이전트 스크립트로 동작하는 프리셋의 공통 로직
+#[derive(Debug, Clone)]
+pub struct PresetScript {
+    /// 세션에 로드할 스크립트 이름
+    script_name: String,
+    source: String,
+}
+
+impl PresetScript {
+    /// 프리셋 이름과 `setup(options)` 본문으로 스크립트 생성
+    pub fn new(preset_name: &str, body: &str) -> Self {
+        Self {
+            script_name: format!("preset-{}", preset_name),
+            source: PRESET_PRELUDE.replace("/*NAME*/", preset_name).replace("/*BODY*/", body),
+        }
+    }
+
+    pub fn script_name(&self) -> &str {
+        &self.script_name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// 스크립트 로드 후 후크 설치
+    pub fn apply(&self, session: &mut dyn RpcBackend, options: &PresetOptions) -> VlitzResult<PresetReport> {
+        session.load_script(&self.script_name, &self.source)?;
+
+        let result = session.call_script(&self.script_name, "apply", &[options.to_json()])
+            .and_then(|value| serde_json::from_value::<Vec<HookStatus>>(value)
+                .map_err(|e| VlitzError::Preset(format!("Invalid apply result: {}", e))));
+
+        match result {
+            Ok(hooks) => Ok(PresetReport { hooks }),
+            Err(e) => {
+                // 설치 도중 실패하면 반쯤 적용된 상태가 남지 않도록 스크립트를 내림
+                self.unload(session);
+                Err(e)
+            },
+        }
+    }
+
+    /// 적용 중인 스크립트에 옵션 변경 전달
+    pub fn update(&self, session: &mut dyn RpcBackend, name: &str, value: &OptionValue) -> VlitzResult<()> {
+        session.call_script(&self.script_name, "update", &[Value::String(name.to_string()), value.to_json()])
+            .map(|_| ())
+    }
+
+    /// 후크별 설치 여부와 동작 횟수
+    pub fn status(&self, session: &dyn RpcBackend) -> VlitzResult<PresetReport> {
+        let value = session.call_script(&self.script_name, "status", &[])?;
+        serde_json::from_value::<Vec<HookStatus>>(value)
+            .map(|hooks| PresetReport { hooks })
+            .map_err(|e| VlitzError::Preset(format!("Invalid status result: {}", e)))
+    }
+
+    /// 후크를 되돌리고 스크립트 언로드
+    pub fn revert(&self, session: &mut dyn RpcBackend) -> VlitzResult<()> {
+        let reverted = session.call_script(&self.script_name, "revert", &[]);
+        session.unload_script(&self.script_name)?;
+        reverted.map(|_: Value| ())
+    }
+
+    fn unload(&self, session: &mut dyn RpcBackend) {
+        if let Err(e) = session.unload_script(&self.script_name) {
+            log::warn!("Failed to unload '{}': {}", self.script_name, e);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use vlitz_runtime::RpcBackend;
+use vlitz_shared::{VlitzError, VlitzResult};
+use crate::options::{OptionValue, PresetOption, PresetOptions};
+use crate::preset::{Preset, PresetCommand, PresetReport};
+use crate::script::PresetScript;
+
+const SPEEDHACK_SCRIPT: &str = r#"
+    const NANOS = 1000000000n;
+    const FACTOR_SCALE = 1000000n;
+
+    // Linux clockid_t
+    const CLOCK_REALTIME = 0;
+    const CLOCK_MONOTONIC = 1;
+    const CLOCK_MONOTONIC_RAW = 4;
+    const CLOCK_REALTIME_COARSE = 5;
+    const CLOCK_MONOTONIC_COARSE = 6;
+    const CLOCK_BOOTTIME = 7;
+    const CLOCK_BOOTTIME_ALARM = 9;
+
+    let factor = 1.0;
+    const clocks = {};
+    const seen = new Set();
+    // Java 후크가 원래 값을 읽는 동안 네이티브 후크를 건너뛸 스레드 (중복 배속 방지)
+    const bypass = new Set();
+
+    // 시계마다 (기준 실제 시각, 기준 가상 시각)에서 배속만큼 진행
+    // 배속이 바뀌면 마지막으로 돌려준 시각에서 이어가므로 시간이 뒤로 가지 않는다.
+    const scaled = (key, real) => {
+        let clock = clocks[key];
+        if (clock === undefined) {
+            clock = clocks[key] = { baseReal: real, baseVirtual: real, factor: factor, lastReal: real, lastVirtual: real };
+        }
+        if (clock.factor !== factor) {
+            clock.baseReal = clock.lastReal;
+            clock.baseVirtual = clock.lastVirtual;
+            clock.factor = factor;
+        }
+        // 벽시계가 뒤로 조정되면 그 시점부터 다시 진행
+        if (real < clock.lastReal) {
+            clock.baseReal = real;
+            clock.baseVirtual = clock.lastVirtual;
+        }
+        let value = clock.baseVirtual + (real - clock.baseReal) * BigInt(Math.round(clock.factor * 1e6)) / FACTOR_SCALE;
+        if (value < clock.lastVirtual) value = clock.lastVirtual;
+        clock.lastReal = real;
+        clock.lastVirtual = value;
+        return value;
+    };
+
+    // 매 프레임 호출되는 함수라 처음 동작했을 때만 알림
+    const firstHit = (name, detail) => {
+        if (seen.has(name)) return;
+        seen.add(name);
+        hit(name, detail);
+    };
+
+    const readLong = p => BigInt(Process.pointerSize === 8 ? p.readS64().toString() : p.readS32());
+    const writeLong = (p, value) => {
+        if (Process.pointerSize === 8) {
+            p.writeS64(int64(value.toString()));
+        } else {
+            p.writeS32(Number(value));
+        }
+    };
+
+    const setup = options => {
+        factor = options.factor;
+
+        const scaledClocks = [];
+        if (options.monotonic) scaledClocks.push(CLOCK_MONOTONIC, CLOCK_MONOTONIC_RAW, CLOCK_MONOTONIC_COARSE);
+        if (options.boottime) scaledClocks.push(CLOCK_BOOTTIME, CLOCK_BOOTTIME_ALARM);
+        if (options.realtime) scaledClocks.push(CLOCK_REALTIME, CLOCK_REALTIME_COARSE);
+
+        // 같은 종류의 시계는 상태를 공유해 서로 어긋나지 않게 함
+        const clockKey = id => {
+            if (id === CLOCK_REALTIME || id === CLOCK_REALTIME_COARSE) return "realtime";
+            if (id === CLOCK_BOOTTIME || id === CLOCK_BOOTTIME_ALARM) return "boottime";
+            return "monotonic";
+        };
+
+        install("clock_gettime", () => attachExport("clock_gettime", {
+            onEnter(args) {
+                this.clock = args[0].toInt32();
+                this.ts = args[1];
+            },
+            onLeave(retval) {
+                if (retval.toInt32() !== 0 || this.ts.isNull() || bypass.has(this.threadId)) return;
+                if (scaledClocks.indexOf(this.clock) === -1) return;
+                const secOffset = this.ts;
+                const nsecOffset = this.ts.add(Process.pointerSize);
+                const value = scaled(clockKey(this.clock), readLong(secOffset) * NANOS + readLong(nsecOffset));
+                writeLong(secOffset, value / NANOS);
+                writeLong(nsecOffset, value % NANOS);
+                firstHit("clock_gettime", "clock " + this.clock);
+            }
+        }));
+
+        if (options.realtime) {
+            install("gettimeofday", () => attachExport("gettimeofday", {
+                onEnter(args) {
+                    this.tv = args[0];
+                },
+                onLeave(retval) {
+                    if (retval.toInt32() !== 0 || this.tv.isNull() || bypass.has(this.threadId)) return;
+                    const usecOffset = this.tv.add(Process.pointerSize);
+                    const value = scaled("realtime", readLong(this.tv) * NANOS + readLong(usecOffset) * 1000n);
+                    writeLong(this.tv, value / NANOS);
+                    writeLong(usecOffset, value % NANOS / 1000n);
+                    firstHit("gettimeofday");
+                }
+            }));
+        }
+
+        if (!options.java) return;
+
+        // Java 시계 (원래 값은 네이티브 배속 없이 읽음)
+        const javaClock = (className, methodName, key, unit) => {
+            const name = className.split(".").pop() + "." + methodName;
+            install(name, () => replaceJava(className, methodName, overload => function () {
+                const threadId = Process.getCurrentThreadId();
+                bypass.add(threadId);
+                let real;
+                try {
+                    real = overload.call(this);
+                } finally {
+                    bypass.delete(threadId);
+                }
+                firstHit(name);
+                return Number(scaled(key, BigInt(real) * unit) / unit);
+            }));
+        };
+
+        if (options.monotonic) {
+            javaClock("java.lang.System", "nanoTime", "monotonic", 1n);
+            javaClock("android.os.SystemClock", "uptimeMillis", "uptime", 1000000n);
+        }
+        if (options.boottime) {
+            javaClock("android.os.SystemClock", "elapsedRealtime", "boottime", 1000000n);
+            javaClock("android.os.SystemClock", "elapsedRealtimeNanos", "boottime", 1n);
+        }
+        if (options.realtime) {
+            javaClock("java.lang.System", "currentTimeMillis", "realtime", 1000000n);
+        }
+    };
+
+    const onOptionChange = (key, value) => {
+        if (key !== "factor") {
+            throw new Error("Only factor can be changed while speedhack is applied");
+        }
+        factor = value;
+    };
+"#;
+
+/// 시간 함수 배속 프리셋
+///
+/// `clock_gettime`(MONOTONIC/BOOTTIME/REALTIME), `gettimeofday`, Java `System.nanoTime`/`SystemClock`가
+/// 돌려주는 경과 시간을 `factor`배로 늘린다. 적용 중에도 `preset set speedhack factor 2.0`이나
+/// 적용하면 추가되는 `speed 2.0` 명령어로 바꿀 수 있다.
+pub struct SpeedhackPreset {
+    script: PresetScript,
+    /// 적용 중인 배속
+    factor: f64,
+}
+
+impl SpeedhackPreset {
+    pub fn new() -> Self {
+        Self { script: PresetScript::new("speedhack", SPEEDHACK_SCRIPT), factor: 1.0 }
+    }
+}
+
+impl Default for SpeedhackPreset {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 배속 값 검사 (0보다 커야 함)
+fn validate_factor(value: Option<&OptionValue>) -> VlitzResult<()> {
+    match value {
+        Some(OptionValue::Number(factor)) if *factor > 0.0 => Ok(()),
+        Some(other) => Err(VlitzError::Preset(format!("Speed factor must be greater than 0, got {}", other))),
+        None => Err(VlitzError::Preset("Speed factor is required".to_string())),
+    }
+}
+
+impl Preset for SpeedhackPreset {
+    fn name(&self) -> &str {
+        "speedhack"
+    }
+
+    fn description(&self) -> &str {
+        "Scale elapsed time (clock_gettime, gettimeofday, System.nanoTime, SystemClock)"
+    }
+
+    fn options(&self) -> Vec<PresetOption> {
+        vec![
+            PresetOption::new("factor", "Speed multiplier (changeable while applied)", OptionValue::Number(2.0)),
+            PresetOption::new("monotonic", "Scale CLOCK_MONOTONIC, System.nanoTime and uptimeMillis", OptionValue::Bool(true)),
+            PresetOption::new("boottime", "Scale CLOCK_BOOTTIME and SystemClock.elapsedRealtime", OptionValue::Bool(true)),
+            PresetOption::new("realtime", "Scale wall-clock time (CLOCK_REALTIME, gettimeofday, currentTimeMillis)", OptionValue::Bool(true)),
+            PresetOption::new("java", "Hook Java clocks", OptionValue::Bool(true)),
+        ]
+    }
+
+    fn apply(&mut self, session: &mut dyn RpcBackend, options: &PresetOptions) -> VlitzResult<PresetReport> {
+        validate_factor(options.get("factor"))?;
+        let report = self.script.apply(session, options)?;
+        if let Some(OptionValue::Number(factor)) = options.get("factor") {
+            self.factor = *factor;
+        }
+        Ok(report)
+    }
+
+    fn revert(&mut self, session: &mut dyn RpcBackend) -> VlitzResult<()> {
+        self.script.revert(session)
+    }
+
+    fn update(&mut self, session: &mut dyn RpcBackend, name: &str, value: &OptionValue) -> VlitzResult<()> {
+        if name != "factor" {
+            return Err(VlitzError::Preset(format!(
+                "Option '{}' of preset 'speedhack' cannot be changed while applied (revert and apply again)", name
+            )));
+        }
+        validate_factor(Some(value))?;
+        self.script.update(session, name, value)?;
+        if let OptionValue::Number(factor) = value {
+            self.factor = *factor;
+        }
+        Ok(())
+    }
+
+    fn status(&self, session: &dyn RpcBackend) -> VlitzResult<PresetReport> {
+        self.script.status(session)
+    }
+
+    fn extra_commands(&self) -> Vec<PresetCommand> {
+        vec![PresetCommand::new("speed", "[factor]", "Show or change the speed factor of the applied speedhack")]
+    }
+
+    /// `speed [factor]`: 적용 중인 배속만 바꿈 (다음 적용에는 `preset set` 값을 사용)
+    fn run_command(&mut self, session: &mut dyn RpcBackend, name: &str, args: &[String]) -> VlitzResult<String> {
+        if name != "speed" {
+            return Err(VlitzError::Preset(format!("Preset 'speedhack' has no command '{}'", name)));
+        }
+        let Some(input) = args.first() else {
+            return Ok(format!("Speed factor: {}", self.factor));
+        };
+        let factor = input.parse::<f64>()
+            .map_err(|_| VlitzError::Preset(format!("Expected number value, got '{}'", input)))?;
+        self.update(session, "factor", &OptionValue::Number(factor))?;
+        Ok(format!("Speed factor set to {}", factor))
+    }
+}
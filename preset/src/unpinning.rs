@@ -0,0 +1,154 @@
+use vlitz_runtime::RpcBackend;
+use vlitz_shared::VlitzResult;
+use crate::options::{OptionValue, PresetOption, PresetOptions};
+use crate::preset::{Platform, Preset, PresetReport};
+use crate::script::PresetScript;
+
+const UNPINNING_SCRIPT: &str = r#"
+    // 교체 구현 종류: 검사를 건너뛰거나(void), 통과(true), 받은 체인을 그대로 신뢰(chain)
+    const JAVA_PINNERS = [
+        { name: "TrustManagerImpl.verifyChain", className: "com.android.org.conscrypt.TrustManagerImpl", methods: ["verifyChain"], result: "chain" },
+        { name: "TrustManagerImpl.checkTrustedRecursive", className: "com.android.org.conscrypt.TrustManagerImpl", methods: ["checkTrustedRecursive"], result: "emptyList" },
+        { name: "Conscrypt TrustManagerImpl.verifyChain", className: "org.conscrypt.TrustManagerImpl", methods: ["verifyChain"], result: "chain" },
+        { name: "Conscrypt CertPinManager", className: "com.android.org.conscrypt.CertPinManager", methods: ["isChainValid"], result: "true" },
+        { name: "Conscrypt CertPinManager.checkChainPinning", className: "com.android.org.conscrypt.CertPinManager", methods: ["checkChainPinning"], result: "void" },
+        { name: "Conscrypt OpenSSLSocketImpl.verifyCertificateChain", className: "com.android.org.conscrypt.OpenSSLSocketImpl", methods: ["verifyCertificateChain"], result: "void" },
+        { name: "OkHttp3 CertificatePinner.check", className: "okhttp3.CertificatePinner", methods: ["check", "check$okhttp"], result: "void" },
+        { name: "OkHttp CertificatePinner.check", className: "com.squareup.okhttp.CertificatePinner", methods: ["check"], result: "void" },
+        { name: "TrustKit PinningTrustManager", className: "com.datatheorem.android.trustkit.pinning.PinningTrustManager", methods: ["checkServerTrusted"], result: "void" },
+        { name: "Appcelerator PinningTrustManager", className: "appcelerator.https.PinningTrustManager", methods: ["checkServerTrusted"], result: "void" },
+    ];
+
+    // BoringSSL 검증 결과 값
+    const SSL_VERIFY_OK = 0;
+    const X509_V_OK = 0;
+
+    // 네이티브 검증 콜백 대체 (GC되지 않도록 스크립트 수명 동안 유지)
+    const acceptAll = new NativeCallback((ssl, outAlert) => SSL_VERIFY_OK, "int", ["pointer", "pointer"]);
+
+    const setup = options => {
+        JAVA_PINNERS.forEach(pinner => {
+            install(pinner.name, () => replaceJavaMethods(pinner.className, pinner.methods, overload => function (...args) {
+                hit(pinner.name, args.length > 0 && typeof args[args.length - 1] === "string" ? args[args.length - 1] : null);
+                switch (pinner.result) {
+                    case "chain":
+                        return args[0];
+                    case "emptyList":
+                        return Java.use("java.util.ArrayList").$new();
+                    case "true":
+                        return true;
+                    default:
+                        return;
+                }
+            }));
+        });
+
+        if (options.sslcontext) {
+            install("SSLContext.init", () => {
+                if (!Java.available) unavailable("Java runtime not available");
+                let undo = null;
+                Java.performNow(() => {
+                    const X509TrustManager = Java.use("javax.net.ssl.X509TrustManager");
+                    const TrustAll = Java.registerClass({
+                        name: "vlitz.preset.TrustAllManager",
+                        implements: [X509TrustManager],
+                        methods: {
+                            checkClientTrusted(chain, authType) {},
+                            checkServerTrusted(chain, authType) {},
+                            getAcceptedIssuers() {
+                                return [];
+                            }
+                        }
+                    });
+                    const managers = Java.array("javax.net.ssl.TrustManager", [TrustAll.$new()]);
+                    undo = replaceJava("javax.net.ssl.SSLContext", "init", overload => function (keyManagers, trustManagers, random) {
+                        hit("SSLContext.init");
+                        return overload.call(this, keyManagers, managers, random);
+                    });
+                });
+                return undo;
+            });
+        }
+
+        if (options.webview) {
+            install("WebViewClient.onReceivedSslError", () => replaceJava("android.webkit.WebViewClient", "onReceivedSslError", () => function (view, handler, error) {
+                hit("WebViewClient.onReceivedSslError", error !== null ? error.getUrl() : null);
+                handler.proceed();
+            }));
+        }
+
+        if (!options.native) return;
+
+        ["SSL_CTX_set_custom_verify", "SSL_set_custom_verify"].forEach(name => {
+            install(name, () => attachAllExports(name, {
+                onEnter(args) {
+                    hit(name);
+                    args[2] = acceptAll;
+                }
+            }));
+        });
+
+        install("SSL_get_verify_result", () => attachAllExports("SSL_get_verify_result", {
+            onLeave(retval) {
+                if (retval.toInt32() !== X509_V_OK) {
+                    hit("SSL_get_verify_result", retval.toInt32());
+                    retval.replace(ptr(X509_V_OK));
+                }
+            }
+        }));
+    };
+"#;
+
+/// SSL 인증서 피닝 우회 프리셋
+///
+/// Android 시스템 TrustManager/Conscrypt, OkHttp, TrustKit, `SSLContext.init`, WebView와
+/// 네이티브 BoringSSL 검증을 통과시킨다. 후크별 동작 기록으로 앱이 쓰는 피닝 구현을 알 수 있다.
+pub struct UnpinningPreset {
+    script: PresetScript,
+}
+
+impl UnpinningPreset {
+    pub fn new() -> Self {
+        Self { script: PresetScript::new("unpinning", UNPINNING_SCRIPT) }
+    }
+}
+
+impl Default for UnpinningPreset {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Preset for UnpinningPreset {
+    fn name(&self) -> &str {
+        "unpinning"
+    }
+
+    fn description(&self) -> &str {
+        "Bypass SSL pinning (TrustManagerImpl, Conscrypt, OkHttp, WebView, BoringSSL)"
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::Android
+    }
+
+    fn options(&self) -> Vec<PresetOption> {
+        vec![
+            PresetOption::new("sslcontext", "Install a trust-all manager through SSLContext.init", OptionValue::Bool(true)),
+            PresetOption::new("webview", "Proceed on WebView SSL errors", OptionValue::Bool(true)),
+            PresetOption::new("native", "Hook BoringSSL verification in every loaded module", OptionValue::Bool(true)),
+        ]
+    }
+
+    fn apply(&mut self, session: &mut dyn RpcBackend, options: &PresetOptions) -> VlitzResult<PresetReport> {
+        self.script.apply(session, options)
+    }
+
+    fn revert(&mut self, session: &mut dyn RpcBackend) -> VlitzResult<()> {
+        self.script.revert(session)
+    }
+
+    fn status(&self, session: &dyn RpcBackend) -> VlitzResult<PresetReport> {
+        self.script.status(session)
+    }
+}
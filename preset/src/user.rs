@@ -0,0 +1,365 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use vlitz_runtime::RpcBackend;
+use vlitz_shared::{config_dir, VlitzError, VlitzResult};
+use crate::options::{OptionValue, PresetOption, PresetOptions};
+use crate::preset::{Platform, Preset, PresetCommands, PresetReport};
+use crate::script::PresetScript;
+
+/// 사용자 프리셋 매니페스트 확장자
+pub const MANIFEST_EXTENSION: &str = "preset";
+
+/// 사용자 프리셋 디렉터리 (`~/.config/vlitz/presets`)
+pub fn user_preset_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("presets"))
+}
+
+/// 매니페스트 오류 (파일과 줄 번호 포함)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestError {
+    pub path: PathBuf,
+    /// 1부터 시작하는 줄 번호 (빠진 키처럼 가리킬 줄이 없으면 None)
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ManifestError {
+    fn new(path: &Path, line: Option<usize>, message: String) -> Self {
+        Self { path: path.to_path_buf(), line, message }
+    }
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+impl From<ManifestError> for VlitzError {
+    fn from(error: ManifestError) -> Self {
+        VlitzError::Preset(error.to_string())
+    }
+}
+
+/// 프리셋 동작 스크립트
+#[derive(Debug, Clone, PartialEq)]
+pub enum PresetSource {
+    /// `setup(options)`를 정의하는 Frida JS 파일 (내장 프리셋과 같은 도우미 사용)
+    Frida(PathBuf),
+    /// `.vzs` 명령어 스크립트 (옵션은 같은 이름의 변수로 전달)
+    Commands(PresetCommands),
+}
+
+/// 사용자 프리셋 매니페스트
+///
+/// 한 줄에 하나씩 `key = value` 또는 `param <이름> <bool|number|text|list> <기본값> [설명]`을 쓴다.
+/// `#`으로 시작하는 줄은 주석이고, 스크립트 경로는 매니페스트 파일 위치 기준이다.
+///
+/// ```text
+/// name = nolicense
+/// description = Skip license verification
+/// platform = android
+/// script = nolicense.js
+/// param strict bool false Also skip signature checks
+/// param message text "Licensed to vlitz"
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresetManifest {
+    pub path: PathBuf,
+    pub name: String,
+    pub description: String,
+    pub platform: Platform,
+    pub params: Vec<PresetOption>,
+    pub source: PresetSource,
+    /// 이름이 정의된 줄 (등록 오류 위치용)
+    pub name_line: usize,
+}
+
+impl PresetManifest {
+    /// 매니페스트 파일 읽기
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ManifestError::new(path, None, format!("Failed to read manifest: {}", e)))?;
+        Self::parse(path, &content)
+    }
+
+    /// 매니페스트 내용 해석 (스크립트 파일이 있는지도 확인)
+    pub fn parse(path: &Path, content: &str) -> Result<Self, ManifestError> {
+        let error = |line: usize, message: String| ManifestError::new(path, Some(line), message);
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+
+        let mut keys: HashMap<&str, (usize, &str)> = HashMap::new();
+        let mut params: Vec<(usize, PresetOption)> = Vec::new();
+
+        for (idx, raw) in content.lines().enumerate() {
+            let number = idx + 1;
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix("param ").or_else(|| line.strip_prefix("param\t")) {
+                let param = parse_param(rest).map_err(|message| error(number, message))?;
+                if let Some((first, _)) = params.iter().find(|(_, existing)| existing.name == param.name) {
+                    return Err(error(number, format!("Duplicate parameter '{}' (first defined on line {})", param.name, first)));
+                }
+                params.push((number, param));
+                continue;
+            }
+
+            let (key, value) = line.split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .ok_or_else(|| error(number, "Expected 'key = value' or 'param <name> <type> <default> [description]'".to_string()))?;
+            if !matches!(key, "name" | "description" | "platform" | "script" | "revert") {
+                return Err(error(number, format!("Unknown key '{}' (expected name, description, platform, script, revert or param)", key)));
+            }
+            if let Some((first, _)) = keys.get(key) {
+                return Err(error(number, format!("Duplicate key '{}' (first set on line {})", key, first)));
+            }
+            keys.insert(key, (number, value));
+        }
+
+        let required = |key: &str| keys.get(key)
+            .copied()
+            .ok_or_else(|| ManifestError::new(path, None, format!("Missing required key '{}'", key)));
+
+        let (name_line, name) = required("name")?;
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(error(name_line, format!("Invalid preset name '{}' (use letters, digits, '-' and '_')", name)));
+        }
+
+        let platform = match keys.get("platform") {
+            Some((number, value)) => value.parse().map_err(|e| error(*number, e))?,
+            None => Platform::Any,
+        };
+
+        let (script_line, script) = required("script")?;
+        let script_path = existing_file(base, script).map_err(|message| error(script_line, message))?;
+        let source = match script_path.extension().and_then(|ext| ext.to_str()) {
+            Some("js") => {
+                if let Some((number, _)) = keys.get("revert") {
+                    return Err(error(*number, "'revert' is only used with .vzs scripts (Frida presets revert their hooks)".to_string()));
+                }
+                PresetSource::Frida(script_path)
+            },
+            Some("vzs") => {
+                let revert = match keys.get("revert") {
+                    Some((number, value)) => Some(existing_file(base, value).map_err(|message| error(*number, message))?),
+                    None => None,
+                };
+                PresetSource::Commands(PresetCommands { apply: script_path, revert })
+            },
+            _ => return Err(error(script_line, format!("Script must be a .js or .vzs file, got '{}'", script))),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            name: name.to_string(),
+            description: keys.get("description").map(|(_, value)| value.to_string()).unwrap_or_default(),
+            platform,
+            params: params.into_iter().map(|(_, param)| param).collect(),
+            source,
+            name_line,
+        })
+    }
+}
+
+/// `<이름> <종류> <기본값> [설명]` 해석
+fn parse_param(rest: &str) -> Result<PresetOption, String> {
+    let usage = "Expected 'param <name> <bool|number|text|list> <default> [description]'";
+    let (name, rest) = next_token(rest).ok_or_else(|| usage.to_string())?;
+    let (kind, rest) = next_token(rest).ok_or_else(|| usage.to_string())?;
+    let (default, rest) = next_token(rest).ok_or_else(|| usage.to_string())?;
+
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') || name.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(format!("Invalid parameter name '{}' (use letters, digits and '_')", name));
+    }
+    let template = match kind.as_str() {
+        "bool" => OptionValue::Bool(false),
+        "number" => OptionValue::Number(0.0),
+        "text" => OptionValue::Text(String::new()),
+        "list" => OptionValue::List(Vec::new()),
+        _ => return Err(format!("Unknown parameter type '{}' (expected bool, number, text or list)", kind)),
+    };
+    let default = template.parse_like(&default)
+        .map_err(|e| format!("Invalid default for '{}': {}", name, e))?;
+
+    Ok(PresetOption { name, description: rest.trim().to_string(), default })
+}
+
+/// 공백으로 구분된 토큰 하나 (큰따옴표로 묶으면 공백 포함)
+fn next_token(input: &str) -> Option<(String, &str)> {
+    let input = input.trim_start();
+    if input.is_empty() {
+        return None;
+    }
+    if let Some(quoted) = input.strip_prefix('"') {
+        let end = quoted.find('"')?;
+        return Some((quoted[..end].to_string(), &quoted[end + 1..]));
+    }
+    let end = input.find(char::is_whitespace).unwrap_or(input.len());
+    Some((input[..end].to_string(), &input[end..]))
+}
+
+/// 매니페스트 기준 상대 경로를 풀고 파일이 있는지 확인
+fn existing_file(base: &Path, value: &str) -> Result<PathBuf, String> {
+    if value.is_empty() {
+        return Err("Script path is empty".to_string());
+    }
+    let path = base.join(value);
+    if !path.is_file() {
+        return Err(format!("Script not found: {}", path.display()));
+    }
+    Ok(path)
+}
+
+/// 매니페스트로 정의한 사용자 프리셋
+pub struct UserPreset {
+    manifest: PresetManifest,
+    /// Frida 프리셋의 에이전트 스크립트 (명령어 스크립트 프리셋은 None)
+    script: Option<PresetScript>,
+}
+
+impl UserPreset {
+    /// 매니페스트와 Frida 스크립트 읽기
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        Self::from_manifest(PresetManifest::load(path)?)
+    }
+
+    pub fn from_manifest(manifest: PresetManifest) -> Result<Self, ManifestError> {
+        let script = match &manifest.source {
+            PresetSource::Frida(path) => {
+                let body = fs::read_to_string(path).map_err(|e| {
+                    ManifestError::new(&manifest.path, None, format!("Failed to read {}: {}", path.display(), e))
+                })?;
+                Some(PresetScript::new(&manifest.name, &body))
+            },
+            PresetSource::Commands(_) => None,
+        };
+        Ok(Self { manifest, script })
+    }
+
+    pub fn manifest(&self) -> &PresetManifest {
+        &self.manifest
+    }
+}
+
+impl Preset for UserPreset {
+    fn name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    fn description(&self) -> &str {
+        &self.manifest.description
+    }
+
+    fn platform(&self) -> Platform {
+        self.manifest.platform
+    }
+
+    fn options(&self) -> Vec<PresetOption> {
+        self.manifest.params.clone()
+    }
+
+    fn commands(&self) -> Option<&PresetCommands> {
+        match &self.manifest.source {
+            PresetSource::Commands(commands) => Some(commands),
+            PresetSource::Frida(_) => None,
+        }
+    }
+
+    fn apply(&mut self, session: &mut dyn RpcBackend, options: &PresetOptions) -> VlitzResult<PresetReport> {
+        match (&self.script, &self.manifest.source) {
+            (Some(script), _) => script.apply(session, options),
+            (None, PresetSource::Commands(commands)) => Err(VlitzError::Preset(format!(
+                "Preset '{}' runs {} and must be applied by the script runner", self.manifest.name, commands.apply.display()
+            ))),
+            (None, PresetSource::Frida(path)) => Err(VlitzError::Preset(format!("Script {} is not loaded", path.display()))),
+        }
+    }
+
+    fn revert(&mut self, session: &mut dyn RpcBackend) -> VlitzResult<()> {
+        // 명령어 스크립트 프리셋은 에이전트에 남긴 상태가 없음 (revert 스크립트는 스크립트 실행기가 실행)
+        match &self.script {
+            Some(script) => script.revert(session),
+            None => Ok(()),
+        }
+    }
+
+    fn update(&mut self, session: &mut dyn RpcBackend, name: &str, value: &OptionValue) -> VlitzResult<()> {
+        match &self.script {
+            Some(script) => script.update(session, name, value),
+            None => Err(VlitzError::Preset(format!(
+                "Option '{}' of preset '{}' cannot be changed while applied (revert and apply again)", name, self.manifest.name
+            ))),
+        }
+    }
+
+    fn status(&self, session: &dyn RpcBackend) -> VlitzResult<PresetReport> {
+        match &self.script {
+            Some(script) => script.status(session),
+            None => Err(VlitzError::Preset(format!("Preset '{}' does not report status", self.manifest.name))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("vlitz-preset-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let dir = temp_dir("parse");
+        fs::write(dir.join("hide.vzs"), "hook $target\n").unwrap();
+        fs::write(dir.join("unhide.vzs"), "unhook all\n").unwrap();
+        let content = "# 주석\nname = hide\ndescription = Hide things\nplatform = linux\nscript = hide.vzs\nrevert = unhide.vzs\n\
+                       param target text \"open at\" Function to hook\nparam count number 3\nparam paths list /a,/b\n";
+
+        let manifest = PresetManifest::parse(&dir.join("hide.preset"), content).unwrap();
+        assert_eq!(manifest.name, "hide");
+        assert_eq!(manifest.platform, Platform::Linux);
+        assert_eq!(manifest.name_line, 2);
+        assert_eq!(manifest.params[0], PresetOption::new("target", "Function to hook", OptionValue::Text("open at".to_string())));
+        assert_eq!(manifest.params[1].default, OptionValue::Number(3.0));
+        assert_eq!(manifest.params[2].default, OptionValue::List(vec!["/a".to_string(), "/b".to_string()]));
+        assert_eq!(manifest.source, PresetSource::Commands(PresetCommands {
+            apply: dir.join("hide.vzs"),
+            revert: Some(dir.join("unhide.vzs")),
+        }));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_manifest_errors_point_at_line() {
+        let dir = temp_dir("errors");
+        fs::write(dir.join("ok.js"), "const setup = options => {};").unwrap();
+        let path = dir.join("bad.preset");
+        let line_of = |content: &str| PresetManifest::parse(&path, content).unwrap_err();
+
+        let error = line_of("name = bad\nscript = ok.js\nparam strict bool maybe\n");
+        assert_eq!(error.line, Some(3));
+        assert!(error.to_string().ends_with("bad.preset:3: Invalid default for 'strict': Expected bool value, got 'maybe'"));
+
+        assert_eq!(line_of("name = bad\nscript = missing.js\n").line, Some(2));
+        assert_eq!(line_of("name = bad\nname = again\nscript = ok.js\n").line, Some(2));
+        assert_eq!(line_of("name = bad\nscript = ok.js\nplatform = beos\n").line, Some(3));
+        assert_eq!(line_of("name = bad\nscript = ok.js\nrevert = ok.js\n").line, Some(3));
+        assert_eq!(line_of("name = bad\n\nscript = ok.js\nparam x float 1\n").line, Some(4));
+        assert_eq!(line_of("name = bad\nscript ok.js\n").line, Some(2));
+        assert_eq!(line_of("name = bad one\nscript = ok.js\n").line, Some(1));
+        assert_eq!(line_of("name = bad\n").line, None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,633 @@
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use vlitz_shared::{MemoryType, MemoryValue, VlitzError, VlitzResult, VzData};
+use crate::backend::RpcBackend;
+use crate::cancel::CancelToken;
+use crate::java::JavaMethodInfo;
+use crate::lock::{LockInfo, LockOptions};
+use crate::objc::{ObjcMethodInfo, ObjcValue};
+use crate::process::{ExportInfo, ImportInfo, ModuleInfo, RangeInfo, SymbolInfo};
+use crate::coverage::{CoverageBatch, StalkHandle};
+use crate::trace::{TraceBatch, TraceHandle};
+use crate::watch::{WatchEvent, WatchHandle};
+
+/// 쿼리 결과를 한 번에 읽어오는 항목 수
+pub const QUERY_CHUNK_SIZE: usize = 500;
+
+/// 대상 프로세스의 클래스 런타임 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClassRuntime {
+    Java,
+    ObjC,
+}
+
+/// 이름 검색 패턴 (`/.../` 형식이면 정규식)
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamePattern {
+    pub pattern: String,
+    pub regex: bool,
+}
+
+impl NamePattern {
+    /// 명령어 인자에서 패턴 생성
+    pub fn parse(input: &str) -> Self {
+        match input.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+            Some(re) if !re.is_empty() => Self { pattern: re.to_string(), regex: true },
+            _ => Self { pattern: input.to_string(), regex: false },
+        }
+    }
+
+    /// RPC 인자로 변환 (pattern, isRegex)
+    fn to_args(pattern: Option<&NamePattern>) -> [Value; 2] {
+        match pattern {
+            Some(p) => [json!(p.pattern), json!(p.regex)],
+            None => [Value::Null, json!(false)],
+        }
+    }
+}
+
+/// 에이전트 쿼리 커서 핸들
+#[derive(Debug, Deserialize)]
+struct QueryHandle {
+    id: u64,
+    total: usize,
+    #[serde(default)]
+    runtime: Option<ClassRuntime>,
+}
+
+/// 클래스 계층 정보
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ClassHierarchy {
+    /// 상위 클래스 체인 (가까운 순)
+    pub superclasses: Vec<String>,
+    /// 구현한 인터페이스 (ObjC는 프로토콜)
+    pub interfaces: Vec<String>,
+}
+
+/// 에이전트 RPC를 타입이 있는 Rust API로 감싸는 래퍼
+pub struct Agent<'a> {
+    backend: &'a dyn RpcBackend,
+    /// 청크 단위 쿼리를 중간에 멈추는 취소 요청
+    cancel: CancelToken,
+}
+
+impl<'a> Agent<'a> {
+    /// 새 에이전트 래퍼 생성
+    pub fn new(backend: &'a dyn RpcBackend) -> Self {
+        Self { backend, cancel: CancelToken::new() }
+    }
+
+    /// 취소 요청을 확인하는 에이전트 래퍼
+    pub fn with_cancel(mut self, cancel: &CancelToken) -> Self {
+        self.cancel = cancel.clone();
+        self
+    }
+
+    /// RPC 호출 후 응답 역직렬화
+    fn call<T: DeserializeOwned>(&self, export_name: &str, args: &[Value]) -> VlitzResult<T> {
+        let value = self.backend.call(export_name, args)?;
+        serde_json::from_value(value).map_err(|e| {
+            VlitzError::ScriptExec(format!("Invalid response from RPC '{}': {}", export_name, e))
+        })
+    }
+
+    /// 쿼리 결과를 청크 단위로 모두 읽고 커서 닫기 (취소되면 청크 사이에서 멈춤)
+    fn collect_query<T: DeserializeOwned>(&self, handle: &QueryHandle) -> VlitzResult<Vec<T>> {
+        let mut items: Vec<T> = Vec::with_capacity(handle.total);
+        let mut result = Ok(());
+
+        while items.len() < handle.total {
+            if let Err(e) = self.cancel.check() {
+                result = Err(e);
+                break;
+            }
+            let args = [json!(handle.id), json!(items.len()), json!(QUERY_CHUNK_SIZE)];
+            match self.call::<Vec<T>>("readQuery", &args) {
+                Ok(chunk) if chunk.is_empty() => break,
+                Ok(chunk) => items.extend(chunk),
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                },
+            }
+        }
+
+        self.backend.call("closeQuery", &[json!(handle.id)])?;
+        result.map(|_| items)
+    }
+
+    /// 패턴에 맞는 클래스 검색 (매칭은 에이전트에서 수행)
+    pub fn query_classes(&self, pattern: Option<&NamePattern>) -> VlitzResult<Vec<VzData>> {
+        let handle: QueryHandle = self.call("queryClasses", &NamePattern::to_args(pattern))?;
+        let names: Vec<String> = self.collect_query(&handle)?;
+        Ok(names.into_iter().map(VzData::new_class).collect())
+    }
+
+    /// 클래스의 메서드 검색 (inherited면 상위 클래스 메서드 포함)
+    pub fn query_methods(
+        &self,
+        class_name: &str,
+        inherited: bool,
+        pattern: Option<&NamePattern>,
+    ) -> VlitzResult<Vec<VzData>> {
+        let [pattern, is_regex] = NamePattern::to_args(pattern);
+        let handle: QueryHandle = self.call(
+            "queryMethods",
+            &[json!(class_name), json!(inherited), pattern, is_regex],
+        )?;
+
+        match handle.runtime {
+            Some(ClassRuntime::Java) => {
+                let methods: Vec<JavaMethodInfo> = self.collect_query(&handle)?;
+                Ok(methods.iter().map(JavaMethodInfo::to_vzdata).collect())
+            },
+            Some(ClassRuntime::ObjC) => {
+                let methods: Vec<ObjcMethodInfo> = self.collect_query(&handle)?;
+                methods.iter().map(|m| m.to_vzdata(class_name)).collect()
+            },
+            None => {
+                self.collect_query::<Value>(&handle)?;
+                Err(VlitzError::Frida("No Java or ObjC runtime available in target".to_string()))
+            },
+        }
+    }
+
+    /// 모듈 검색
+    pub fn query_modules(&self, pattern: Option<&NamePattern>) -> VlitzResult<Vec<VzData>> {
+        Ok(self.module_infos(pattern)?.iter().map(ModuleInfo::to_vzdata).collect())
+    }
+
+    /// 모듈 원시 정보 검색 (경로 포함)
+    pub fn module_infos(&self, pattern: Option<&NamePattern>) -> VlitzResult<Vec<ModuleInfo>> {
+        let handle: QueryHandle = self.call("queryModules", &NamePattern::to_args(pattern))?;
+        self.collect_query(&handle)
+    }
+
+    /// 모듈 익스포트 검색 (VzFunction/VzVariable)
+    pub fn query_exports(&self, module_name: &str, pattern: Option<&NamePattern>) -> VlitzResult<Vec<VzData>> {
+        let exports: Vec<ExportInfo> = self.query_module_items("queryExports", module_name, pattern)?;
+        Ok(exports.iter().map(ExportInfo::to_vzdata).collect())
+    }
+
+    /// 모듈 임포트 검색 (주소가 해석된 항목만)
+    pub fn query_imports(&self, module_name: &str, pattern: Option<&NamePattern>) -> VlitzResult<Vec<VzData>> {
+        let imports: Vec<ImportInfo> = self.query_module_items("queryImports", module_name, pattern)?;
+        Ok(imports.iter().filter_map(ImportInfo::to_vzdata).collect())
+    }
+
+    /// 모듈 심볼 검색 (함수/데이터 심볼만)
+    pub fn query_symbols(&self, module_name: &str, pattern: Option<&NamePattern>) -> VlitzResult<Vec<VzData>> {
+        let symbols: Vec<SymbolInfo> = self.query_module_items("querySymbols", module_name, pattern)?;
+        Ok(symbols.iter().filter_map(SymbolInfo::to_vzdata).collect())
+    }
+
+    /// 보호 속성에 맞는 메모리 범위 검색
+    pub fn query_ranges(&self, protection: &str) -> VlitzResult<Vec<VzData>> {
+        let handle: QueryHandle = self.call("queryRanges", &[json!(protection)])?;
+        let ranges: Vec<RangeInfo> = self.collect_query(&handle)?;
+        Ok(ranges.iter().map(RangeInfo::to_vzdata).collect())
+    }
+
+    /// 모듈 단위 쿼리 공통 처리
+    fn query_module_items<T: DeserializeOwned>(
+        &self,
+        export_name: &str,
+        module_name: &str,
+        pattern: Option<&NamePattern>,
+    ) -> VlitzResult<Vec<T>> {
+        let [pattern, is_regex] = NamePattern::to_args(pattern);
+        let handle: QueryHandle = self.call(export_name, &[json!(module_name), pattern, is_regex])?;
+        self.collect_query(&handle)
+    }
+
+    /// 메모리 값 읽기 (문자열은 `size` 바이트까지, 0이면 NUL까지)
+    pub fn read_value(&self, address: u64, memory_type: MemoryType, size: usize) -> VlitzResult<MemoryValue> {
+        let value: Value = self.call("readMemory", &[json!(format!("0x{:x}", address)), json!(size), json!(memory_type.agent_name())])?;
+        let invalid = || VlitzError::ScriptExec(format!("Invalid {} value from readMemory: {}", memory_type.agent_name(), value));
+
+        // long/ulong/pointer는 문자열, bytes는 숫자 배열로 옴
+        let text = match &value {
+            Value::String(text) => text.clone(),
+            Value::Number(number) => number.to_string(),
+            Value::Bool(flag) => flag.to_string(),
+            Value::Array(items) if memory_type == MemoryType::Bytes => {
+                let bytes: Option<Vec<u8>> = items.iter()
+                    .map(|item| item.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+                    .collect();
+                return bytes.map(MemoryValue::Bytes).ok_or_else(invalid);
+            },
+            _ => return Err(invalid()),
+        };
+        MemoryValue::parse(&text, memory_type).map_err(|_| invalid())
+    }
+
+    /// 메모리 워치 추가 (하드웨어 워치포인트 우선)
+    pub fn add_watch(&self, address: u64, memory_type: MemoryType, size: usize) -> VlitzResult<WatchHandle> {
+        self.call("addWatch", &[json!(format!("0x{:x}", address)), json!(memory_type.agent_name()), json!(size)])
+    }
+
+    /// 메모리 워치 제거
+    pub fn remove_watch(&self, id: u64) -> VlitzResult<()> {
+        self.call::<bool>("removeWatch", &[json!(id)]).map(|_| ())
+    }
+
+    /// 에이전트에 쌓인 워치 이벤트 가져오기
+    pub fn drain_watch_events(&self) -> VlitzResult<Vec<WatchEvent>> {
+        self.call("drainWatchEvents", &[])
+    }
+
+    /// 함수 추적 추가
+    pub fn add_trace(&self, address: u64, name: &str, arg_count: usize) -> VlitzResult<TraceHandle> {
+        self.call("addTrace", &[json!(format!("0x{:x}", address)), json!(name), json!(arg_count)])
+    }
+
+    /// 모듈의 함수 익스포트 중 패턴에 맞는 것을 모두 추적
+    pub fn trace_module(&self, module_name: &str, pattern: Option<&NamePattern>, arg_count: usize) -> VlitzResult<Vec<TraceHandle>> {
+        let [pattern, is_regex] = NamePattern::to_args(pattern);
+        self.call("traceModule", &[json!(module_name), pattern, is_regex, json!(arg_count)])
+    }
+
+    /// 함수 추적 제거
+    pub fn remove_trace(&self, id: u64) -> VlitzResult<()> {
+        self.call::<bool>("removeTrace", &[json!(id)]).map(|_| ())
+    }
+
+    /// 에이전트에 쌓인 추적 이벤트 가져오기
+    pub fn drain_trace_events(&self) -> VlitzResult<TraceBatch> {
+        self.call("drainTraceEvents", &[])
+    }
+
+    /// 함수 호출 동안의 기본 블록 커버리지 수집 시작
+    pub fn stalk_function(&self, address: u64, name: &str) -> VlitzResult<StalkHandle> {
+        self.call("stalkFunction", &[json!(format!("0x{:x}", address)), json!(name)])
+    }
+
+    /// 스레드의 기본 블록 커버리지 수집 시작
+    pub fn stalk_thread(&self, thread_id: u64) -> VlitzResult<StalkHandle> {
+        self.call("stalkThread", &[json!(thread_id)])
+    }
+
+    /// 커버리지 수집 중지
+    pub fn stop_stalk(&self, id: u64) -> VlitzResult<()> {
+        self.call::<bool>("stopStalk", &[json!(id)]).map(|_| ())
+    }
+
+    /// 새로 수집된 기본 블록 가져오기
+    pub fn drain_coverage(&self) -> VlitzResult<Vec<CoverageBatch>> {
+        self.call("drainCoverage", &[])
+    }
+
+    /// 메모리 잠금 추가 (에이전트 잠금 테이블에 등록)
+    pub fn add_lock(&self, address: u64, value: &MemoryValue, options: &LockOptions) -> VlitzResult<LockInfo> {
+        let memory_type = value.get_type();
+        let size = match value {
+            MemoryValue::String(s) => s.len() + 1,
+            MemoryValue::Bytes(bytes) => bytes.len(),
+            _ => memory_type.size(),
+        };
+        let options = serde_json::to_value(options)
+            .map_err(|e| VlitzError::TypeConversion(format!("Invalid lock options: {}", e)))?;
+        
+        self.call("addLock", &[
+            json!(format!("0x{:x}", address)),
+            json!(memory_type.agent_name()),
+            json!(size),
+            value.to_agent_value(),
+            options,
+        ])
+    }
+
+    /// 메모리 잠금 제거
+    pub fn remove_lock(&self, id: u64) -> VlitzResult<()> {
+        self.call::<bool>("removeLock", &[json!(id)]).map(|_| ())
+    }
+
+    /// 모든 메모리 잠금 제거 (제거된 개수 반환)
+    pub fn remove_all_locks(&self) -> VlitzResult<usize> {
+        self.call("removeAllLocks", &[])
+    }
+
+    /// 에이전트 잠금 테이블 조회
+    pub fn list_locks(&self) -> VlitzResult<Vec<LockInfo>> {
+        self.call("listLocks", &[])
+    }
+
+    /// 클래스 계층 정보 조회
+    pub fn class_hierarchy(&self, class_name: &str) -> VlitzResult<ClassHierarchy> {
+        self.call("classHierarchy", &[json!(class_name)])
+    }
+
+    /// 로드된 클래스 목록 (Java/ObjC)
+    pub fn enumerate_classes(&self) -> VlitzResult<Vec<VzData>> {
+        let names: Vec<String> = self.call("enumerateClasses", &[])?;
+        Ok(names.into_iter().map(VzData::new_class).collect())
+    }
+
+    /// ObjC 런타임 사용 가능 여부
+    pub fn objc_available(&self) -> VlitzResult<bool> {
+        self.call("objcAvailable", &[])
+    }
+
+    /// ObjC 클래스의 메서드 목록 (VzMethod)
+    pub fn enumerate_objc_methods(&self, class_name: &str) -> VlitzResult<Vec<VzData>> {
+        let methods: Vec<ObjcMethodInfo> = self.call("enumerateObjcMethods", &[json!(class_name)])?;
+        methods.iter()
+            .map(|method| method.to_vzdata(class_name))
+            .collect()
+    }
+
+    /// 네이티브 함수 후킹 (진입/반환 시 functionHook 메시지 전송), 후크 ID 반환
+    pub fn hook_function(&self, address: u64) -> VlitzResult<String> {
+        self.call("hookFunction", &[json!(format!("0x{:x}", address))])
+    }
+
+    /// hookFunction 후크 해제 (이미 해제되었으면 false)
+    pub fn unhook_function(&self, hook_id: &str) -> VlitzResult<bool> {
+        self.call("unhookFunction", &[json!(hook_id)])
+    }
+
+    /// ObjC 메서드 후킹, 훅 ID 반환
+    pub fn hook_objc_method(&self, class_name: &str, selector: &str) -> VlitzResult<String> {
+        self.call("hookObjcMethod", &[json!(class_name), json!(selector)])
+    }
+
+    /// ObjC 메서드 호출
+    ///
+    /// `receiver`가 없으면 클래스 객체에 대해 호출한다 (`+` 메서드).
+    pub fn call_objc_method(
+        &self,
+        class_name: &str,
+        selector: &str,
+        receiver: Option<u64>,
+        args: &[ObjcValue],
+    ) -> VlitzResult<ObjcValue> {
+        let receiver = match receiver {
+            Some(handle) => json!(format!("0x{:x}", handle)),
+            None => Value::Null,
+        };
+        let args = serde_json::to_value(args)
+            .map_err(|e| VlitzError::TypeConversion(format!("Failed to serialize ObjC arguments: {}", e)))?;
+
+        self.call("callObjcMethod", &[json!(class_name), json!(selector), receiver, args])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MockBackend;
+
+    #[test]
+    fn test_enumerate_classes() {
+        let backend = MockBackend::new()
+            .on_value("enumerateClasses", json!(["NSObject", "UIViewController"]));
+        let agent = Agent::new(&backend);
+
+        let classes = agent.enumerate_classes().unwrap();
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes[1].as_class().unwrap().name, "UIViewController");
+    }
+
+    #[test]
+    fn test_enumerate_objc_methods() {
+        let backend = MockBackend::new()
+            .on("enumerateObjcMethods", |args| {
+                assert_eq!(args[0], json!("UIView"));
+                Ok(json!([
+                    { "selector": "- initWithFrame:", "types": "@48@0:8{CGRect={CGPoint=dd}{CGSize=dd}}16" },
+                    { "selector": "+ layerClass", "types": "#16@0:8" }
+                ]))
+            });
+        let agent = Agent::new(&backend);
+
+        let methods = agent.enumerate_objc_methods("UIView").unwrap();
+        let init = methods[0].as_method().unwrap();
+        assert_eq!(init.class_name, "UIView");
+        assert_eq!(init.name, "- initWithFrame:");
+        assert_eq!(init.args, vec!["CGRect"]);
+        assert_eq!(init.ret, "id");
+
+        let layer_class = methods[1].as_method().unwrap();
+        assert!(layer_class.args.is_empty());
+        assert_eq!(layer_class.ret, "Class");
+    }
+
+    #[test]
+    fn test_hook_objc_method() {
+        let backend = MockBackend::new()
+            .on("hookObjcMethod", |args| {
+                Ok(json!(format!("objc_hook_{}_{}", args[0].as_str().unwrap(), args[1].as_str().unwrap())))
+            });
+        let agent = Agent::new(&backend);
+
+        let hook_id = agent.hook_objc_method("NSURLSession", "- dataTaskWithRequest:").unwrap();
+        assert_eq!(hook_id, "objc_hook_NSURLSession_- dataTaskWithRequest:");
+        assert_eq!(backend.call_count("hookObjcMethod"), 1);
+    }
+
+    #[test]
+    fn test_call_objc_method() {
+        let backend = MockBackend::new()
+            .on("callObjcMethod", |args| {
+                assert_eq!(args[2], json!("0x1000"));
+                assert_eq!(args[3], json!([
+                    { "type": "string", "value": "key" },
+                    { "type": "object", "handle": "0x2000" }
+                ]));
+                Ok(json!({ "type": "object", "handle": "0x3000", "className": "NSString", "description": "value" }))
+            });
+        let agent = Agent::new(&backend);
+
+        let result = agent.call_objc_method(
+            "NSDictionary",
+            "- objectForKey:",
+            Some(0x1000),
+            &[ObjcValue::parse("key"), ObjcValue::object(0x2000)],
+        ).unwrap();
+
+        assert_eq!(result, ObjcValue::Object {
+            handle: "0x3000".to_string(),
+            class_name: Some("NSString".to_string()),
+            description: Some("value".to_string()),
+        });
+    }
+
+    #[test]
+    fn test_query_classes_in_chunks() {
+        let names: Vec<Value> = (0..1200).map(|i| json!(format!("com.example.C{}", i))).collect();
+        let backend = MockBackend::new().with_query("queryClasses", Some("java"), names);
+        let agent = Agent::new(&backend);
+
+        let pattern = NamePattern::parse("/C1.*/");
+        let classes = agent.query_classes(Some(&pattern)).unwrap();
+        assert_eq!(classes.len(), 1200);
+        assert_eq!(backend.call_count("readQuery"), 3);
+        assert_eq!(backend.call_count("closeQuery"), 1);
+
+        let (_, args) = &backend.calls()[0];
+        assert_eq!(args, &vec![json!("C1.*"), json!(true)]);
+    }
+
+    #[test]
+    fn test_query_cancelled_between_chunks() {
+        let cancel = CancelToken::new();
+        let requested = cancel.clone();
+        let backend = MockBackend::new()
+            .on_value("queryClasses", json!({ "id": 1, "total": 1200, "runtime": "java" }))
+            .on("readQuery", move |_| {
+                // 첫 청크를 받는 중에 Ctrl-C
+                requested.cancel();
+                Ok(json!(vec!["com.example.C"; QUERY_CHUNK_SIZE]))
+            })
+            .on_value("closeQuery", json!(true));
+        let agent = Agent::new(&backend).with_cancel(&cancel);
+
+        assert!(matches!(agent.query_classes(None), Err(VlitzError::Cancelled)));
+        assert_eq!(backend.call_count("readQuery"), 1);
+        assert_eq!(backend.call_count("closeQuery"), 1);
+    }
+
+    #[test]
+    fn test_query_methods_java_inherited() {
+        let backend = MockBackend::new().with_query("queryMethods", Some("java"), vec![
+            json!({ "className": "com.example.Main", "name": "onCreate", "returnType": "void", "argumentTypes": ["android.os.Bundle"] }),
+            json!({ "className": "android.app.Activity", "name": "finish", "returnType": "void", "argumentTypes": [] }),
+        ]);
+        let agent = Agent::new(&backend);
+
+        let methods = agent.query_methods("com.example.Main", true, None).unwrap();
+        assert_eq!(methods.len(), 2);
+        assert_eq!(methods[1].as_method().unwrap().class_name, "android.app.Activity");
+        assert_eq!(methods[0].as_method().unwrap().args, vec!["android.os.Bundle"]);
+    }
+
+    #[test]
+    fn test_query_methods_objc() {
+        let backend = MockBackend::new().with_query("queryMethods", Some("objc"), vec![
+            json!({ "selector": "- viewDidLoad", "types": "v16@0:8" }),
+            json!({ "selector": "- init", "types": "@16@0:8", "className": "NSObject" }),
+        ]);
+        let agent = Agent::new(&backend);
+
+        let methods = agent.query_methods("ViewController", true, None).unwrap();
+        assert_eq!(methods[0].as_method().unwrap().class_name, "ViewController");
+        assert_eq!(methods[1].as_method().unwrap().class_name, "NSObject");
+    }
+
+    #[test]
+    fn test_query_without_runtime() {
+        let backend = MockBackend::new().with_query("queryMethods", None, vec![]);
+        let agent = Agent::new(&backend);
+
+        assert!(agent.query_methods("Foo", false, None).is_err());
+        assert_eq!(backend.call_count("closeQuery"), 1);
+    }
+
+    #[test]
+    fn test_query_exports() {
+        let backend = MockBackend::new().with_query("queryExports", None, vec![
+            json!({ "type": "function", "name": "open", "address": "0x7f001000" }),
+            json!({ "type": "variable", "name": "environ", "address": "0x7f002000" }),
+        ]);
+        let agent = Agent::new(&backend);
+
+        let exports = agent.query_exports("libc.so", Some(&NamePattern::parse("o"))).unwrap();
+        assert!(exports[0].as_function().is_some());
+        assert!(exports[1].as_variable().is_some());
+
+        let (_, args) = &backend.calls()[0];
+        assert_eq!(args, &vec![json!("libc.so"), json!("o"), json!(false)]);
+    }
+
+    #[test]
+    fn test_watch_roundtrip() {
+        let backend = MockBackend::new()
+            .on("addWatch", |args| {
+                assert_eq!(args, &[json!("0x1000"), json!("float"), json!(4)]);
+                Ok(json!({ "id": 1, "mode": "hardware", "value": 100.0 }))
+            })
+            .on_value("drainWatchEvents", json!([{
+                "watchId": 1, "address": "0x1000", "from": "0x7f00abcd", "symbol": "libgame.so!takeHit+0x1c",
+                "threadId": 4242, "backtrace": ["libgame.so!update+0x40"],
+                "oldValue": 100.0, "newValue": 95.0, "changed": true, "timestamp": 1
+            }]));
+        let agent = Agent::new(&backend);
+
+        let handle = agent.add_watch(0x1000, MemoryType::Float, 4).unwrap();
+        assert_eq!(handle.mode, crate::watch::WatchMode::Hardware);
+
+        let events = agent.drain_watch_events().unwrap();
+        assert_eq!(events[0].summary(), "0x1000: 100.0 -> 95.0 by 0x7f00abcd (libgame.so!takeHit+0x1c) [tid 4242]");
+    }
+
+    #[test]
+    fn test_read_value() {
+        let backend = MockBackend::new().on("readMemory", |args| {
+            Ok(match args[2].as_str().unwrap() {
+                "float" => json!(47.29999923706055),
+                "ulong" => json!("18446744073709551615"),
+                "bytes" => json!([1, 2, 255]),
+                _ => json!(null),
+            })
+        });
+        let agent = Agent::new(&backend);
+
+        assert!(matches!(agent.read_value(0x1000, MemoryType::Float, 4).unwrap(), MemoryValue::Float(v) if v == 47.3));
+        assert!(matches!(agent.read_value(0x1000, MemoryType::ULong, 8).unwrap(), MemoryValue::ULong(u64::MAX)));
+        assert!(matches!(agent.read_value(0x1000, MemoryType::Bytes, 3).unwrap(), MemoryValue::Bytes(bytes) if bytes == [1, 2, 255]));
+        assert!(agent.read_value(0x1000, MemoryType::Int, 4).is_err());
+    }
+
+    #[test]
+    fn test_lock_value_serialized_as_json() {
+        let backend = MockBackend::new().on("addLock", |args| {
+            Ok(json!({
+                "id": 1, "address": args[0], "type": args[1], "value": args[3], "mode": args[4]["mode"],
+                "threshold": args[4]["threshold"], "intervalMs": args[4]["intervalMs"], "writes": 1, "original": "x"
+            }))
+        });
+        let agent = Agent::new(&backend);
+
+        let lock = agent.add_lock(0x2000, &MemoryValue::String("a\"); evil()".to_string()), &LockOptions::default()).unwrap();
+        assert_eq!(lock.value, json!("a\"); evil()"));
+
+        let (_, args) = &backend.calls()[0];
+        assert_eq!(args[2], json!(12));
+        assert_eq!(args[4], json!({ "mode": "always", "threshold": null, "intervalMs": 50 }));
+
+        agent.add_lock(0x2000, &MemoryValue::ULong(u64::MAX), &LockOptions::default()).unwrap();
+        assert_eq!(backend.calls()[1].1[3], json!("18446744073709551615"));
+    }
+
+    #[test]
+    fn test_trace_module_and_drain() {
+        let backend = MockBackend::new()
+            .on_value("traceModule", json!([{ "id": 1, "address": "0x7f000100", "name": "libc.so!open" }]))
+            .on_value("drainTraceEvents", json!({
+                "events": [
+                    { "traceId": 1, "event": "enter", "threadId": 7, "depth": 0, "timestamp": 1.5, "args": ["0x1234"] },
+                    { "traceId": 1, "event": "leave", "threadId": 7, "depth": 0, "timestamp": 2.0, "retval": "0x3" }
+                ],
+                "dropped": 0
+            }));
+        let agent = Agent::new(&backend);
+
+        let handles = agent.trace_module("libc.so", Some(&NamePattern::parse("/^open$/")), 2).unwrap();
+        assert_eq!(handles[0].name, "libc.so!open");
+        assert_eq!(backend.calls()[0].1, vec![json!("libc.so"), json!("^open$"), json!(true), json!(2)]);
+
+        let batch = agent.drain_trace_events().unwrap();
+        assert_eq!(batch.events[1].kind, crate::trace::TraceEventKind::Leave);
+        assert_eq!(batch.events[1].retval.as_deref(), Some("0x3"));
+    }
+
+    #[test]
+    fn test_invalid_response() {
+        let backend = MockBackend::new().on_value("objcAvailable", json!("yes"));
+        let agent = Agent::new(&backend);
+
+        assert!(agent.objc_available().is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use serde_json::{json, Value};
+use vlitz_shared::{VlitzError, VlitzResult};
+use crate::session::VlitzSession;
+use crate::message::{MessageQueue, ScriptMessage};
+use crate::event::EventBus;
+
+/// 에이전트 RPC 호출 백엔드
+///
+/// 실제 세션은 Frida 스크립트의 `rpc.exports`를 호출하고,
+/// 테스트에서는 `MockBackend`로 대체하여 타겟 없이 로직을 검증한다.
+pub trait RpcBackend {
+    /// RPC 익스포트 호출
+    fn call(&self, export_name: &str, args: &[Value]) -> VlitzResult<Value>;
+
+    /// 에이전트가 `send()`로 보낸 메시지를 모두 꺼냄
+    fn drain_messages(&self) -> Vec<ScriptMessage> {
+        Vec::new()
+    }
+
+    /// 에이전트 이벤트 버스 (없으면 이벤트 로그를 쓸 수 없음)
+    fn events(&self) -> Option<&EventBus> {
+        None
+    }
+
+    /// 추가 스크립트 로드 (프리셋 등)
+    fn load_script(&mut self, name: &str, _source: &str) -> VlitzResult<()> {
+        Err(VlitzError::ScriptExec(format!("Cannot load script '{}' on this backend", name)))
+    }
+
+    /// 추가 스크립트 언로드
+    fn unload_script(&mut self, name: &str) -> VlitzResult<()> {
+        Err(VlitzError::ScriptExec(format!("Script '{}' not found", name)))
+    }
+
+    /// 추가 스크립트의 RPC 익스포트 호출
+    fn call_script(&self, name: &str, _export_name: &str, _args: &[Value]) -> VlitzResult<Value> {
+        Err(VlitzError::ScriptExec(format!("Script '{}' not found", name)))
+    }
+}
+
+impl RpcBackend for VlitzSession {
+    fn call(&self, export_name: &str, args: &[Value]) -> VlitzResult<Value> {
+        self.call_rpc(crate::session::BASE_SCRIPT_NAME, export_name, args)
+    }
+
+    fn drain_messages(&self) -> Vec<ScriptMessage> {
+        VlitzSession::drain_messages(self)
+    }
+
+    fn events(&self) -> Option<&EventBus> {
+        Some(VlitzSession::events(self))
+    }
+
+    fn load_script(&mut self, name: &str, source: &str) -> VlitzResult<()> {
+        self.create_script(name, source).map(|_| ())
+    }
+
+    fn unload_script(&mut self, name: &str) -> VlitzResult<()> {
+        VlitzSession::unload_script(self, name)
+    }
+
+    fn call_script(&self, name: &str, export_name: &str, args: &[Value]) -> VlitzResult<Value> {
+        self.call_rpc(name, export_name, args)
+    }
+}
+
+type MockHandler = Box<dyn Fn(&[Value]) -> VlitzResult<Value>>;
+
+/// 테스트용 모의 RPC 백엔드
+#[derive(Default)]
+pub struct MockBackend {
+    /// 익스포트 이름 -> 응답 핸들러
+    handlers: HashMap<String, MockHandler>,
+    /// 호출 기록 (익스포트 이름, 인자)
+    calls: RefCell<Vec<(String, Vec<Value>)>>,
+    /// `with_query`로 등록한 쿼리 결과 (쿼리 ID - 1 -> 항목)
+    queries: Rc<RefCell<Vec<Vec<Value>>>>,
+    /// 테스트에서 넣는 에이전트 메시지
+    messages: MessageQueue,
+    /// `with_events`로 붙인 이벤트 버스
+    events: Option<EventBus>,
+    /// 로드된 추가 스크립트 (이름 -> 소스)
+    scripts: RefCell<HashMap<String, String>>,
+}
+
+impl MockBackend {
+    /// 새 모의 백엔드 생성
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 익스포트 핸들러 등록
+    pub fn on<F>(mut self, export_name: &str, handler: F) -> Self
+    where
+        F: Fn(&[Value]) -> VlitzResult<Value> + 'static,
+    {
+        self.handlers.insert(export_name.to_string(), Box::new(handler));
+        self
+    }
+
+    /// 고정 응답 등록
+    pub fn on_value(self, export_name: &str, value: Value) -> Self {
+        self.on(export_name, move |_| Ok(value.clone()))
+    }
+
+    /// 쿼리 커서 익스포트 등록 (`export_name`이 커서를 열고 `readQuery`/`closeQuery`로 읽음)
+    ///
+    /// 여러 번 등록하면 쿼리마다 다른 커서 ID를 받는다. `runtime`은 클래스/메서드 쿼리의 런타임 이름이다.
+    pub fn with_query(self, export_name: &str, runtime: Option<&str>, items: Vec<Value>) -> Self {
+        let total = items.len();
+        let id = {
+            let mut queries = self.queries.borrow_mut();
+            queries.push(items);
+            queries.len()
+        };
+        let queries = Rc::clone(&self.queries);
+
+        self.on_value(export_name, json!({ "id": id, "total": total, "runtime": runtime }))
+            .on("readQuery", move |args| {
+                let queries = queries.borrow();
+                let items = args[0].as_u64()
+                    .and_then(|id| queries.get((id as usize).wrapping_sub(1)))
+                    .ok_or_else(|| VlitzError::ScriptExec(format!("Query not found: {}", args[0])))?;
+                let offset = args[1].as_u64().unwrap_or(0) as usize;
+                let limit = args[2].as_u64().unwrap_or(0) as usize;
+                Ok(json!(items.iter().skip(offset).take(limit).cloned().collect::<Vec<_>>()))
+            })
+            .on_value("closeQuery", json!(true))
+    }
+
+    /// 이벤트 버스 연결 (이후 메시지는 `EventBus::publish`로 넣음)
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.messages = events.message_queue();
+        self.events = Some(events);
+        self
+    }
+
+    /// 메시지 큐 (백엔드를 넘긴 뒤에도 테스트에서 메시지를 넣을 때 사용)
+    pub fn message_queue(&self) -> MessageQueue {
+        self.messages.clone()
+    }
+
+    /// 로드된 추가 스크립트 이름 (정렬됨)
+    pub fn loaded_scripts(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.scripts.borrow().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// 로드된 추가 스크립트 소스
+    pub fn script_source(&self, name: &str) -> Option<String> {
+        self.scripts.borrow().get(name).cloned()
+    }
+
+    /// 호출 기록 가져오기
+    pub fn calls(&self) -> Vec<(String, Vec<Value>)> {
+        self.calls.borrow().clone()
+    }
+
+    /// 특정 익스포트 호출 횟수
+    pub fn call_count(&self, export_name: &str) -> usize {
+        self.calls.borrow().iter().filter(|(name, _)| name == export_name).count()
+    }
+}
+
+impl RpcBackend for MockBackend {
+    fn call(&self, export_name: &str, args: &[Value]) -> VlitzResult<Value> {
+        self.calls.borrow_mut().push((export_name.to_string(), args.to_vec()));
+
+        match self.handlers.get(export_name) {
+            Some(handler) => handler(args),
+            None => Err(VlitzError::ScriptExec(format!("RPC export '{}' not mocked", export_name))),
+        }
+    }
+
+    fn drain_messages(&self) -> Vec<ScriptMessage> {
+        self.messages.drain()
+    }
+
+    fn events(&self) -> Option<&EventBus> {
+        self.events.as_ref()
+    }
+
+    fn load_script(&mut self, name: &str, source: &str) -> VlitzResult<()> {
+        let mut scripts = self.scripts.borrow_mut();
+        if scripts.contains_key(name) {
+            return Err(VlitzError::ScriptExec(format!("Script '{}' already exists", name)));
+        }
+        scripts.insert(name.to_string(), source.to_string());
+        Ok(())
+    }
+
+    fn unload_script(&mut self, name: &str) -> VlitzResult<()> {
+        match self.scripts.borrow_mut().remove(name) {
+            Some(_) => Ok(()),
+            None => Err(VlitzError::ScriptExec(format!("Script '{}' not found", name))),
+        }
+    }
+
+    /// 추가 스크립트 익스포트는 `"스크립트.익스포트"` 이름으로 모의한다.
+    fn call_script(&self, name: &str, export_name: &str, args: &[Value]) -> VlitzResult<Value> {
+        if !self.scripts.borrow().contains_key(name) {
+            return Err(VlitzError::ScriptExec(format!("Script '{}' not found", name)));
+        }
+        self.call(&format!("{}.{}", name, export_name), args)
+    }
+}
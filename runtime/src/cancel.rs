@@ -0,0 +1,40 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use vlitz_shared::{VlitzError, VlitzResult};
+
+/// 진행 중인 명령어 취소 요청 (복제본끼리 상태 공유, 시그널 핸들러에서도 설정 가능)
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// 취소되지 않은 새 토큰 생성
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 취소 요청
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// 취소 요청 여부
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// 다음 명령어를 위해 취소 상태 초기화
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::SeqCst);
+    }
+
+    /// 취소됐으면 `VlitzError::Cancelled` 반환
+    pub fn check(&self) -> VlitzResult<()> {
+        if self.is_cancelled() {
+            Err(VlitzError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
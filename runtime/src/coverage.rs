@@ -0,0 +1,48 @@
+use serde::Deserialize;
+use crate::process::deserialize_address;
+
+/// 커버리지 수집 대상 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StalkKind {
+    /// 함수 호출 동안만 수집
+    Function,
+    /// 스레드 전체 수집
+    Thread,
+}
+
+/// stalkFunction/stalkThread 응답
+#[derive(Debug, Clone, Deserialize)]
+pub struct StalkHandle {
+    pub id: u64,
+    pub kind: StalkKind,
+    /// 표시용 대상 이름 (함수 이름 또는 "thread <tid>")
+    pub target: String,
+}
+
+/// 실행된 기본 블록 (["0x시작 주소", 크기])
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct CoverageBlock(
+    #[serde(deserialize_with = "deserialize_address")] pub u64,
+    pub u32,
+);
+
+impl CoverageBlock {
+    /// 블록 시작 주소
+    pub fn start(&self) -> u64 {
+        self.0
+    }
+
+    /// 블록 크기 (바이트)
+    pub fn size(&self) -> u32 {
+        self.1
+    }
+}
+
+/// drainCoverage 응답 항목
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageBatch {
+    pub stalk_id: u64,
+    pub blocks: Vec<CoverageBlock>,
+}
@@ -0,0 +1,539 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde_json::Value;
+use crate::message::{MessageKind, MessageQueue, ScriptMessage};
+
+/// 이벤트 로그에 보관하는 기본 최대 이벤트 수
+pub const DEFAULT_EVENT_LOG_CAPACITY: usize = 10_000;
+
+/// 후크가 걸린 런타임
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookRuntime {
+    Native,
+    Java,
+    ObjC,
+}
+
+impl HookRuntime {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookRuntime::Native => "native",
+            HookRuntime::Java => "java",
+            HookRuntime::ObjC => "objc",
+        }
+    }
+}
+
+/// 에이전트 메시지를 해석한 이벤트 내용
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventKind {
+    /// `hookFunction`/Java/ObjC 메서드 후크 진입·반환
+    Hook {
+        runtime: HookRuntime,
+        hook_id: String,
+        /// "enter" 또는 "leave"
+        phase: String,
+        thread_id: Option<u64>,
+        args: Vec<Value>,
+        retval: Option<Value>,
+    },
+    /// 메모리 워치 히트
+    MemoryWatch {
+        watch_id: u64,
+        address: String,
+        /// 쓰기 명령어 주소와 심볼
+        from: Option<String>,
+        symbol: Option<String>,
+        thread_id: Option<u64>,
+        old_value: Value,
+        new_value: Value,
+        changed: bool,
+    },
+    /// 프리셋 후크 동작
+    PresetHit {
+        preset: String,
+        hook: String,
+        hits: u64,
+        detail: Option<String>,
+    },
+    /// 형식을 모르는 `send()` 페이로드
+    Send(Value),
+    /// 스크립트 예외
+    Error {
+        description: String,
+        stack: Option<String>,
+    },
+    /// `console.log` 출력
+    Log {
+        level: String,
+        text: String,
+    },
+}
+
+/// 이벤트 분류 (필터용)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventCategory {
+    Hook,
+    Watch,
+    Preset,
+    Send,
+    Error,
+    Log,
+}
+
+impl EventCategory {
+    /// 모든 분류 (자동완성 등에서 사용)
+    pub const ALL: [EventCategory; 6] = [
+        EventCategory::Hook,
+        EventCategory::Watch,
+        EventCategory::Preset,
+        EventCategory::Send,
+        EventCategory::Error,
+        EventCategory::Log,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventCategory::Hook => "hook",
+            EventCategory::Watch => "watch",
+            EventCategory::Preset => "preset",
+            EventCategory::Send => "send",
+            EventCategory::Error => "error",
+            EventCategory::Log => "log",
+        }
+    }
+}
+
+impl FromStr for EventCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hook" => Ok(EventCategory::Hook),
+            "watch" => Ok(EventCategory::Watch),
+            "preset" => Ok(EventCategory::Preset),
+            "send" => Ok(EventCategory::Send),
+            "error" => Ok(EventCategory::Error),
+            "log" => Ok(EventCategory::Log),
+            _ => Err(format!("Unknown event type: {} (expected hook, watch, preset, send, error or log)", s)),
+        }
+    }
+}
+
+/// 이벤트 버스가 받은 이벤트
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    /// 받은 순서대로 1부터 증가하는 번호
+    pub id: u64,
+    /// 이벤트 버스 시작 후 경과 시간
+    pub elapsed: Duration,
+    /// 보낸 스크립트 이름
+    pub script: String,
+    pub kind: EventKind,
+}
+
+impl Event {
+    /// 스크립트 메시지를 이벤트로 해석
+    pub fn from_message(id: u64, elapsed: Duration, message: ScriptMessage) -> Self {
+        let kind = match message.kind {
+            MessageKind::Send(payload) => decode_payload(payload),
+            MessageKind::Error { description, stack } => EventKind::Error { description, stack },
+            MessageKind::Log { level, text } => EventKind::Log { level, text },
+        };
+        Self { id, elapsed, script: message.script, kind }
+    }
+
+    pub fn category(&self) -> EventCategory {
+        match self.kind {
+            EventKind::Hook { .. } => EventCategory::Hook,
+            EventKind::MemoryWatch { .. } => EventCategory::Watch,
+            EventKind::PresetHit { .. } => EventCategory::Preset,
+            EventKind::Send(_) => EventCategory::Send,
+            EventKind::Error { .. } => EventCategory::Error,
+            EventKind::Log { .. } => EventCategory::Log,
+        }
+    }
+
+    /// 후크 ID, 워치 ID 또는 프리셋 이름
+    pub fn source_id(&self) -> Option<String> {
+        match &self.kind {
+            EventKind::Hook { hook_id, .. } => Some(hook_id.clone()),
+            EventKind::PresetHit { preset, .. } => Some(preset.clone()),
+            EventKind::MemoryWatch { watch_id, .. } => Some(watch_id.to_string()),
+            _ => None,
+        }
+    }
+
+    /// 한 줄 요약 (번호/시간 제외)
+    pub fn summary(&self) -> String {
+        match &self.kind {
+            EventKind::Hook { runtime, hook_id, phase, thread_id, args, retval } => {
+                let mut line = format!("{} {} {}", runtime.name(), hook_id, phase);
+                if let Some(thread_id) = thread_id {
+                    line.push_str(&format!(" tid={}", thread_id));
+                }
+                if !args.is_empty() {
+                    let args: Vec<String> = args.iter().map(display_value).collect();
+                    line.push_str(&format!(" args=({})", args.join(", ")));
+                }
+                if let Some(retval) = retval {
+                    line.push_str(&format!(" retval={}", display_value(retval)));
+                }
+                line
+            },
+            EventKind::MemoryWatch { watch_id, address, from, symbol, old_value, new_value, changed, .. } => {
+                let mut line = format!("watch #{} {}", watch_id, address);
+                if *changed {
+                    line.push_str(&format!(" {} -> {}", display_value(old_value), display_value(new_value)));
+                } else {
+                    line.push_str(&format!(" = {}", display_value(new_value)));
+                }
+                match (symbol, from) {
+                    (Some(symbol), _) => line.push_str(&format!(" from {}", symbol)),
+                    (None, Some(from)) => line.push_str(&format!(" from {}", from)),
+                    _ => {},
+                }
+                line
+            },
+            EventKind::PresetHit { preset, hook, hits, detail } => match detail {
+                Some(detail) => format!("preset {} {} hit #{} ({})", preset, hook, hits, detail),
+                None => format!("preset {} {} hit #{}", preset, hook, hits),
+            },
+            EventKind::Send(payload) => format!("send {}", payload),
+            EventKind::Error { description, .. } => format!("error {}", description),
+            EventKind::Log { level, text } => format!("log[{}] {}", level, text),
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} +{:.3}s [{}] {}", self.id, self.elapsed.as_secs_f64(), self.script, self.summary())
+    }
+}
+
+/// `send()` 페이로드의 `type` 필드로 이벤트 종류 결정
+fn decode_payload(payload: Value) -> EventKind {
+    let text = |key: &str| payload.get(key).and_then(Value::as_str).map(str::to_string);
+    let thread_id = payload.get("threadId").and_then(Value::as_u64);
+
+    let runtime = match payload.get("type").and_then(Value::as_str) {
+        Some("functionHook") => Some(HookRuntime::Native),
+        Some("javaMethodHook") => Some(HookRuntime::Java),
+        Some("objcMethodHook") => Some(HookRuntime::ObjC),
+        Some("memoryWatch") => {
+            return EventKind::MemoryWatch {
+                watch_id: payload.get("watchId").and_then(Value::as_u64).unwrap_or_default(),
+                address: text("address").unwrap_or_default(),
+                from: text("from"),
+                symbol: text("symbol"),
+                thread_id,
+                old_value: payload.get("oldValue").cloned().unwrap_or(Value::Null),
+                new_value: payload.get("newValue").cloned().unwrap_or(Value::Null),
+                changed: payload.get("changed").and_then(Value::as_bool).unwrap_or(true),
+            };
+        },
+        Some("presetHit") => {
+            return EventKind::PresetHit {
+                preset: text("preset").unwrap_or_default(),
+                hook: text("hook").unwrap_or_default(),
+                hits: payload.get("hits").and_then(Value::as_u64).unwrap_or_default(),
+                detail: text("detail"),
+            };
+        },
+        _ => None,
+    };
+
+    match runtime {
+        Some(runtime) => EventKind::Hook {
+            runtime,
+            hook_id: text("id").unwrap_or_default(),
+            phase: text("event").unwrap_or_default(),
+            thread_id,
+            args: payload.get("args").and_then(Value::as_array).cloned().unwrap_or_default(),
+            retval: payload.get("retval").cloned(),
+        },
+        None => EventKind::Send(payload),
+    }
+}
+
+/// 문자열은 따옴표 없이 표시
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// 이벤트 로그 조회 조건
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    pub category: Option<EventCategory>,
+    pub script: Option<String>,
+    /// 후크 ID 또는 워치 ID
+    pub source_id: Option<String>,
+    /// 요약에 포함된 문자열
+    pub text: Option<String>,
+    /// 이 번호 이후의 이벤트만
+    pub after: Option<u64>,
+    /// 마지막 N개만
+    pub last: Option<usize>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &Event) -> bool {
+        self.category.is_none_or(|category| event.category() == category)
+            && self.script.as_ref().is_none_or(|script| &event.script == script)
+            && self.source_id.as_ref().is_none_or(|id| event.source_id().as_ref() == Some(id))
+            && self.text.as_ref().is_none_or(|text| event.summary().contains(text.as_str()))
+            && self.after.is_none_or(|after| event.id > after)
+    }
+}
+
+/// 받은 이벤트를 보관하는 링 버퍼 (스레드 간 공유)
+#[derive(Debug, Clone)]
+pub struct EventLog {
+    events: Arc<Mutex<VecDeque<Event>>>,
+    capacity: usize,
+}
+
+impl EventLog {
+    /// 최대 `capacity`개를 보관하는 이벤트 로그 생성
+    pub fn new(capacity: usize) -> Self {
+        Self { events: Arc::new(Mutex::new(VecDeque::new())), capacity: capacity.max(1) }
+    }
+
+    /// 이벤트 추가 (가득 차면 가장 오래된 이벤트를 버림)
+    pub fn push(&self, event: Event) {
+        let mut events = self.lock();
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// 조건에 맞는 이벤트 (오래된 순)
+    pub fn query(&self, filter: &EventFilter) -> Vec<Event> {
+        let events = self.lock();
+        let mut matched: Vec<Event> = events.iter().filter(|event| filter.matches(event)).cloned().collect();
+        if let Some(last) = filter.last {
+            matched.drain(..matched.len().saturating_sub(last));
+        }
+        matched
+    }
+
+    /// 보관 중인 이벤트 수
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// 모든 이벤트 삭제 (삭제한 수 반환)
+    pub fn clear(&self) -> usize {
+        let mut events = self.lock();
+        let count = events.len();
+        events.clear();
+        count
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<Event>> {
+        self.events.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_LOG_CAPACITY)
+    }
+}
+
+type EventListener = Box<dyn FnMut(&Event) + Send>;
+
+/// 스크립트 메시지를 받아 이벤트로 바꾸는 이벤트 버스
+///
+/// Frida 메시지 스레드는 채널에 메시지를 넣기만 하고, 해석/기록/구독자 호출은
+/// 백그라운드 스레드에서 처리한다. 원본 메시지는 스크립트 핸들러용 `MessageQueue`로도 전달된다.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: Sender<ScriptMessage>,
+    log: EventLog,
+    messages: MessageQueue,
+    listeners: Arc<Mutex<Vec<EventListener>>>,
+}
+
+impl EventBus {
+    /// 이벤트 버스 생성 및 백그라운드 스레드 시작
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let bus = Self {
+            sender,
+            log: EventLog::default(),
+            messages: MessageQueue::new(),
+            listeners: Arc::new(Mutex::new(Vec::new())),
+        };
+
+        let worker = bus.clone_parts();
+        thread::Builder::new()
+            .name("vlitz-events".to_string())
+            .spawn(move || worker.run(receiver))
+            .expect("Failed to spawn event bus thread");
+        bus
+    }
+
+    /// 메시지를 버스로 보내는 송신자 (Frida 메시지 핸들러용)
+    pub fn sender(&self) -> Sender<ScriptMessage> {
+        self.sender.clone()
+    }
+
+    /// 메시지 발행
+    pub fn publish(&self, message: ScriptMessage) {
+        // 수신 스레드는 버스가 살아 있는 동안 종료되지 않음
+        let _ = self.sender.send(message);
+    }
+
+    /// 이벤트 로그
+    pub fn log(&self) -> &EventLog {
+        &self.log
+    }
+
+    /// 스크립트 핸들러용 원본 메시지 큐
+    pub fn message_queue(&self) -> MessageQueue {
+        self.messages.clone()
+    }
+
+    /// 새 이벤트마다 호출할 구독자 등록 (백그라운드 스레드에서 호출됨)
+    pub fn subscribe<F>(&self, listener: F)
+    where
+        F: FnMut(&Event) + Send + 'static,
+    {
+        self.lock_listeners().push(Box::new(listener));
+    }
+
+    /// 등록된 구독자 모두 제거
+    pub fn unsubscribe_all(&self) {
+        self.lock_listeners().clear();
+    }
+
+    fn clone_parts(&self) -> Worker {
+        Worker {
+            log: self.log.clone(),
+            messages: self.messages.clone(),
+            listeners: self.listeners.clone(),
+        }
+    }
+
+    fn lock_listeners(&self) -> MutexGuard<'_, Vec<EventListener>> {
+        self.listeners.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 백그라운드 스레드 쪽 상태 (송신자를 갖지 않아 버스가 모두 사라지면 종료)
+struct Worker {
+    log: EventLog,
+    messages: MessageQueue,
+    listeners: Arc<Mutex<Vec<EventListener>>>,
+}
+
+impl Worker {
+    fn run(self, receiver: Receiver<ScriptMessage>) {
+        let started = Instant::now();
+
+        for (id, message) in (1..).zip(receiver) {
+            self.messages.push(message.clone());
+
+            let event = Event::from_message(id, started.elapsed(), message);
+            self.log.push(event.clone());
+
+            let mut listeners = self.listeners.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            for listener in listeners.iter_mut() {
+                listener(&event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn send(payload: Value) -> ScriptMessage {
+        ScriptMessage { script: "vlitz".to_string(), kind: MessageKind::Send(payload) }
+    }
+
+    #[test]
+    fn test_decode_events() {
+        let hook = Event::from_message(1, Duration::ZERO, send(json!({
+            "type": "functionHook", "id": "hook_0x10", "event": "enter", "threadId": 7, "args": ["0x1", "0x2"]
+        })));
+        assert_eq!(hook.category(), EventCategory::Hook);
+        assert_eq!(hook.summary(), "native hook_0x10 enter tid=7 args=(0x1, 0x2)");
+
+        let watch = Event::from_message(2, Duration::ZERO, send(json!({
+            "type": "memoryWatch", "watchId": 3, "address": "0x1000", "symbol": "libgame.so!update",
+            "oldValue": 5, "newValue": 7, "changed": true
+        })));
+        assert_eq!(watch.source_id().as_deref(), Some("3"));
+        assert_eq!(watch.summary(), "watch #3 0x1000 5 -> 7 from libgame.so!update");
+
+        let preset = Event::from_message(3, Duration::ZERO, send(json!({
+            "type": "presetHit", "preset": "unpinning", "hook": "OkHttp3 CertificatePinner.check", "hits": 2, "detail": "example.com"
+        })));
+        assert_eq!(preset.category(), EventCategory::Preset);
+        assert_eq!(preset.summary(), "preset unpinning OkHttp3 CertificatePinner.check hit #2 (example.com)");
+
+        let other = Event::from_message(3, Duration::ZERO, send(json!({ "type": "custom" })));
+        assert_eq!(other.category(), EventCategory::Send);
+    }
+
+    #[test]
+    fn test_log_filter_and_capacity() {
+        let log = EventLog::new(3);
+        for id in 1..=4 {
+            let phase = if id % 2 == 0 { "leave" } else { "enter" };
+            log.push(Event::from_message(id, Duration::ZERO, send(json!({
+                "type": "functionHook", "id": format!("hook_{}", id % 2), "event": phase
+            }))));
+        }
+        assert_eq!(log.len(), 3);
+
+        let enters = log.query(&EventFilter { text: Some("enter".to_string()), ..Default::default() });
+        assert_eq!(enters.iter().map(|e| e.id).collect::<Vec<_>>(), vec![3]);
+
+        let last = log.query(&EventFilter { category: Some(EventCategory::Hook), last: Some(2), ..Default::default() });
+        assert_eq!(last.iter().map(|e| e.id).collect::<Vec<_>>(), vec![3, 4]);
+
+        assert_eq!(log.clear(), 3);
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn test_bus_delivers_on_background_thread() {
+        let bus = EventBus::new();
+        let (tx, rx) = mpsc::channel();
+        bus.subscribe(move |event| {
+            let _ = tx.send((event.id, thread::current().name().map(str::to_string)));
+        });
+
+        bus.publish(send(json!({ "type": "functionHook", "id": "hook_0x10", "event": "enter" })));
+        let (id, thread_name) = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        assert_eq!(id, 1);
+        assert_eq!(thread_name.as_deref(), Some("vlitz-events"));
+        assert_eq!(bus.message_queue().drain().len(), 1);
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Serialize, Deserialize};
+use vlitz_shared::VzData;
+
+/// 에이전트가 반환하는 Java 메서드 정보
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaMethodInfo {
+    /// 메서드를 선언한 클래스
+    pub class_name: String,
+    /// 메서드 이름
+    pub name: String,
+    /// 반환 타입
+    pub return_type: String,
+    /// 인자 타입 목록
+    pub argument_types: Vec<String>,
+}
+
+impl JavaMethodInfo {
+    /// VzMethod로 변환
+    pub fn to_vzdata(&self) -> VzData {
+        VzData::new_method(
+            self.class_name.clone(),
+            self.name.clone(),
+            self.argument_types.clone(),
+            self.return_type.clone(),
+        )
+    }
+}
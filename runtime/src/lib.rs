@@ -1,7 +1,31 @@
 pub mod frida;
 pub mod session;
 pub mod script;
+pub mod backend;
+pub mod agent;
+pub mod objc;
+pub mod java;
+pub mod process;
+pub mod watch;
+pub mod lock;
+pub mod trace;
+pub mod coverage;
+pub mod message;
+pub mod event;
+pub mod cancel;
 
 pub use frida::*;
 pub use session::*;
-pub use script::*; 
\ No newline at end of file
+pub use script::*;
+pub use backend::*;
+pub use agent::*;
+pub use objc::*;
+pub use java::*;
+pub use process::*;
+pub use watch::*;
+pub use lock::*;
+pub use trace::*;
+pub use coverage::*;
+pub use message::*;
+pub use event::*;
+pub use cancel::*;
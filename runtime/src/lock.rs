@@ -0,0 +1,86 @@
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+/// 기본 잠금 주기 (밀리초)
+pub const DEFAULT_LOCK_INTERVAL_MS: u64 = 50;
+
+/// 잠금 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockMode {
+    /// 항상 값을 덮어씀
+    Always,
+    /// 현재 값이 임계값보다 작을 때만 덮어씀
+    Below,
+    /// 현재 값이 임계값보다 클 때만 덮어씀
+    Above,
+}
+
+/// 잠금 옵션 (addLock의 options 인자)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockOptions {
+    pub mode: LockMode,
+    /// below/above 모드의 임계값
+    pub threshold: Option<f64>,
+    pub interval_ms: u64,
+}
+
+impl Default for LockOptions {
+    fn default() -> Self {
+        Self {
+            mode: LockMode::Always,
+            threshold: None,
+            interval_ms: DEFAULT_LOCK_INTERVAL_MS,
+        }
+    }
+}
+
+impl LockOptions {
+    /// 조건 설명 문자열 (예: "below 50 every 50ms")
+    pub fn describe(&self) -> String {
+        let condition = match (self.mode, self.threshold) {
+            (LockMode::Below, Some(threshold)) => format!("below {} ", threshold),
+            (LockMode::Above, Some(threshold)) => format!("above {} ", threshold),
+            _ => String::new(),
+        };
+        format!("{}every {}ms", condition, self.interval_ms)
+    }
+}
+
+/// 에이전트 잠금 테이블 항목
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockInfo {
+    pub id: u64,
+    pub address: String,
+    #[serde(rename = "type")]
+    pub memory_type: String,
+    pub value: Value,
+    pub mode: LockMode,
+    #[serde(default)]
+    pub threshold: Option<f64>,
+    pub interval_ms: u64,
+    /// 지금까지 값을 덮어쓴 횟수
+    pub writes: u64,
+    /// 쓰기 실패로 잠금이 멈춘 경우의 오류
+    #[serde(default)]
+    pub error: Option<String>,
+    /// 잠금 전 값 (addLock 응답에만 포함)
+    #[serde(default)]
+    pub original: Option<Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_lock_options_serialization() {
+        let options = LockOptions { mode: LockMode::Below, threshold: Some(50.0), interval_ms: 100 };
+        assert_eq!(serde_json::to_value(&options).unwrap(), json!({ "mode": "below", "threshold": 50.0, "intervalMs": 100 }));
+        assert_eq!(options.describe(), "below 50 every 100ms");
+        assert_eq!(LockOptions::default().describe(), "every 50ms");
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use frida_rust::ScriptHandler;
+use serde_json::Value;
+
+/// 에이전트 스크립트에서 온 메시지 종류
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageKind {
+    /// `send()` 페이로드
+    Send(Value),
+    /// 스크립트 예외
+    Error {
+        description: String,
+        stack: Option<String>,
+    },
+    /// `console.log` 등 로그 출력
+    Log {
+        level: String,
+        text: String,
+    },
+}
+
+/// 에이전트 스크립트 메시지 (보낸 스크립트 이름 포함)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptMessage {
+    pub script: String,
+    pub kind: MessageKind,
+}
+
+impl ScriptMessage {
+    /// Frida 메시지 JSON 해석 (`{"type": "send", "payload": ...}` 등)
+    pub fn parse(script: &str, raw: &str) -> Option<Self> {
+        let message: Value = serde_json::from_str(raw).ok()?;
+        let text = |key: &str| message.get(key).and_then(Value::as_str).map(str::to_string);
+
+        let kind = match message.get("type")?.as_str()? {
+            "send" => MessageKind::Send(message.get("payload").cloned().unwrap_or(Value::Null)),
+            "error" => MessageKind::Error {
+                description: text("description").unwrap_or_default(),
+                stack: text("stack"),
+            },
+            "log" => MessageKind::Log {
+                level: text("level").unwrap_or_else(|| "info".to_string()),
+                text: match message.get("payload") {
+                    Some(Value::String(s)) => s.clone(),
+                    Some(other) => other.to_string(),
+                    None => String::new(),
+                },
+            },
+            _ => return None,
+        };
+
+        Some(Self { script: script.to_string(), kind })
+    }
+
+    /// `send()` 페이로드의 `type` 필드 (예: "functionHook")
+    pub fn payload_type(&self) -> Option<&str> {
+        match &self.kind {
+            MessageKind::Send(payload) => payload.get("type").and_then(Value::as_str),
+            _ => None,
+        }
+    }
+}
+
+/// 스크립트 메시지 큐 (Frida 메시지 스레드와 공유)
+#[derive(Debug, Clone, Default)]
+pub struct MessageQueue {
+    messages: Arc<Mutex<VecDeque<ScriptMessage>>>,
+}
+
+impl MessageQueue {
+    /// 새 메시지 큐 생성
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 메시지 추가
+    pub fn push(&self, message: ScriptMessage) {
+        self.lock().push_back(message);
+    }
+
+    /// 쌓인 메시지를 모두 꺼냄
+    pub fn drain(&self) -> Vec<ScriptMessage> {
+        self.lock().drain(..).collect()
+    }
+
+    /// 대기 중인 메시지 수
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// 대기 중인 메시지가 없는지 여부
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<ScriptMessage>> {
+        // 핸들러 스레드가 패닉해도 큐는 계속 사용
+        self.messages.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// 스크립트 메시지를 이벤트 버스로 전달하는 Frida 메시지 핸들러
+pub struct MessageSink {
+    script: String,
+    sender: Sender<ScriptMessage>,
+}
+
+impl MessageSink {
+    pub fn new(script: &str, sender: Sender<ScriptMessage>) -> Self {
+        Self { script: script.to_string(), sender }
+    }
+}
+
+impl ScriptHandler for MessageSink {
+    fn on_message(&mut self, message: &str) {
+        match ScriptMessage::parse(&self.script, message) {
+            Some(parsed) => {
+                if self.sender.send(parsed).is_err() {
+                    log::debug!("Event bus closed, dropping message from '{}'", self.script);
+                }
+            },
+            None => log::debug!("Ignoring unknown message from '{}': {}", self.script, message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_messages() {
+        let send = ScriptMessage::parse("vlitz", r#"{"type":"send","payload":{"type":"functionHook","id":"hook_0x10"}}"#).unwrap();
+        assert_eq!(send.payload_type(), Some("functionHook"));
+        assert_eq!(send.kind, MessageKind::Send(json!({ "type": "functionHook", "id": "hook_0x10" })));
+
+        let error = ScriptMessage::parse("vlitz", r#"{"type":"error","description":"TypeError: x","stack":"at foo"}"#).unwrap();
+        assert_eq!(error.kind, MessageKind::Error { description: "TypeError: x".to_string(), stack: Some("at foo".to_string()) });
+
+        assert!(ScriptMessage::parse("vlitz", "not json").is_none());
+    }
+}
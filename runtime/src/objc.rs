@@ -0,0 +1,278 @@
+use serde::{Serialize, Deserialize};
+use vlitz_shared::{VlitzError, VlitzResult, VzData};
+
+/// 에이전트가 반환하는 ObjC 메서드 원시 정보
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ObjcMethodInfo {
+    /// `-`/`+` 접두사가 붙은 셀렉터 (예: "- initWithFrame:")
+    pub selector: String,
+    /// ObjC 타입 인코딩 (예: "@40@0:8{CGRect={CGPoint=dd}{CGSize=dd}}16")
+    pub types: String,
+    /// 메서드를 선언한 클래스 (상속 메서드 조회 시)
+    #[serde(rename = "className", default, skip_serializing_if = "Option::is_none")]
+    pub class_name: Option<String>,
+}
+
+impl ObjcMethodInfo {
+    /// 인스턴스 메서드 여부
+    pub fn is_instance_method(&self) -> bool {
+        self.selector.starts_with('-')
+    }
+
+    /// 접두사를 제외한 셀렉터 이름
+    pub fn selector_name(&self) -> &str {
+        self.selector.trim_start_matches(['-', '+']).trim_start()
+    }
+
+    /// 타입 인코딩을 디코딩하여 VzMethod로 변환
+    ///
+    /// 선언 클래스 정보가 있으면 `class_name` 대신 사용한다.
+    pub fn to_vzdata(&self, class_name: &str) -> VlitzResult<VzData> {
+        let (ret, args) = decode_method_types(&self.types)?;
+        let class_name = self.class_name.as_deref().unwrap_or(class_name);
+        Ok(VzData::new_method(class_name.to_string(), self.selector.clone(), args, ret))
+    }
+}
+
+/// ObjC 호출 인자/반환값
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ObjcValue {
+    /// nil
+    Nil,
+    /// ObjC.Object 핸들
+    Object {
+        handle: String,
+        #[serde(rename = "className", default, skip_serializing_if = "Option::is_none")]
+        class_name: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+    /// NSString으로 변환되는 문자열
+    String { value: String },
+    /// 숫자 (정수/실수)
+    Number { value: f64 },
+    /// BOOL
+    Bool { value: bool },
+    /// 원시 포인터
+    Pointer { value: String },
+}
+
+impl ObjcValue {
+    /// 주소로 ObjC 객체 인자 생성
+    pub fn object(handle: u64) -> Self {
+        ObjcValue::Object {
+            handle: format!("0x{:x}", handle),
+            class_name: None,
+            description: None,
+        }
+    }
+
+    /// 명령어 인자 문자열에서 ObjC 값 추론
+    pub fn parse(value: &str) -> Self {
+        if value == "nil" || value == "NULL" {
+            return ObjcValue::Nil;
+        }
+        if let Some(hex) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+            if let Ok(handle) = u64::from_str_radix(hex, 16) {
+                return ObjcValue::object(handle);
+            }
+        }
+        if let Ok(b) = value.parse::<bool>() {
+            return ObjcValue::Bool { value: b };
+        }
+        if let Ok(n) = value.parse::<f64>() {
+            return ObjcValue::Number { value: n };
+        }
+        ObjcValue::String { value: value.to_string() }
+    }
+}
+
+/// 메서드 타입 인코딩을 (반환 타입, 인자 타입 목록)으로 디코딩
+///
+/// 처음 두 인자(self, _cmd)는 결과에서 제외된다.
+pub fn decode_method_types(types: &str) -> VlitzResult<(String, Vec<String>)> {
+    let mut decoder = TypeDecoder { bytes: types.as_bytes(), pos: 0 };
+    let mut decoded = Vec::new();
+
+    while decoder.pos < decoder.bytes.len() {
+        decoded.push(decoder.next_type()?);
+        decoder.skip_offset();
+    }
+
+    if decoded.len() < 3 {
+        return Err(VlitzError::TypeConversion(format!("Invalid method type encoding: {}", types)));
+    }
+
+    let ret = decoded.remove(0);
+    Ok((ret, decoded.split_off(2)))
+}
+
+/// ObjC 타입 인코딩 디코더
+struct TypeDecoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl TypeDecoder<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn error(&self) -> VlitzError {
+        VlitzError::TypeConversion(format!(
+            "Invalid type encoding at {}: {}",
+            self.pos,
+            String::from_utf8_lossy(self.bytes)
+        ))
+    }
+
+    /// 스택 오프셋 숫자 건너뛰기
+    fn skip_offset(&mut self) {
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+    }
+
+    fn read_number(&mut self) -> usize {
+        let start = self.pos;
+        self.skip_offset();
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// 닫는 문자까지의 이름 읽기 (`{CGRect=...}`의 CGRect)
+    fn read_aggregate(&mut self, close: u8) -> VlitzResult<String> {
+        let start = self.pos;
+        let mut name_end = None;
+        let mut depth = 0;
+
+        while let Some(c) = self.peek() {
+            match c {
+                b'{' | b'(' | b'[' => depth += 1,
+                b'}' | b')' | b']' if depth > 0 => depth -= 1,
+                c if c == close && depth == 0 => {
+                    let end = name_end.unwrap_or(self.pos);
+                    let name = String::from_utf8_lossy(&self.bytes[start..end]).to_string();
+                    self.pos += 1;
+                    return Ok(if name.is_empty() || name == "?" { "struct".to_string() } else { name });
+                },
+                b'=' if depth == 0 && name_end.is_none() => name_end = Some(self.pos),
+                _ => {},
+            }
+            self.pos += 1;
+        }
+
+        Err(self.error())
+    }
+
+    fn next_type(&mut self) -> VlitzResult<String> {
+        // 타입 한정자 (const, in, out 등) 무시
+        while matches!(self.peek(), Some(b'r' | b'n' | b'N' | b'o' | b'O' | b'R' | b'V' | b'A' | b'j')) {
+            self.pos += 1;
+        }
+
+        let c = self.peek().ok_or_else(|| self.error())?;
+        self.pos += 1;
+
+        let name = match c {
+            b'c' => "char".to_string(),
+            b'i' => "int".to_string(),
+            b's' => "short".to_string(),
+            b'l' => "long".to_string(),
+            b'q' => "long long".to_string(),
+            b'C' => "unsigned char".to_string(),
+            b'I' => "unsigned int".to_string(),
+            b'S' => "unsigned short".to_string(),
+            b'L' => "unsigned long".to_string(),
+            b'Q' => "unsigned long long".to_string(),
+            b'f' => "float".to_string(),
+            b'd' => "double".to_string(),
+            b'B' => "bool".to_string(),
+            b'v' => "void".to_string(),
+            b'*' => "char *".to_string(),
+            b'#' => "Class".to_string(),
+            b':' => "SEL".to_string(),
+            b'?' => "unknown".to_string(),
+            b'@' => match self.peek() {
+                Some(b'?') => {
+                    self.pos += 1;
+                    "block".to_string()
+                },
+                Some(b'"') => {
+                    self.pos += 1;
+                    let start = self.pos;
+                    while self.peek().is_some_and(|c| c != b'"') {
+                        self.pos += 1;
+                    }
+                    let class_name = String::from_utf8_lossy(&self.bytes[start..self.pos]).to_string();
+                    self.pos += 1;
+                    format!("{} *", class_name)
+                },
+                _ => "id".to_string(),
+            },
+            b'^' => format!("{} *", self.next_type()?),
+            b'b' => format!("bitfield:{}", self.read_number()),
+            b'[' => {
+                let count = self.read_number();
+                let inner = self.next_type()?;
+                if self.peek() != Some(b']') {
+                    return Err(self.error());
+                }
+                self.pos += 1;
+                format!("{}[{}]", inner, count)
+            },
+            b'{' => self.read_aggregate(b'}')?,
+            b'(' => self.read_aggregate(b')')?,
+            _ => return Err(self.error()),
+        };
+
+        Ok(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_primitive_types() {
+        let (ret, args) = decode_method_types("v24@0:8q16").unwrap();
+        assert_eq!(ret, "void");
+        assert_eq!(args, vec!["long long"]);
+
+        let (ret, args) = decode_method_types("B32@0:8d16r*24").unwrap();
+        assert_eq!(ret, "bool");
+        assert_eq!(args, vec!["double", "char *"]);
+    }
+
+    #[test]
+    fn test_decode_objects_and_structs() {
+        let (ret, args) = decode_method_types("@48@0:8{CGRect={CGPoint=dd}{CGSize=dd}}16").unwrap();
+        assert_eq!(ret, "id");
+        assert_eq!(args, vec!["CGRect"]);
+
+        let (ret, args) = decode_method_types("@\"NSString\"32@0:8@?16^@24").unwrap();
+        assert_eq!(ret, "NSString *");
+        assert_eq!(args, vec!["block", "id *"]);
+
+        let (_, args) = decode_method_types("v28@0:8[4i]16").unwrap();
+        assert_eq!(args, vec!["int[4]"]);
+    }
+
+    #[test]
+    fn test_decode_invalid_encoding() {
+        assert!(decode_method_types("v16").is_err());
+        assert!(decode_method_types("v24@0:8{CGRect=dd").is_err());
+    }
+
+    #[test]
+    fn test_objc_value_parse() {
+        assert_eq!(ObjcValue::parse("nil"), ObjcValue::Nil);
+        assert_eq!(ObjcValue::parse("0x1000"), ObjcValue::object(0x1000));
+        assert_eq!(ObjcValue::parse("42"), ObjcValue::Number { value: 42.0 });
+        assert_eq!(ObjcValue::parse("hello"), ObjcValue::String { value: "hello".to_string() });
+    }
+}
@@ -0,0 +1,170 @@
+use serde::{Deserialize, Deserializer};
+use vlitz_shared::VzData;
+use vlitz_shared::utils::hex_to_addr;
+
+/// "0x..." 문자열 또는 숫자 주소 역직렬화
+pub(crate) fn deserialize_address<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawAddress {
+        Number(u64),
+        Text(String),
+    }
+
+    match RawAddress::deserialize(deserializer)? {
+        RawAddress::Number(n) => Ok(n),
+        RawAddress::Text(s) => hex_to_addr(&s).map_err(serde::de::Error::custom),
+    }
+}
+
+/// 주소가 없을 수 있는 필드 역직렬화
+fn deserialize_optional_address<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    #[derive(Deserialize)]
+    struct Wrapper(#[serde(deserialize_with = "deserialize_address")] u64);
+
+    Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|w| w.0))
+}
+
+/// 모듈 정보
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModuleInfo {
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_address")]
+    pub base: u64,
+    pub size: usize,
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+impl ModuleInfo {
+    /// VzModule로 변환
+    pub fn to_vzdata(&self) -> VzData {
+        VzData::new_module(self.name.clone(), self.base, self.size)
+    }
+}
+
+/// 익스포트 정보 (type: function | variable)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExportInfo {
+    #[serde(rename = "type")]
+    pub export_type: String,
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_address")]
+    pub address: u64,
+}
+
+impl ExportInfo {
+    /// 익스포트 타입에 따라 VzFunction 또는 VzVariable로 변환
+    pub fn to_vzdata(&self) -> VzData {
+        match self.export_type.as_str() {
+            "variable" => VzData::new_variable(self.name.clone(), self.address),
+            _ => VzData::new_function(self.name.clone(), self.address),
+        }
+    }
+}
+
+/// 임포트 정보 (해석되지 않은 임포트는 주소가 없음)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImportInfo {
+    #[serde(rename = "type", default)]
+    pub import_type: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub module: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_address")]
+    pub address: Option<u64>,
+}
+
+impl ImportInfo {
+    /// 주소가 해석된 임포트만 변환 (이름은 module!name 형식)
+    pub fn to_vzdata(&self) -> Option<VzData> {
+        let address = self.address?;
+        let name = match &self.module {
+            Some(module) => format!("{}!{}", module, self.name),
+            None => self.name.clone(),
+        };
+
+        Some(match self.import_type.as_deref() {
+            Some("variable") => VzData::new_variable(name, address),
+            _ => VzData::new_function(name, address),
+        })
+    }
+}
+
+/// 심볼 정보
+#[derive(Debug, Clone, Deserialize)]
+pub struct SymbolInfo {
+    #[serde(rename = "type")]
+    pub symbol_type: String,
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_address")]
+    pub address: u64,
+}
+
+impl SymbolInfo {
+    /// 함수/데이터 심볼만 변환 (section, file 등은 제외)
+    pub fn to_vzdata(&self) -> Option<VzData> {
+        if self.address == 0 || self.name.is_empty() {
+            return None;
+        }
+
+        match self.symbol_type.as_str() {
+            "function" => Some(VzData::new_function(self.name.clone(), self.address)),
+            "object" | "common" | "tls" => Some(VzData::new_variable(self.name.clone(), self.address)),
+            _ => None,
+        }
+    }
+}
+
+/// 메모리 범위 정보
+#[derive(Debug, Clone, Deserialize)]
+pub struct RangeInfo {
+    #[serde(deserialize_with = "deserialize_address")]
+    pub base: u64,
+    pub size: usize,
+    pub protection: String,
+    #[serde(default)]
+    pub file: Option<String>,
+}
+
+impl RangeInfo {
+    /// VzRange로 변환
+    pub fn to_vzdata(&self) -> VzData {
+        VzData::new_range(self.base, self.size, self.protection.clone(), self.file.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use vlitz_shared::VzDataType;
+
+    #[test]
+    fn test_export_type_mapping() {
+        let function: ExportInfo = serde_json::from_value(json!({ "type": "function", "name": "open", "address": "0x7f001000" })).unwrap();
+        let variable: ExportInfo = serde_json::from_value(json!({ "type": "variable", "name": "environ", "address": "0x7f002000" })).unwrap();
+
+        assert_eq!(function.to_vzdata().data_type, VzDataType::Function);
+        assert_eq!(variable.to_vzdata().as_variable().unwrap().address, 0x7f002000);
+    }
+
+    #[test]
+    fn test_unresolved_import_skipped() {
+        let resolved: ImportInfo = serde_json::from_value(json!({ "type": "function", "name": "malloc", "module": "libc.so", "address": "0x1000" })).unwrap();
+        let unresolved: ImportInfo = serde_json::from_value(json!({ "name": "lazy", "address": null })).unwrap();
+
+        assert_eq!(resolved.to_vzdata().unwrap().get_name(), Some("libc.so!malloc"));
+        assert!(unresolved.to_vzdata().is_none());
+    }
+
+    #[test]
+    fn test_range_with_file() {
+        let range: RangeInfo = serde_json::from_value(json!({ "base": "0x400000", "size": 4096, "protection": "r-x", "file": "/system/lib/libc.so" })).unwrap();
+        let data = range.to_vzdata();
+        let range = data.as_range().unwrap();
+
+        assert_eq!(range.address, 0x400000);
+        assert_eq!(range.file.as_deref(), Some("/system/lib/libc.so"));
+    }
+}
@@ -1,6 +1,12 @@
 use std::collections::HashMap;
 use frida_rust::{Session, Script, ScriptOption};
 use vlitz_shared::{VlitzError, VlitzResult};
+use crate::script::ScriptUtils;
+use crate::message::{MessageQueue, MessageSink, ScriptMessage};
+use crate::event::EventBus;
+
+/// 기본 RPC 에이전트 스크립트 이름
+pub const BASE_SCRIPT_NAME: &str = "vlitz";
 
 /// VLITZ 세션 관리 구조체
 pub struct VlitzSession {
@@ -8,6 +14,8 @@ pub struct VlitzSession {
     session: Session,
     /// 현재 로드된 스크립트 맵 (이름 -> 스크립트)
     scripts: HashMap<String, Script>,
+    /// 모든 스크립트의 `send()`/오류 메시지를 받는 이벤트 버스
+    events: EventBus,
 }
 
 impl VlitzSession {
@@ -16,6 +24,7 @@ impl VlitzSession {
         Self {
             session,
             scripts: HashMap::new(),
+            events: EventBus::new(),
         }
     }
 
@@ -25,9 +34,13 @@ impl VlitzSession {
             return Err(VlitzError::ScriptExec(format!("Script '{}' already exists", name)));
         }
 
-        let script = self.session.create_script(source, ScriptOption::new())
+        let mut script = self.session.create_script(source, ScriptOption::new())
             .map_err(|e| VlitzError::ScriptExec(format!("Failed to create script: {}", e)))?;
 
+        // 로드 중에 보낸 메시지도 받도록 로드 전에 핸들러 등록
+        script.handle_message(MessageSink::new(name, self.events.sender()))
+            .map_err(|e| VlitzError::ScriptExec(format!("Failed to register message handler: {}", e)))?;
+
         script.load()
             .map_err(|e| VlitzError::ScriptExec(format!("Failed to load script: {}", e)))?;
 
@@ -36,6 +49,12 @@ impl VlitzSession {
         Ok(self.scripts.get(name).unwrap())
     }
 
+    /// 기본 RPC 에이전트 스크립트 로드
+    pub fn load_base_script(&mut self) -> VlitzResult<()> {
+        self.create_script(BASE_SCRIPT_NAME, ScriptUtils::get_base_script())?;
+        Ok(())
+    }
+
     /// 스크립트 언로드
     pub fn unload_script(&mut self, name: &str) -> VlitzResult<()> {
         if let Some(script) = self.scripts.remove(name) {
@@ -57,6 +76,21 @@ impl VlitzSession {
         self.scripts.keys().map(|k| k.as_str()).collect()
     }
 
+    /// 스크립트 메시지 큐 (다른 스레드에서 메시지를 꺼낼 때 사용)
+    pub fn message_queue(&self) -> MessageQueue {
+        self.events.message_queue()
+    }
+
+    /// 쌓인 스크립트 메시지를 모두 꺼냄
+    pub fn drain_messages(&self) -> Vec<ScriptMessage> {
+        self.events.message_queue().drain()
+    }
+
+    /// 이벤트 버스 (비동기 출력 구독, 이벤트 로그 조회)
+    pub fn events(&self) -> &EventBus {
+        &self.events
+    }
+
     /// 스크립트에서 RPC 호출
     pub fn call_rpc<T: serde::de::DeserializeOwned>(&self, script_name: &str, export_name: &str, args: &[serde_json::Value]) -> VlitzResult<T> {
         if let Some(script) = self.scripts.get(script_name) {
@@ -69,7 +103,12 @@ impl VlitzSession {
     
     /// 세션 분리 (Detach)
     pub fn detach(&self) -> VlitzResult<()> {
-        // 모든 스크립트 언로드 (실제 구현에서는 이 로직이 추가될 수 있음)
+        // 에이전트 잠금 테이블을 먼저 비워 분리 후에도 값이 고정되지 않도록 함
+        if self.scripts.contains_key(BASE_SCRIPT_NAME) {
+            if let Err(e) = self.call_rpc::<usize>(BASE_SCRIPT_NAME, "removeAllLocks", &[]) {
+                log::warn!("Failed to release memory locks: {}", e);
+            }
+        }
         
         self.session.detach()
             .map_err(|e| VlitzError::Frida(format!("Failed to detach session: {}", e)))
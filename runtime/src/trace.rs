@@ -0,0 +1,46 @@
+use serde::{Serialize, Deserialize};
+
+/// 추적 시 기록할 기본 인자 개수
+pub const DEFAULT_TRACE_ARG_COUNT: usize = 4;
+
+/// addTrace/traceModule 응답
+#[derive(Debug, Clone, Deserialize)]
+pub struct TraceHandle {
+    pub id: u64,
+    pub address: String,
+    pub name: String,
+}
+
+/// 추적 이벤트 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceEventKind {
+    Enter,
+    Leave,
+}
+
+/// 함수 진입/반환 이벤트
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceEvent {
+    pub trace_id: u64,
+    #[serde(rename = "event")]
+    pub kind: TraceEventKind,
+    pub thread_id: u64,
+    /// 스레드별 중첩 깊이 (진입 시점 기준)
+    pub depth: usize,
+    /// 밀리초 단위 타임스탬프
+    pub timestamp: f64,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub retval: Option<String>,
+}
+
+/// drainTraceEvents 응답
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TraceBatch {
+    pub events: Vec<TraceEvent>,
+    /// 버퍼 상한 초과로 버려진 이벤트 수
+    pub dropped: usize,
+}
@@ -0,0 +1,53 @@
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+/// 워치 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchMode {
+    /// 하드웨어 워치포인트 (정확한 주소, 스레드별 디버그 레지스터)
+    Hardware,
+    /// MemoryAccessMonitor (페이지 단위, 히트마다 재활성화)
+    Access,
+}
+
+/// addWatch 응답
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchHandle {
+    pub id: u64,
+    pub mode: WatchMode,
+    /// 워치 시작 시점의 값
+    pub value: Value,
+}
+
+/// 워치 히트 이벤트
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchEvent {
+    pub watch_id: u64,
+    pub address: String,
+    /// 값을 쓴 명령어 주소
+    pub from: String,
+    /// 명령어 주소의 심볼 (module!symbol+offset)
+    pub symbol: String,
+    pub thread_id: u64,
+    #[serde(default)]
+    pub backtrace: Vec<String>,
+    pub old_value: Value,
+    pub new_value: Value,
+    /// 값이 실제로 바뀌었는지 여부 (같은 값 쓰기는 false)
+    pub changed: bool,
+    pub timestamp: u64,
+}
+
+impl WatchEvent {
+    /// 이벤트 요약 문자열
+    pub fn summary(&self) -> String {
+        let change = if self.changed {
+            format!("{} -> {}", self.old_value, self.new_value)
+        } else {
+            format!("{} (unchanged)", self.new_value)
+        };
+        format!("{}: {} by {} ({}) [tid {}]", self.address, change, self.from, self.symbol, self.thread_id)
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt;
+
+/// 소스 위치 (1부터 시작하는 줄/열 번호)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Pos {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Pos {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// 파싱된 스크립트
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Script {
+    pub body: Vec<Stmt>,
+}
+
+/// 문장 (시작 위치 포함)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stmt {
+    pub pos: Pos,
+    /// 원본 줄 (오류 메시지용)
+    pub source: String,
+    pub kind: StmtKind,
+}
+
+/// 문장 종류
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtKind {
+    /// vlitz 명령어 또는 프로시저 호출
+    Command(CommandLine),
+    /// `$name = <식>` 또는 `$name = <명령어>` (명령어 결과 저장)
+    Assign { name: String, value: AssignValue },
+    /// `if` / `elif` / `else` / `end`
+    If { branches: Vec<(Expr, Vec<Stmt>)>, otherwise: Option<Vec<Stmt>> },
+    /// `while <식>` ... `end`
+    While { condition: Expr, body: Vec<Stmt> },
+    /// `for each $x in <식>` ... `end`
+    ForEach { var: String, iterable: Expr, body: Vec<Stmt> },
+    /// `proc name $a $b` ... `end`
+    Proc { name: String, params: Vec<String>, body: Vec<Stmt> },
+    /// `return [식]`
+    Return(Option<Expr>),
+    Break,
+    Continue,
+    /// `print <텍스트>` (변수 치환 후 출력)
+    Print(Template),
+    /// `on hook|watch|timer ...` ... `end` (이벤트 핸들러 등록)
+    On { trigger: Trigger, body: Vec<Stmt> },
+    /// `wait <밀리초>` (대기하면서 이벤트 핸들러 실행)
+    Wait(Expr),
+}
+
+/// 이벤트 핸들러 조건
+#[derive(Debug, Clone, PartialEq)]
+pub enum Trigger {
+    /// `on hook <주소> [enter|leave]`
+    Hook { target: Expr, phase: Option<HookPhase> },
+    /// `on watch <주소> [타입]`
+    Watch { target: Expr, memory_type: Option<String> },
+    /// `on timer <밀리초>`
+    Timer { interval: Expr },
+}
+
+/// 후크 이벤트 시점
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookPhase {
+    Enter,
+    Leave,
+}
+
+impl HookPhase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookPhase::Enter => "enter",
+            HookPhase::Leave => "leave",
+        }
+    }
+}
+
+/// 대입문의 오른쪽
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssignValue {
+    Expr(Expr),
+    Command(CommandLine),
+}
+
+/// 명령어 줄
+///
+/// 실행 시 `text`의 변수를 치환해 `Command::parse`로 넘긴다.
+/// 첫 단어가 정의된 프로시저 이름이면 `args`를 평가해 프로시저를 호출한다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandLine {
+    pub name: String,
+    pub text: Template,
+    pub args: Vec<Expr>,
+}
+
+/// 변수 치환이 들어간 텍스트 (`$name`, `${식}`)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Template(pub Vec<TextPart>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextPart {
+    Literal(String),
+    Expr(Expr),
+}
+
+/// 식
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    /// 16진수 리터럴은 주소로 취급
+    Address(u64),
+    Float(f64),
+    /// 문자열 리터럴 (변수 치환 가능)
+    Text(Template),
+    Var(String),
+    /// 현재 선택된 항목 (`sel`)
+    Selected,
+    /// 저장소 선택자 (`lib:all`, `log:0-3`, `lib:@main`)
+    Selector(String),
+    List(Vec<Expr>),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Index(Box<Expr>, Box<Expr>),
+    /// 내장 함수 호출 (`len($x)`)
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+        }
+    }
+}
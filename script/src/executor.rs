@@ -1,38 +1,1175 @@
-use crate::parser::ScriptParser;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use vlitz_core::{Command, CommandExecutor, CommandResult, CommandSource, CommandSpec, CommandType, Pipeline};
+use vlitz_preset::{parse_overrides, OptionValue};
+use vlitz_runtime::MessageKind;
+use vlitz_shared::{Selector, SelectorSource, SelectorTerm, VzData};
+use vlitz_shared::utils::addr_to_hex;
+use crate::ast::*;
+use crate::parser::{ParseError, ScriptParser};
+use crate::value::{DataValue, Value};
+
+/// 스크립트 안에서 run으로 다른 스크립트를 부를 수 있는 최대 깊이
+const MAX_RUN_DEPTH: usize = 8;
+
+/// 프로시저 호출 최대 깊이
+const MAX_CALL_DEPTH: usize = 64;
+
+/// wait 중 이벤트를 확인하는 간격
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// 명령어 실패 시 동작
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnError {
+    /// 첫 오류에서 중단
+    Stop,
+    /// 오류를 기록하고 다음 줄 계속 실행
+    Continue,
+}
+
+/// 실행된 스크립트 줄 (출력 콜백에 전달)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptLine {
+    /// 1부터 시작하는 줄 번호
+    pub number: usize,
+    /// 변수 치환 후의 텍스트
+    pub text: String,
+}
+
+/// 스크립트 실행 오류 (파일, 위치 포함)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptError {
+    /// 오류가 난 스크립트 파일 (문자열로 실행한 경우 None)
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    /// 파싱/평가 오류의 열 번호 (명령어 실패는 None)
+    pub column: Option<usize>,
+    pub command: String,
+    pub message: String,
+}
+
+impl ScriptError {
+    fn parse(file: Option<&Path>, error: ParseError) -> Self {
+        ScriptError {
+            file: file.map(Path::to_path_buf),
+            line: error.pos.line,
+            column: Some(error.pos.column),
+            command: String::new(),
+            message: format!("Parse error: {}", error.message),
+        }
+    }
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}", file.display(), self.line)?,
+            None => write!(f, "line {}", self.line)?,
+        }
+        if let Some(column) = self.column {
+            write!(f, ":{}", column)?;
+        }
+        if !self.command.is_empty() {
+            write!(f, ": {}", self.command)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
 
 /// 스크립트 실행 결과
-#[derive(Debug)]
-pub enum ExecutionResult {
-    Success,
-    Error(String),
+#[derive(Debug, Default)]
+pub struct ExecutionReport {
+    /// 실행한 명령어 수
+    pub executed: usize,
+    pub errors: Vec<ScriptError>,
+    /// 오류로 중단되었는지 여부
+    pub stopped: bool,
+    /// exit 명령어로 종료 요청되었는지 여부
+    pub exit_requested: bool,
+}
+
+impl ExecutionReport {
+    /// 오류 없이 끝났는지 여부
+    pub fn is_success(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// 결과 요약 문자열
+    pub fn summary(&self) -> String {
+        let mut summary = format!("Executed {} commands", self.executed);
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(", {} errors", self.errors.len()));
+        }
+        if self.stopped {
+            summary.push_str(" (stopped on error)");
+        }
+        summary
+    }
+}
+
+/// 등록된 이벤트 핸들러 정보 (`on list` 출력용)
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandlerInfo {
+    pub id: u64,
+    /// 조건 설명 (예: "hook 0x7f001000 enter")
+    pub description: String,
+    /// 핸들러 실행 횟수
+    pub hits: u64,
+    /// 정의 위치 (파일:줄)
+    pub location: String,
+}
+
+impl HandlerInfo {
+    /// 목록 출력용 문자열
+    pub fn format(&self) -> String {
+        format!("[{}] {} ({} hits) at {}", self.id, self.description, self.hits, self.location)
+    }
 }
 
 /// 스크립트 실행자 구조체
+///
+/// 스크립트를 AST로 파싱한 뒤 변수, 제어문, 프로시저를 해석하고,
+/// 명령어 줄은 변수를 치환해 `Command::parse`로 `CommandExecutor`에서 실행한다.
+/// 명령어 결과는 `output` 콜백으로 전달되어 REPL과 같은 방식으로 출력할 수 있다.
+///
+/// 전역 변수, 프로시저, `on` 핸들러는 실행 사이에 유지되므로
+/// 스크립트가 끝난 뒤에도 `dispatch_events`로 핸들러를 계속 실행할 수 있다.
 pub struct ScriptExecutor {
     parser: ScriptParser,
+    on_error: OnError,
+    state: ScriptState,
 }
 
 impl ScriptExecutor {
-    /// 새 실행자 인스턴스 생성
+    /// 새 실행자 인스턴스 생성 (기본은 첫 오류에서 중단)
     pub fn new() -> Self {
         ScriptExecutor {
             parser: ScriptParser::new(),
+            on_error: OnError::Stop,
+            state: ScriptState::default(),
         }
     }
-    
+
+    /// 오류 처리 방식 설정
+    pub fn on_error(mut self, on_error: OnError) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// 오류 처리 방식 변경 (REPL에서 실행마다 지정)
+    pub fn set_on_error(&mut self, on_error: OnError) {
+        self.on_error = on_error;
+    }
+
     /// 스크립트 실행
-    pub fn execute(&self, script_content: &str) -> ExecutionResult {
-        // 스크립트 파싱
-        let commands = match self.parser.parse(script_content) {
-            Ok(cmds) => cmds,
-            Err(err) => return ExecutionResult::Error(format!("파싱 오류: {}", err)),
+    pub fn execute<F>(&mut self, executor: &mut CommandExecutor, script_content: &str, mut output: F) -> ExecutionReport
+    where
+        F: FnMut(&ScriptLine, &CommandResult),
+    {
+        let mut interpreter = Interpreter::new(&self.parser, self.on_error, &mut self.state, executor, &mut output);
+        interpreter.run_content(script_content, None);
+        interpreter.report
+    }
+
+    /// 스크립트 파일 실행
+    pub fn execute_file<F>(&mut self, executor: &mut CommandExecutor, path: &Path, mut output: F) -> ExecutionReport
+    where
+        F: FnMut(&ScriptLine, &CommandResult),
+    {
+        let mut interpreter = Interpreter::new(&self.parser, self.on_error, &mut self.state, executor, &mut output);
+        interpreter.run_path(path, None);
+        interpreter.report
+    }
+
+    /// 에이전트 메시지와 만료된 타이머로 이벤트 핸들러 실행
+    pub fn dispatch_events<F>(&mut self, executor: &mut CommandExecutor, mut output: F) -> ExecutionReport
+    where
+        F: FnMut(&ScriptLine, &CommandResult),
+    {
+        let mut interpreter = Interpreter::new(&self.parser, self.on_error, &mut self.state, executor, &mut output);
+        interpreter.dispatch();
+        interpreter.report
+    }
+
+    /// 등록된 이벤트 핸들러 목록
+    pub fn handlers(&self) -> Vec<HandlerInfo> {
+        self.state.handlers.iter().map(Handler::info).collect()
+    }
+
+    /// 스크립트 실행기를 거쳐야 하는 명령어인지 여부
+    /// (명령어 스크립트 프리셋의 `preset apply/revert`, 스크립트에서 정의한 프로시저)
+    pub fn handles(executor: &CommandExecutor, command: &Command) -> bool {
+        command_preset(executor, command).is_some()
+            || executor.command_spec(command).is_some_and(|spec| spec.command_type == CommandType::Procedure)
+    }
+
+    /// `on list` / `on remove` 명령어 처리
+    pub fn execute_handler_command(&mut self, executor: &CommandExecutor, command: &Command) -> CommandResult {
+        self.state.handler_command(executor, command)
+    }
+}
+
+/// 실행 사이에 유지되는 상태
+#[derive(Default)]
+struct ScriptState {
+    globals: HashMap<String, Value>,
+    procs: HashMap<String, Rc<ProcDef>>,
+    handlers: Vec<Handler>,
+    next_handler_id: u64,
+}
+
+impl ScriptState {
+    fn handler_command(&mut self, executor: &CommandExecutor, command: &Command) -> CommandResult {
+        match command.get_type() {
+            CommandType::OnList => {
+                if self.handlers.is_empty() {
+                    return CommandResult::Success("No event handlers".to_string());
+                }
+                let lines: Vec<String> = self.handlers.iter().map(|handler| handler.info().format()).collect();
+                CommandResult::Success(lines.join("\n"))
+            },
+            CommandType::OnRemove => {
+                let ids: Vec<u64> = match command.get_raw_arg(0) {
+                    Some("all") => self.handlers.iter().map(|handler| handler.id).collect(),
+                    Some(id) => match id.parse() {
+                        Ok(id) if self.handlers.iter().any(|handler| handler.id == id) => vec![id],
+                        Ok(id) => return CommandResult::Error(format!("No event handler #{}", id)),
+                        Err(_) => return CommandResult::Error(format!("Invalid handler id: {}", id)),
+                    },
+                    None => return CommandResult::Error("Handler id or 'all' required".to_string()),
+                };
+
+                for id in &ids {
+                    if let Err(e) = self.remove_handler(executor, *id) {
+                        return CommandResult::Error(format!("Failed to remove handler #{}: {}", id, e));
+                    }
+                }
+                CommandResult::Success(format!("Removed {} event handlers", ids.len()))
+            },
+            _ => CommandResult::Error("Unknown handler command".to_string()),
+        }
+    }
+
+    /// 핸들러 제거 (같은 후크를 쓰는 핸들러가 없으면 후크도 해제)
+    fn remove_handler(&mut self, executor: &CommandExecutor, id: u64) -> Result<(), String> {
+        let Some(index) = self.handlers.iter().position(|handler| handler.id == id) else {
+            return Ok(());
+        };
+        let handler = self.handlers.remove(index);
+
+        if let ActiveTrigger::Hook { hook_id, .. } = &handler.trigger {
+            let shared = self.handlers.iter()
+                .any(|other| matches!(&other.trigger, ActiveTrigger::Hook { hook_id: other_id, .. } if other_id == hook_id));
+            if !shared {
+                executor.agent()
+                    .and_then(|agent| agent.unhook_function(hook_id))
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 등록된 이벤트 핸들러
+struct Handler {
+    id: u64,
+    trigger: ActiveTrigger,
+    body: Rc<Vec<Stmt>>,
+    file: Option<PathBuf>,
+    line: usize,
+    hits: u64,
+}
+
+/// 설치된 핸들러 조건
+enum ActiveTrigger {
+    Hook { hook_id: String, address: u64, phase: Option<HookPhase> },
+    /// 워치 자체는 mem list에 남으므로 주소로만 메시지를 구분
+    Watch { address: u64 },
+    Timer { interval: Duration, next_due: Instant },
+}
+
+impl Handler {
+    fn info(&self) -> HandlerInfo {
+        let description = match &self.trigger {
+            ActiveTrigger::Hook { address, phase, .. } => match phase {
+                Some(phase) => format!("hook {} {}", addr_to_hex(*address), phase.name()),
+                None => format!("hook {}", addr_to_hex(*address)),
+            },
+            ActiveTrigger::Watch { address } => format!("watch {}", addr_to_hex(*address)),
+            ActiveTrigger::Timer { interval, .. } => format!("timer every {}ms", interval.as_millis()),
+        };
+        let location = match &self.file {
+            Some(file) => format!("{}:{}", file.display(), self.line),
+            None => format!("line {}", self.line),
+        };
+        HandlerInfo { id: self.id, description, hits: self.hits, location }
+    }
+
+    /// `send()` 페이로드가 이 핸들러 대상인지 여부
+    fn matches(&self, payload: &serde_json::Value) -> bool {
+        let field = |key: &str| payload.get(key).and_then(serde_json::Value::as_str);
+
+        match &self.trigger {
+            ActiveTrigger::Hook { hook_id, phase, .. } => {
+                field("type") == Some("functionHook")
+                    && field("id") == Some(hook_id.as_str())
+                    && phase.is_none_or(|phase| field("event") == Some(phase.name()))
+            },
+            ActiveTrigger::Watch { address } => {
+                field("type") == Some("memoryWatch")
+                    && field("address").and_then(|a| vlitz_shared::utils::hex_to_addr(a).ok()) == Some(*address)
+            },
+            ActiveTrigger::Timer { .. } => false,
+        }
+    }
+}
+
+/// 정의된 프로시저
+struct ProcDef {
+    params: Vec<String>,
+    body: Vec<Stmt>,
+    /// 정의된 스크립트 파일 (오류 위치용)
+    file: Option<PathBuf>,
+}
+
+/// 문장 실행 후 흐름
+enum Flow {
+    Normal,
+    Break,
+    Continue,
+    Return(Box<Value>),
+    /// 실행 중단 (오류 또는 exit)
+    Halt,
+}
+
+/// 식 평가 중단 사유
+enum Interrupt {
+    /// 평가 오류 (현재 문장 위치로 기록)
+    Error(String),
+    /// 이미 기록된 오류/exit로 실행 중단
+    Halt,
+}
+
+impl From<String> for Interrupt {
+    fn from(message: String) -> Self {
+        Interrupt::Error(message)
+    }
+}
+
+type Eval<T> = Result<T, Interrupt>;
+
+/// AST 해석기 (변수, 프로시저 상태 보관)
+struct Interpreter<'a> {
+    parser: &'a ScriptParser,
+    on_error: OnError,
+    state: &'a mut ScriptState,
+    executor: &'a mut CommandExecutor,
+    output: &'a mut dyn FnMut(&ScriptLine, &CommandResult),
+    report: ExecutionReport,
+    /// 프로시저/핸들러 호출별 지역 변수
+    frames: Vec<HashMap<String, Value>>,
+    /// 현재 실행 중인 스크립트 파일
+    file: Option<PathBuf>,
+    run_depth: usize,
+    /// 핸들러 실행 중 여부 (핸들러 안의 wait가 다시 이벤트를 처리하지 않도록)
+    dispatching: bool,
+}
+
+impl<'a> Interpreter<'a> {
+    fn new(
+        parser: &'a ScriptParser,
+        on_error: OnError,
+        state: &'a mut ScriptState,
+        executor: &'a mut CommandExecutor,
+        output: &'a mut dyn FnMut(&ScriptLine, &CommandResult),
+    ) -> Self {
+        Interpreter {
+            parser,
+            on_error,
+            state,
+            executor,
+            output,
+            report: ExecutionReport::default(),
+            frames: Vec::new(),
+            file: None,
+            run_depth: 0,
+            dispatching: false,
+        }
+    }
+
+    fn run_path(&mut self, path: &Path, from: Option<&Stmt>) -> Flow {
+        match std::fs::read_to_string(path) {
+            Ok(content) => self.run_content(&content, Some(path)),
+            Err(e) => {
+                self.report.errors.push(ScriptError {
+                    file: Some(path.to_path_buf()),
+                    line: from.map_or(0, |stmt| stmt.pos.line),
+                    column: None,
+                    command: "run".to_string(),
+                    message: format!("Failed to read script: {}", e),
+                });
+                self.report.stopped = true;
+                Flow::Halt
+            },
+        }
+    }
+
+    /// 스크립트 내용 파싱 후 실행 (현재 변수/프로시저 공유)
+    fn run_content(&mut self, content: &str, file: Option<&Path>) -> Flow {
+        let script = match self.parser.parse(content) {
+            Ok(script) => script,
+            Err(e) => {
+                self.report.errors.push(ScriptError::parse(file, e));
+                self.report.stopped = true;
+                return Flow::Halt;
+            },
+        };
+
+        let parent = std::mem::replace(&mut self.file, file.map(Path::to_path_buf));
+        let flow = self.block(&script.body);
+        self.file = parent;
+        flow
+    }
+
+    fn block(&mut self, body: &[Stmt]) -> Flow {
+        for stmt in body {
+            match self.statement(stmt) {
+                Flow::Normal => {},
+                flow => return flow,
+            }
+        }
+        Flow::Normal
+    }
+
+    /// 오류 기록 후 오류 처리 방식에 따른 흐름 반환
+    fn fail(&mut self, stmt: &Stmt, command: String, column: Option<usize>, message: String) -> Flow {
+        self.report.errors.push(ScriptError {
+            file: self.file.clone(),
+            line: stmt.pos.line,
+            column,
+            command,
+            message,
+        });
+        if self.on_error == OnError::Stop {
+            self.report.stopped = true;
+            Flow::Halt
+        } else {
+            Flow::Normal
+        }
+    }
+
+    /// 식 평가 결과를 흐름으로 변환 (평가 오류는 문장 위치로 기록)
+    fn check<T>(&mut self, stmt: &Stmt, result: Eval<T>) -> Result<T, Flow> {
+        result.map_err(|interrupt| match interrupt {
+            Interrupt::Error(message) => self.fail(stmt, stmt.source.clone(), Some(stmt.pos.column), message),
+            Interrupt::Halt => Flow::Halt,
+        })
+    }
+
+    fn statement(&mut self, stmt: &Stmt) -> Flow {
+        match self.statement_inner(stmt) {
+            Ok(flow) | Err(flow) => flow,
+        }
+    }
+
+    /// 취소 요청(REPL의 Ctrl-C)이 있으면 오류 처리 방식과 상관없이 중단
+    fn check_cancelled(&mut self, stmt: &Stmt) -> Result<(), Flow> {
+        if !self.executor.cancel_token().is_cancelled() {
+            return Ok(());
+        }
+        self.report.errors.push(ScriptError {
+            file: self.file.clone(),
+            line: stmt.pos.line,
+            column: None,
+            command: stmt.source.clone(),
+            message: "Cancelled".to_string(),
+        });
+        self.report.stopped = true;
+        Err(Flow::Halt)
+    }
+
+    fn statement_inner(&mut self, stmt: &Stmt) -> Result<Flow, Flow> {
+        self.check_cancelled(stmt)?;
+        match &stmt.kind {
+            StmtKind::Command(line) => {
+                self.command(stmt, line)?;
+            },
+            StmtKind::Assign { name, value } => {
+                let value = match value {
+                    AssignValue::Expr(expr) => {
+                        let result = self.eval(expr);
+                        self.check(stmt, result)?
+                    },
+                    AssignValue::Command(line) => self.command(stmt, line)?,
+                };
+                self.set_var(name, value);
+            },
+            StmtKind::If { branches, otherwise } => {
+                for (condition, body) in branches {
+                    let result = self.eval(condition);
+                    if self.check(stmt, result)?.is_truthy() {
+                        return Ok(self.block(body));
+                    }
+                }
+                if let Some(body) = otherwise {
+                    return Ok(self.block(body));
+                }
+            },
+            StmtKind::While { condition, body } => loop {
+                self.check_cancelled(stmt)?;
+                let result = self.eval(condition);
+                if !self.check(stmt, result)?.is_truthy() {
+                    break;
+                }
+                match self.block(body) {
+                    Flow::Normal | Flow::Continue => {},
+                    Flow::Break => break,
+                    flow => return Ok(flow),
+                }
+            },
+            StmtKind::ForEach { var, iterable, body } => {
+                let result = self.eval(iterable).and_then(|value| iterate(value).map_err(Interrupt::Error));
+                for item in self.check(stmt, result)? {
+                    self.check_cancelled(stmt)?;
+                    self.set_var(var, item);
+                    match self.block(body) {
+                        Flow::Normal | Flow::Continue => {},
+                        Flow::Break => break,
+                        flow => return Ok(flow),
+                    }
+                }
+            },
+            StmtKind::Proc { name, params, body } => {
+                self.state.procs.insert(name.clone(), Rc::new(ProcDef {
+                    params: params.clone(),
+                    body: body.clone(),
+                    file: self.file.clone(),
+                }));
+                self.register_proc_command(name, params, stmt.pos.line);
+            },
+            StmtKind::Return(value) => {
+                let result = match value {
+                    Some(expr) => self.eval(expr),
+                    None => Ok(Value::Nil),
+                };
+                return Ok(Flow::Return(Box::new(self.check(stmt, result)?)));
+            },
+            StmtKind::Break => return Ok(Flow::Break),
+            StmtKind::Continue => return Ok(Flow::Continue),
+            StmtKind::Print(template) => {
+                let result = self.interpolate(template, false);
+                let text = self.check(stmt, result)?;
+                (self.output)(&ScriptLine { number: stmt.pos.line, text: stmt.source.clone() }, &CommandResult::Success(text));
+            },
+            StmtKind::On { trigger, body } => {
+                let result = self.install_trigger(trigger);
+                let trigger = self.check(stmt, result)?;
+
+                self.state.next_handler_id += 1;
+                let handler = Handler {
+                    id: self.state.next_handler_id,
+                    trigger,
+                    body: Rc::new(body.clone()),
+                    file: self.file.clone(),
+                    line: stmt.pos.line,
+                    hits: 0,
+                };
+                let message = format!("Registered event handler #{}: {}", handler.id, handler.info().description);
+                self.state.handlers.push(handler);
+                (self.output)(&ScriptLine { number: stmt.pos.line, text: stmt.source.clone() }, &CommandResult::Success(message));
+            },
+            StmtKind::Wait(duration) => {
+                let result = self.eval(duration).and_then(|value| match value {
+                    Value::Int(ms) if ms >= 0 => Ok(ms as u64),
+                    other => Err(format!("wait expects milliseconds, got {}", other).into()),
+                });
+                let deadline = Instant::now() + Duration::from_millis(self.check(stmt, result)?);
+
+                loop {
+                    self.check_cancelled(stmt)?;
+                    if let Flow::Halt = self.dispatch() {
+                        return Err(Flow::Halt);
+                    }
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break;
+                    }
+                    std::thread::sleep(WAIT_POLL_INTERVAL.min(deadline - now));
+                }
+            },
+        }
+        Ok(Flow::Normal)
+    }
+
+    /// 핸들러 조건 설치 (후크 설치, 워치 등록)
+    fn install_trigger(&mut self, trigger: &Trigger) -> Eval<ActiveTrigger> {
+        let address_of = |value: Value| value.as_address()
+            .ok_or_else(|| Interrupt::Error(format!("Event target must be an address, got {}", value)));
+
+        match trigger {
+            Trigger::Hook { target, phase } => {
+                let address = address_of(self.eval(target)?)?;
+                let hook_id = self.executor.agent()
+                    .and_then(|agent| agent.hook_function(address))
+                    .map_err(|e| format!("Failed to hook {}: {}", addr_to_hex(address), e))?;
+                Ok(ActiveTrigger::Hook { hook_id, address, phase: *phase })
+            },
+            Trigger::Watch { target, memory_type } => {
+                let address = address_of(self.eval(target)?)?;
+                let text = match memory_type {
+                    Some(memory_type) => format!("mem watch {} {}", addr_to_hex(address), memory_type),
+                    None => format!("mem watch {}", addr_to_hex(address)),
+                };
+                let command = Command::parse(&text).map_err(|e| e.to_string())?;
+                match self.executor.execute(&command) {
+                    CommandResult::Error(message) => Err(message.into()),
+                    _ => Ok(ActiveTrigger::Watch { address }),
+                }
+            },
+            Trigger::Timer { interval } => match self.eval(interval)? {
+                Value::Int(ms) if ms > 0 => {
+                    let interval = Duration::from_millis(ms as u64);
+                    Ok(ActiveTrigger::Timer { interval, next_due: Instant::now() + interval })
+                },
+                other => Err(format!("Timer interval must be a positive number of milliseconds, got {}", other).into()),
+            },
+        }
+    }
+
+    /// 쌓인 에이전트 메시지와 만료된 타이머로 핸들러 실행
+    fn dispatch(&mut self) -> Flow {
+        if self.dispatching || self.state.handlers.is_empty() {
+            return Flow::Normal;
+        }
+        self.dispatching = true;
+        let flow = self.dispatch_inner();
+        self.dispatching = false;
+        flow
+    }
+
+    fn dispatch_inner(&mut self) -> Flow {
+        for message in self.executor.drain_messages() {
+            let MessageKind::Send(payload) = &message.kind else {
+                continue;
+            };
+            let matched: Vec<u64> = self.state.handlers.iter()
+                .filter(|handler| handler.matches(payload))
+                .map(|handler| handler.id)
+                .collect();
+            if matched.is_empty() {
+                continue;
+            }
+
+            // 페이로드 필드를 지역 변수로 노출 ($args, $retval, $newValue 등)
+            let locals: HashMap<String, Value> = payload.as_object()
+                .map(|fields| fields.iter().map(|(key, value)| (key.clone(), Value::from_json(value))).collect())
+                .unwrap_or_default();
+            for id in matched {
+                if let Flow::Halt = self.run_handler(id, locals.clone()) {
+                    return Flow::Halt;
+                }
+            }
+        }
+
+        let now = Instant::now();
+        let mut due = Vec::new();
+        for handler in &mut self.state.handlers {
+            if let ActiveTrigger::Timer { interval, next_due } = &mut handler.trigger {
+                if *next_due <= now {
+                    // 밀린 주기는 건너뛰고 한 번만 실행
+                    *next_due = now + *interval;
+                    due.push(handler.id);
+                }
+            }
+        }
+        for id in due {
+            if let Flow::Halt = self.run_handler(id, HashMap::new()) {
+                return Flow::Halt;
+            }
+        }
+        Flow::Normal
+    }
+
+    /// 핸들러 본문 실행 (`$handler`는 핸들러 ID)
+    fn run_handler(&mut self, id: u64, mut locals: HashMap<String, Value>) -> Flow {
+        let Some(handler) = self.state.handlers.iter_mut().find(|handler| handler.id == id) else {
+            return Flow::Normal;
+        };
+        handler.hits += 1;
+        let body = handler.body.clone();
+        let file = handler.file.clone();
+        locals.insert("handler".to_string(), Value::Int(id as i64));
+
+        self.frames.push(locals);
+        let caller_file = std::mem::replace(&mut self.file, file);
+        let flow = self.block(&body);
+        self.file = caller_file;
+        self.frames.pop();
+
+        match flow {
+            Flow::Halt => Flow::Halt,
+            _ => Flow::Normal,
+        }
+    }
+
+    /// 명령어 줄 실행 후 결과 값 반환
+    ///
+    /// 결과 값은 DataList면 항목 리스트, 명령어가 로그를 새로 채웠으면 로그 항목 리스트,
+    /// 그 외 성공이면 출력 메시지 문자열이다.
+    fn command(&mut self, stmt: &Stmt, line: &CommandLine) -> Result<Value, Flow> {
+        if let Some(proc_def) = self.state.procs.get(&line.name).cloned() {
+            let mut args = Vec::with_capacity(line.args.len());
+            for arg in &line.args {
+                let result = self.eval(arg);
+                args.push(self.check(stmt, result)?);
+            }
+            let result = self.call_proc(&line.name, &proc_def, args);
+            return self.check(stmt, result);
+        }
+
+        let result = self.interpolate(&line.text, true);
+        let text = self.check(stmt, result)?;
+        let command = match Pipeline::parse(&text) {
+            Ok(mut pipeline) if pipeline.is_single() => pipeline.stages.remove(0),
+            Ok(pipeline) => return self.command_result(stmt, text, |executor| executor.execute_pipeline(&pipeline)),
+            Err(e) => return Err(self.fail(stmt, text, None, format!("Parse error: {}", e))),
         };
-        
-        // 명령어 실행 (현재는 로깅만 함)
-        for cmd in commands {
-            println!("실행: {}", cmd);
+
+        if matches!(command.get_type(), CommandType::OnList | CommandType::OnRemove) {
+            let result = self.state.handler_command(self.executor, &command);
+            (self.output)(&ScriptLine { number: stmt.pos.line, text: text.clone() }, &result);
+            return match result {
+                CommandResult::Error(message) => Err(self.fail(stmt, text, None, message)),
+                _ => Ok(Value::Nil),
+            };
+        }
+
+        if let Some((name, applying)) = command_preset(self.executor, &command) {
+            return self.preset_commands(stmt, text, &command, &name, applying);
+        }
+
+        // 중첩 스크립트는 현재 스크립트 위치 기준 상대 경로로 실행
+        if command.get_type() == CommandType::Run {
+            let nested = match command.get_raw_arg(0) {
+                Some(nested) => resolve_path(self.file.as_deref(), nested),
+                None => return Err(self.fail(stmt, text, None, "Script path argument required".to_string())),
+            };
+            if self.run_depth + 1 >= MAX_RUN_DEPTH {
+                let message = format!("Script nesting too deep (max {})", MAX_RUN_DEPTH);
+                self.fail(stmt, text, None, message);
+                self.report.stopped = true;
+                return Err(Flow::Halt);
+            }
+            self.run_depth += 1;
+            let flow = self.run_path(&nested, Some(stmt));
+            self.run_depth -= 1;
+            return match flow {
+                Flow::Halt => Err(Flow::Halt),
+                _ => Ok(Value::Nil),
+            };
         }
-        
-        ExecutionResult::Success
+
+        self.command_result(stmt, text, |executor| executor.execute(&command))
     }
-} 
\ No newline at end of file
+
+    /// 명령어(또는 파이프라인) 실행 결과를 출력하고 값으로 변환
+    fn command_result<F>(&mut self, stmt: &Stmt, text: String, run: F) -> Result<Value, Flow>
+    where
+        F: FnOnce(&mut CommandExecutor) -> CommandResult,
+    {
+        let generation = self.executor.store().log_generation();
+        let result = run(self.executor);
+        self.report.executed += 1;
+        (self.output)(&ScriptLine { number: stmt.pos.line, text: text.clone() }, &result);
+
+        match result {
+            CommandResult::Error(message) => Err(self.fail(stmt, text, None, message)),
+            CommandResult::Exit => {
+                self.report.exit_requested = true;
+                Err(Flow::Halt)
+            },
+            CommandResult::DataList(items) => Ok(Value::List(
+                items.into_iter().map(|(_, data)| data_value(data, None)).collect(),
+            )),
+            CommandResult::Success(_) if self.executor.store().log_generation() != generation => {
+                let log = self.executor.store().get_log();
+                Ok(Value::List(log.iter()
+                    .enumerate()
+                    .map(|(idx, data)| data_value(data.clone(), Some(format!("log:{}", idx))))
+                    .collect()))
+            },
+            CommandResult::Success(message) => Ok(Value::Str(message)),
+        }
+    }
+
+    /// 명령어 스크립트 프리셋 적용/되돌리기
+    ///
+    /// 프리셋 옵션을 지역 변수로 넘겨 스크립트를 실행하고, 오류 없이 끝나면 적용 상태를 바꾼다.
+    fn preset_commands(&mut self, stmt: &Stmt, text: String, command: &Command, name: &str, applying: bool) -> Result<Value, Flow> {
+        let action = if applying { "apply" } else { "revert" };
+        let presets = self.executor.presets();
+        let commands = presets.get(name).and_then(|preset| preset.commands()).cloned();
+        let prepared = match (commands, presets.is_applied(name)) {
+            (Some(_), true) if applying => Err(format!("Preset '{}' is already applied", name)),
+            (Some(_), false) if !applying => Err(format!("Preset '{}' is not applied", name)),
+            (Some(commands), _) => {
+                let overrides = if applying { parse_overrides(&command.raw_args[1..]) } else { Ok(Vec::new()) };
+                overrides.and_then(|overrides| presets.options_for(name, &overrides))
+                    .map(|options| (if applying { Some(commands.apply) } else { commands.revert }, options))
+                    .map_err(|e| e.to_string())
+            },
+            (None, _) => Err(format!("Unknown preset: {}", name)),
+        };
+        let (script, options) = match prepared {
+            Ok(prepared) => prepared,
+            Err(e) => return Err(self.fail(stmt, text, None, format!("Failed to {} preset: {}", action, e))),
+        };
+
+        if let Some(script) = script {
+            if self.run_depth + 1 >= MAX_RUN_DEPTH {
+                let message = format!("Script nesting too deep (max {})", MAX_RUN_DEPTH);
+                self.fail(stmt, text, None, message);
+                self.report.stopped = true;
+                return Err(Flow::Halt);
+            }
+
+            let errors = self.report.errors.len();
+            self.frames.push(options.iter().map(|(key, value)| (key.clone(), option_value(value))).collect());
+            self.run_depth += 1;
+            let flow = self.run_path(&script, Some(stmt));
+            self.run_depth -= 1;
+            self.frames.pop();
+
+            if let Flow::Halt = flow {
+                return Err(Flow::Halt);
+            }
+            if self.report.errors.len() > errors {
+                let message = format!("Failed to {} preset: {} reported errors", action, script.display());
+                return Err(self.fail(stmt, text, None, message));
+            }
+        }
+
+        self.executor.presets_mut().set_applied(name, applying);
+        let message = if applying { format!("Applied preset {}", name) } else { format!("Reverted preset {}", name) };
+        (self.output)(&ScriptLine { number: stmt.pos.line, text }, &CommandResult::Success(message));
+        Ok(Value::Nil)
+    }
+
+    /// 프로시저를 명령어로 등록 (REPL에서 호출, `help`/자동완성에 표시)
+    ///
+    /// 다시 정의하면 정의를 바꾸고, 다른 명령어와 이름이 겹치면 스크립트 안에서만 호출할 수 있다.
+    fn register_proc_command(&mut self, name: &str, params: &[String], line: usize) {
+        let registry = self.executor.registry_mut();
+        if registry.find(name, None).is_some_and(|spec| spec.source == CommandSource::Script) {
+            registry.unregister(name);
+        }
+        let location = match &self.file {
+            Some(file) => format!("{}:{}", file.display(), line),
+            None => format!("line {}", line),
+        };
+        let args: Vec<String> = params.iter().map(|param| format!("<{}>", param)).collect();
+        let spec = CommandSpec::new(name, CommandType::Procedure)
+            .args(&args.join(" "))
+            .description(&format!("Script procedure ({})", location))
+            .source(CommandSource::Script);
+        if let Err(e) = registry.register(spec) {
+            log::debug!("Procedure '{}' is not available as a command: {}", name, e);
+        }
+    }
+
+    fn call_proc(&mut self, name: &str, proc_def: &ProcDef, args: Vec<Value>) -> Eval<Value> {
+        if args.len() != proc_def.params.len() {
+            return Err(format!("proc '{}' expects {} arguments, got {}", name, proc_def.params.len(), args.len()).into());
+        }
+        if self.frames.len() >= MAX_CALL_DEPTH {
+            return Err(format!("Procedure calls nested too deep (max {})", MAX_CALL_DEPTH).into());
+        }
+
+        self.frames.push(proc_def.params.iter().cloned().zip(args).collect());
+        let caller_file = std::mem::replace(&mut self.file, proc_def.file.clone());
+        let flow = self.block(&proc_def.body);
+        self.file = caller_file;
+        self.frames.pop();
+
+        match flow {
+            Flow::Return(value) => Ok(*value),
+            Flow::Halt => Err(Interrupt::Halt),
+            _ => Ok(Value::Nil),
+        }
+    }
+
+    fn get_var(&self, name: &str) -> Option<&Value> {
+        self.frames.last()
+            .and_then(|frame| frame.get(name))
+            .or_else(|| self.state.globals.get(name))
+    }
+
+    /// 변수 설정
+    ///
+    /// 프로시저/핸들러 안에서는 이미 있는 전역 변수면 전역 변수를, 아니면 지역 변수를 바꾼다.
+    fn set_var(&mut self, name: &str, value: Value) {
+        match self.frames.last_mut() {
+            Some(frame) if frame.contains_key(name) || !self.state.globals.contains_key(name) => {
+                frame.insert(name.to_string(), value);
+            },
+            _ => {
+                self.state.globals.insert(name.to_string(), value);
+            },
+        }
+    }
+
+    /// 텍스트 치환 (명령어 인자용이면 `Value::to_arg` 형식)
+    fn interpolate(&mut self, template: &Template, as_arg: bool) -> Eval<String> {
+        let mut text = String::new();
+        for part in &template.0 {
+            match part {
+                TextPart::Literal(literal) => text.push_str(literal),
+                TextPart::Expr(expr) => {
+                    let value = self.eval(expr)?;
+                    if as_arg {
+                        text.push_str(&value.to_arg());
+                    } else {
+                        text.push_str(&value.to_string());
+                    }
+                },
+            }
+        }
+        Ok(text)
+    }
+
+    fn eval(&mut self, expr: &Expr) -> Eval<Value> {
+        match expr {
+            Expr::Nil => Ok(Value::Nil),
+            Expr::Bool(b) => Ok(Value::Bool(*b)),
+            Expr::Int(i) => Ok(Value::Int(*i)),
+            Expr::Address(a) => Ok(Value::Address(*a)),
+            Expr::Float(f) => Ok(Value::Float(*f)),
+            Expr::Text(template) => self.interpolate(template, false).map(Value::Str),
+            Expr::Var(name) => self.get_var(name)
+                .cloned()
+                .ok_or_else(|| format!("Undefined variable '${}'", name).into()),
+            Expr::Selected => Ok(self.executor.navigator()
+                .get_selected()
+                .map(|data| data_value(data.clone(), None))
+                .unwrap_or(Value::Nil)),
+            Expr::Selector(selector) => self.select(selector).map_err(Interrupt::Error),
+            Expr::List(items) => items.iter()
+                .map(|item| self.eval(item))
+                .collect::<Eval<_>>()
+                .map(Value::List),
+            Expr::Unary(op, operand) => {
+                let value = self.eval(operand)?;
+                match (op, value) {
+                    (UnaryOp::Not, value) => Ok(Value::Bool(!value.is_truthy())),
+                    (UnaryOp::Neg, Value::Int(i)) => i.checked_neg()
+                        .map(Value::Int)
+                        .ok_or_else(|| "Arithmetic overflow in negation".to_string().into()),
+                    (UnaryOp::Neg, Value::Float(f)) => Ok(Value::Float(-f)),
+                    (UnaryOp::Neg, value) => Err(format!("Cannot negate {}", value.type_name()).into()),
+                }
+            },
+            Expr::Binary(op, lhs, rhs) => self.eval_binary(*op, lhs, rhs),
+            Expr::Index(target, index) => {
+                let target = self.eval(target)?;
+                let index = self.eval(index)?;
+                index_value(&target, &index).map_err(Interrupt::Error)
+            },
+            Expr::Call(name, args) => {
+                let args = args.iter().map(|arg| self.eval(arg)).collect::<Eval<Vec<_>>>()?;
+                match self.state.procs.get(name).cloned() {
+                    Some(proc_def) => self.call_proc(name, &proc_def, args),
+                    None => builtin(name, args).map_err(Interrupt::Error),
+                }
+            },
+        }
+    }
+
+    fn eval_binary(&mut self, op: BinaryOp, lhs: &Expr, rhs: &Expr) -> Eval<Value> {
+        let lhs = self.eval(lhs)?;
+        match op {
+            BinaryOp::And if !lhs.is_truthy() => return Ok(Value::Bool(false)),
+            BinaryOp::Or if lhs.is_truthy() => return Ok(Value::Bool(true)),
+            BinaryOp::And | BinaryOp::Or => return Ok(Value::Bool(self.eval(rhs)?.is_truthy())),
+            _ => {},
+        }
+
+        let rhs = self.eval(rhs)?;
+        let ordering = || lhs.compare(&rhs)
+            .ok_or_else(|| format!("Cannot compare {} and {}", lhs.type_name(), rhs.type_name()));
+        let result = match op {
+            BinaryOp::Eq => Value::Bool(lhs.loose_eq(&rhs)),
+            BinaryOp::Ne => Value::Bool(!lhs.loose_eq(&rhs)),
+            BinaryOp::Lt => Value::Bool(ordering()?.is_lt()),
+            BinaryOp::Le => Value::Bool(ordering()?.is_le()),
+            BinaryOp::Gt => Value::Bool(ordering()?.is_gt()),
+            BinaryOp::Ge => Value::Bool(ordering()?.is_ge()),
+            _ => lhs.arithmetic(op, &rhs)?,
+        };
+        Ok(result)
+    }
+
+    /// 저장소 선택자 평가 (단일 인덱스는 항목, 그 외는 리스트)
+    fn select(&self, text: &str) -> Result<Value, String> {
+        let selector = Selector::from_str(text).map_err(|e| e.to_string())?;
+        // 스크립트 선택자는 항상 lib:/log:/nav: 접두사가 있음
+        let (source, items) = match self.executor.select(&selector, SelectorSource::Log) {
+            Ok(selected) => selected,
+            // 전체/태그 선택은 맞는 항목이 없어도 빈 리스트 (for each에서 그대로 쓰도록)
+            Err(_) if selector.terms.iter().all(|term| matches!(term, SelectorTerm::All | SelectorTerm::Tag(_))) => {
+                return Ok(Value::List(Vec::new()));
+            },
+            Err(e) => return Err(e.to_string()),
+        };
+
+        let mut values: Vec<Value> = items.into_iter()
+            .map(|(idx, data)| data_value(data, Some(format!("{}:{}", source, idx))))
+            .collect();
+        match selector.is_single() {
+            true => Ok(values.remove(0)),
+            false => Ok(Value::List(values)),
+        }
+    }
+}
+
+fn data_value(data: VzData, reference: Option<String>) -> Value {
+    Value::Data(DataValue { data, reference })
+}
+
+/// for each 대상 값을 항목 목록으로 변환
+fn iterate(value: Value) -> Result<Vec<Value>, String> {
+    match value {
+        Value::List(items) => Ok(items),
+        Value::Nil => Ok(Vec::new()),
+        Value::Data(_) => Ok(vec![value]),
+        other => Err(format!("Cannot iterate over {}", other.type_name())),
+    }
+}
+
+/// 리스트/문자열 인덱스 접근 (음수는 끝에서부터)
+fn index_value(target: &Value, index: &Value) -> Result<Value, String> {
+    let Value::Int(index) = index else {
+        return Err(format!("Index must be int, got {}", index.type_name()));
+    };
+    let resolve = |len: usize| {
+        let idx = if *index < 0 { len as i64 + index } else { *index };
+        usize::try_from(idx).ok().filter(|&idx| idx < len)
+            .ok_or_else(|| format!("Index {} out of bounds (length {})", index, len))
+    };
+
+    match target {
+        Value::List(items) => Ok(items[resolve(items.len())?].clone()),
+        Value::Str(s) => {
+            let chars: Vec<char> = s.chars().collect();
+            Ok(Value::Str(chars[resolve(chars.len())?].to_string()))
+        },
+        other => Err(format!("Cannot index {}", other.type_name())),
+    }
+}
+
+/// 내장 함수
+fn builtin(name: &str, args: Vec<Value>) -> Result<Value, String> {
+    let arity = |expected: usize| {
+        if args.len() == expected {
+            Ok(())
+        } else {
+            Err(format!("{}() expects {} arguments, got {}", name, expected, args.len()))
+        }
+    };
+
+    match name {
+        "len" => {
+            arity(1)?;
+            match &args[0] {
+                Value::List(items) => Ok(Value::Int(items.len() as i64)),
+                Value::Str(s) => Ok(Value::Int(s.chars().count() as i64)),
+                Value::Nil => Ok(Value::Int(0)),
+                other => Err(format!("len() not supported for {}", other.type_name())),
+            }
+        },
+        "addr" => {
+            arity(1)?;
+            args[0].as_address()
+                .map(Value::Address)
+                .ok_or_else(|| format!("{} has no address", args[0]))
+        },
+        "hex" => {
+            arity(1)?;
+            args[0].as_address()
+                .map(|address| Value::Str(addr_to_hex(address)))
+                .ok_or_else(|| format!("Cannot convert {} to hex", args[0]))
+        },
+        "name" => {
+            arity(1)?;
+            match &args[0] {
+                Value::Data(d) => Ok(Value::Str(d.data.get_name()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| d.data.get_display_name()))),
+                other => Err(format!("name() not supported for {}", other.type_name())),
+            }
+        },
+        "int" => {
+            arity(1)?;
+            match &args[0] {
+                Value::Int(i) => Ok(Value::Int(*i)),
+                Value::Float(f) => Ok(Value::Int(*f as i64)),
+                Value::Bool(b) => Ok(Value::Int(*b as i64)),
+                Value::Address(a) => i64::try_from(*a)
+                    .map(Value::Int)
+                    .map_err(|_| format!("{} does not fit in int", addr_to_hex(*a))),
+                Value::Str(s) => s.trim().parse()
+                    .map(Value::Int)
+                    .map_err(|_| format!("Cannot convert '{}' to int", s)),
+                other => Err(format!("int() not supported for {}", other.type_name())),
+            }
+        },
+        "str" => {
+            arity(1)?;
+            Ok(Value::Str(args[0].to_string()))
+        },
+        "range" => {
+            let (start, end) = match args.as_slice() {
+                [Value::Int(end)] => (0, *end),
+                [Value::Int(start), Value::Int(end)] => (*start, *end),
+                _ => return Err("range() expects 1 or 2 int arguments".to_string()),
+            };
+            Ok(Value::List((start..end).map(Value::Int).collect()))
+        },
+        _ => Err(format!("Unknown function '{}'", name)),
+    }
+}
+
+/// 명령어 스크립트 프리셋을 대상으로 한 `preset apply/revert`면 (프리셋 이름, 적용 여부)
+fn command_preset(executor: &CommandExecutor, command: &Command) -> Option<(String, bool)> {
+    let applying = match command.get_type() {
+        CommandType::PresetApply => true,
+        CommandType::PresetRevert => false,
+        _ => return None,
+    };
+    let name = command.get_raw_arg(0)?;
+    executor.presets().get(name)?.commands()?;
+    Some((name.to_string(), applying))
+}
+
+/// 프리셋 옵션 값을 스크립트 값으로 변환
+fn option_value(value: &OptionValue) -> Value {
+    match value {
+        OptionValue::Bool(b) => Value::Bool(*b),
+        OptionValue::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => Value::Int(*n as i64),
+        OptionValue::Number(n) => Value::Float(*n),
+        OptionValue::Text(s) => Value::Str(s.clone()),
+        OptionValue::List(items) => Value::List(items.iter().cloned().map(Value::Str).collect()),
+    }
+}
+
+/// 스크립트 경로 해석 (상대 경로는 부모 스크립트 디렉터리 기준)
+fn resolve_path(parent: Option<&Path>, path: &str) -> PathBuf {
+    let path = Path::new(path);
+    match parent.and_then(Path::parent) {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path.to_path_buf(),
+    }
+}
@@ -1,7 +1,11 @@
+pub mod ast;
 pub mod parser;
+pub mod value;
 pub mod executor;
 
+pub use ast::*;
 pub use parser::*;
+pub use value::*;
 pub use executor::*;
 
 // VLITZ 스크립트 파서 및 실행기 모듈
@@ -9,8 +13,424 @@ pub use executor::*;
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use serde_json::json;
+    use vlitz_core::{Command, CommandExecutor, CommandResult};
+    use vlitz_runtime::{MessageKind, MessageQueue, MockBackend, RpcBackend, ScriptMessage};
+
+    fn mock_executor() -> CommandExecutor {
+        let mut executor = CommandExecutor::new(20);
+        executor.set_backend(Box::new(MockBackend::new()
+            .on_value("queryModules", json!({ "id": 1, "total": 1, "runtime": null }))
+            .on_value("readQuery", json!([
+                { "name": "libc.so", "base": "0x7f000000", "size": 4096 },
+                { "name": "libgame.so", "base": "0x7f100000", "size": 8192 },
+            ]))
+            .on_value("closeQuery", json!(true))));
+        executor
+    }
+
+    /// 스크립트 실행 후 print/명령어 출력 수집
+    fn run(executor: &mut CommandExecutor, script: &str) -> (ExecutionReport, Vec<String>) {
+        let mut printed = Vec::new();
+        let report = ScriptExecutor::new().execute(executor, script, |_, result| {
+            if let CommandResult::Success(message) = result {
+                printed.push(message.clone());
+            }
+        });
+        (report, printed)
+    }
+
+    #[test]
+    fn test_parse_blocks_with_positions() {
+        let script = parse_script("# comment\n\nif $x > 1\n  list module\nelse\n  $y = sel\nend\n").unwrap();
+
+        assert_eq!(script.body.len(), 1);
+        assert_eq!(script.body[0].pos, Pos::new(3, 1));
+        let StmtKind::If { branches, otherwise } = &script.body[0].kind else {
+            panic!("expected if statement");
+        };
+        assert_eq!(branches[0].0, Expr::Binary(BinaryOp::Gt, Box::new(Expr::Var("x".to_string())), Box::new(Expr::Int(1))));
+        assert_eq!(branches[0].1[0].pos, Pos::new(4, 3));
+        assert!(matches!(branches[0].1[0].kind, StmtKind::Command(ref line) if line.name == "list"));
+        assert_eq!(otherwise.as_ref().unwrap()[0].kind, StmtKind::Assign {
+            name: "y".to_string(),
+            value: AssignValue::Expr(Expr::Selected),
+        });
+    }
+
+    #[test]
+    fn test_parse_assignment_captures_command() {
+        let script = parse_script("$hits = scan search 100 int\n$next = $hits[0] + 0x10\n").unwrap();
+
+        let StmtKind::Assign { value: AssignValue::Command(line), .. } = &script.body[0].kind else {
+            panic!("expected command capture");
+        };
+        assert_eq!(line.name, "scan");
+        assert_eq!(line.args, vec![Expr::Text(Template(vec![TextPart::Literal("search".to_string())])), Expr::Int(100), Expr::Text(Template(vec![TextPart::Literal("int".to_string())]))]);
+        assert!(matches!(&script.body[1].kind, StmtKind::Assign { value: AssignValue::Expr(Expr::Binary(BinaryOp::Add, _, _)), .. }));
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        let error = parse_script("list module\nwhile $i < 3\n  sel 0\n").unwrap_err();
+        assert_eq!(error.to_string(), "2:1: 'while' without matching 'end'");
+
+        let error = parse_script("if $x +* 2\nend\n").unwrap_err();
+        assert_eq!(error.pos, Pos::new(1, 8));
+
+        let error = parse_script("  break\n").unwrap_err();
+        assert_eq!(error.to_string(), "1:3: 'break' outside of loop");
+
+        let error = parse_script("print ${$x + }\n").unwrap_err();
+        assert_eq!(error.pos, Pos::new(1, 14));
+    }
+
+    #[test]
+    fn test_incomplete_blocks() {
+        assert!(is_incomplete("while $i < 3\n  sel 0"));
+        assert!(is_incomplete("if $x\n  print a\nelse"));
+        assert!(is_incomplete("proc f $a\n  for $x in $a\n    print $x\n  end"));
+        assert!(!is_incomplete("proc f $a\n  print $a\nend"));
+        assert!(!is_incomplete("list module"));
+        // 문법 오류는 더 입력받지 않고 바로 보고
+        assert!(!is_incomplete("  break"));
+        assert!(!is_incomplete("end"));
+    }
+
+    #[test]
+    fn test_variables_and_address_arithmetic() {
+        let mut executor = CommandExecutor::new(20);
+        let (report, printed) = run(&mut executor, "$base = 0x1000\n$ptr = $base + 0x20 * 2\nprint ptr=$ptr delta=${$ptr - $base}\nprint ${$base - 1 < $ptr}\n");
+
+        assert!(report.is_success(), "{:?}", report.errors);
+        assert_eq!(printed, vec!["ptr=0x1040 delta=64", "true"]);
+    }
+
+    #[test]
+    fn test_capture_and_for_each() {
+        let mut executor = mock_executor();
+        let script = "\
+$mods = list module
+print found ${len($mods)} modules
+for each $m in $mods
+  if name($m) == \"libgame.so\"
+    sel $m
+    break
+  end
+end
+$selected = sel
+print ${addr($selected) + 0x10}
+";
+        let (report, printed) = run(&mut executor, script);
+
+        assert!(report.is_success(), "{:?}", report.errors);
+        assert_eq!(report.executed, 2);
+        assert_eq!(executor.get_prompt(), "vlitz:Module:libgame.so>");
+        assert_eq!(printed.last().map(String::as_str), Some("0x7f100010"));
+        assert!(printed.contains(&"found 2 modules".to_string()));
+    }
+
+    #[test]
+    fn test_store_selector_iteration() {
+        let mut executor = mock_executor();
+        let script = "\
+list module
+sav log:0-1
+$names = []
+for each $item in lib:all
+  $names = $names + [name($item)]
+end
+print $names
+print ${lib:1}
+meta tag lib:-1 hot
+print ${len(lib:#hot)} ${len(lib:#cold)}
+";
+        let (report, printed) = run(&mut executor, script);
+
+        assert!(report.is_success(), "{:?}", report.errors);
+        assert_eq!(printed[printed.len() - 4], "[libc.so, libgame.so]");
+        assert_eq!(printed[printed.len() - 3], "[Module] libgame.so @ 0x7f100000");
+        assert_eq!(printed[printed.len() - 2], "Added tag 'hot' to 1 items");
+        assert_eq!(printed[printed.len() - 1], "1 0");
+    }
+
+    #[test]
+    fn test_while_and_procedures() {
+        let mut executor = CommandExecutor::new(20);
+        let script = "\
+proc fact $n
+  if $n <= 1
+    return 1
+  end
+  return $n * fact($n - 1)
+end
+proc show $label $value
+  print $label: $value
+end
+$i = 0
+while true
+  $i = $i + 1
+  if $i % 2 == 0
+    continue
+  elif $i > 5
+    break
+  end
+  show odd $i
+end
+$f = fact 5
+show fact $f
+";
+        let (report, printed) = run(&mut executor, script);
+
+        assert!(report.is_success(), "{:?}", report.errors);
+        assert_eq!(printed, vec!["odd: 1", "odd: 3", "odd: 5", "fact: 120"]);
+    }
+
+    #[test]
+    fn test_runtime_error_position() {
+        let mut executor = CommandExecutor::new(20);
+        let (report, _) = run(&mut executor, "$x = 1\n  print ${$x + $missing}\nprint never\n");
+
+        assert!(report.stopped);
+        assert_eq!(report.errors[0].to_string(), "line 2:3: print ${$x + $missing}: Undefined variable '$missing'");
+
+        let (report, _) = run(&mut executor, "proc one $a\nend\none 1 2\n");
+        assert_eq!(report.errors[0].message, "proc 'one' expects 1 arguments, got 2");
+    }
+
+    /// 후크/워치 RPC를 흉내 내는 백엔드와 메시지 큐
+    fn event_executor() -> (CommandExecutor, MessageQueue, std::rc::Rc<MockBackend>) {
+        let backend = std::rc::Rc::new(MockBackend::new()
+            .on("hookFunction", |args| Ok(json!(format!("hook_{}", args[0].as_str().unwrap()))))
+            .on_value("unhookFunction", json!(true))
+            .on_value("addWatch", json!({ "id": 1, "mode": "hardware", "value": 5 })));
+        let queue = backend.message_queue();
+        let mut executor = CommandExecutor::new(20);
+        executor.set_backend(Box::new(SharedBackend(backend.clone())));
+        (executor, queue, backend)
+    }
+
+    /// 테스트에서 호출 기록을 확인하기 위한 공유 백엔드
+    struct SharedBackend(std::rc::Rc<MockBackend>);
+
+    impl RpcBackend for SharedBackend {
+        fn call(&self, export_name: &str, args: &[serde_json::Value]) -> vlitz_shared::VlitzResult<serde_json::Value> {
+            self.0.call(export_name, args)
+        }
+
+        fn drain_messages(&self) -> Vec<ScriptMessage> {
+            self.0.drain_messages()
+        }
+    }
+
+    fn send(queue: &MessageQueue, payload: serde_json::Value) {
+        queue.push(ScriptMessage { script: "vlitz".to_string(), kind: MessageKind::Send(payload) });
+    }
+
+    #[test]
+    fn test_on_hook_runs_with_event_fields() {
+        let (mut executor, queue, backend) = event_executor();
+        send(&queue, json!({ "type": "functionHook", "id": "hook_0x7f000100", "event": "enter", "args": ["0x1", "0x2"] }));
+        send(&queue, json!({ "type": "functionHook", "id": "hook_0x7f000100", "event": "leave", "retval": "0x0" }));
+        send(&queue, json!({ "type": "functionHook", "id": "hook_0x7f000100", "event": "enter", "args": ["0x3"] }));
+
+        let script = "\
+$seen = []
+on hook 0x7f000100 enter
+  $seen = $seen + [$args[0]]
+end
+wait 0
+print ${len($seen)} $seen
+";
+        let (report, printed) = run(&mut executor, script);
+
+        assert!(report.is_success(), "{:?}", report.errors);
+        assert_eq!(printed[0], "Registered event handler #1: hook 0x7f000100 enter");
+        assert_eq!(printed[1], "2 [0x1, 0x3]");
+        assert_eq!(backend.calls()[0], ("hookFunction".to_string(), vec![json!("0x7f000100")]));
+    }
+
+    #[test]
+    fn test_handlers_persist_and_can_be_removed() {
+        let (mut executor, queue, backend) = event_executor();
+        let mut scripts = ScriptExecutor::new();
+        let report = scripts.execute(&mut executor, "on watch 0x1000 int\n  print changed to $newValue\nend\n", |_, _| {});
+        assert!(report.is_success(), "{:?}", report.errors);
+
+        send(&queue, json!({ "type": "memoryWatch", "watchId": 1, "address": "0x1000", "newValue": 7 }));
+        let mut printed = Vec::new();
+        scripts.dispatch_events(&mut executor, |_, result| {
+            if let CommandResult::Success(message) = result {
+                printed.push(message.clone());
+            }
+        });
+        assert_eq!(printed, vec!["changed to 7"]);
+        assert_eq!(scripts.handlers()[0].format(), "[1] watch 0x1000 (1 hits) at line 1");
+
+        let hook = scripts.execute(&mut executor, "on hook 0x2000\nend\non hook 0x2000 leave\nend\n", |_, _| {});
+        assert!(hook.is_success());
+        let remove = Command::parse("on remove 2").unwrap();
+        assert!(matches!(scripts.execute_handler_command(&executor, &remove), CommandResult::Success(_)));
+        assert_eq!(backend.call_count("unhookFunction"), 0);
+
+        let remove_all = Command::parse("on rm all").unwrap();
+        match scripts.execute_handler_command(&executor, &remove_all) {
+            CommandResult::Success(message) => assert_eq!(message, "Removed 2 event handlers"),
+            _ => panic!("expected success"),
+        }
+        assert_eq!(backend.call_count("unhookFunction"), 1);
+        assert!(scripts.handlers().is_empty());
+    }
+
+    #[test]
+    fn test_timer_handler_removes_itself() {
+        let mut executor = CommandExecutor::new(20);
+        let script = "\
+$ticks = 0
+on timer 1
+  $ticks = $ticks + 1
+  if $ticks == 3
+    on remove $handler
+  end
+end
+wait 50
+print ticks=$ticks
+on list
+";
+        let (report, printed) = run(&mut executor, script);
+
+        assert!(report.is_success(), "{:?}", report.errors);
+        assert_eq!(printed[printed.len() - 2], "ticks=3");
+        assert_eq!(printed[printed.len() - 1], "No event handlers");
+    }
+
+    #[test]
+    fn test_execute_runs_commands() {
+        let mut executor = mock_executor();
+        let mut outputs = Vec::new();
+
+        let report = ScriptExecutor::new().execute(&mut executor, "list module libc\nsel 0\n", |line, result| {
+            outputs.push((line.number, matches!(result, CommandResult::Success(_))));
+        });
+
+        assert!(report.is_success());
+        assert_eq!(report.executed, 2);
+        assert_eq!(outputs, vec![(1, true), (2, true)]);
+        assert_eq!(executor.get_prompt(), "vlitz:Module:libc.so>");
+    }
+
+    #[test]
+    fn test_stop_on_error() {
+        let mut executor = mock_executor();
+        let report = ScriptExecutor::new().execute(&mut executor, "list module\n\nfoo bar\nsel 0\n", |_, _| {});
+
+        assert!(report.stopped);
+        assert_eq!(report.executed, 2);
+        assert_eq!(report.errors[0].line, 3);
+        assert_eq!(report.errors[0].to_string(), "line 3: foo bar: Unknown command");
+        assert_eq!(executor.get_prompt(), "vlitz>");
+    }
+
+    #[test]
+    fn test_continue_on_error() {
+        let mut executor = mock_executor();
+        let report = ScriptExecutor::new()
+            .on_error(OnError::Continue)
+            .execute(&mut executor, "sel 5\nlist module\nsel 0\n", |_, _| {});
+
+        assert!(!report.stopped);
+        assert_eq!(report.executed, 3);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].line, 1);
+        assert_eq!(executor.get_prompt(), "vlitz:Module:libc.so>");
+    }
+
+    #[test]
+    fn test_cancel_stops_loop() {
+        let mut executor = CommandExecutor::new(20);
+        let cancel = executor.cancel_token().clone();
+        let mut printed = 0;
+        let report = ScriptExecutor::new()
+            .on_error(OnError::Continue)
+            .execute(&mut executor, "$i = 0\nwhile true\n  $i = $i + 1\n  print $i\nend\n", |_, _| {
+                printed += 1;
+                if printed == 3 {
+                    cancel.cancel();
+                }
+            });
+
+        assert!(report.stopped);
+        assert_eq!(printed, 3);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].to_string(), "line 2: while true: Cancelled");
+    }
+
+    #[test]
+    fn test_nested_run_relative_to_script() {
+        let dir = std::env::temp_dir().join(format!("vlitz-script-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("main.vzs"), "list module\nrun select.vzs\n").unwrap();
+        std::fs::write(dir.join("select.vzs"), "# nested\nsel 0\nsel 9\n").unwrap();
+
+        let mut executor = mock_executor();
+        let report = ScriptExecutor::new().execute_file(&mut executor, &dir.join("main.vzs"), |_, _| {});
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(report.executed, 3);
+        assert_eq!(report.errors[0].file.as_deref(), Some(dir.join("select.vzs").as_path()));
+        assert_eq!(report.errors[0].line, 3);
+    }
+
+    #[test]
+    fn test_command_preset_gets_options_as_variables() {
+        let dir = std::env::temp_dir().join(format!("vlitz-script-preset-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("pick.vzs"), "list module\nsel $index\nprint picked $index strict=$strict\n").unwrap();
+        std::fs::write(dir.join("unpick.vzs"), "unsel\n").unwrap();
+        std::fs::write(dir.join("pick.preset"), "name = pick\nscript = pick.vzs\nrevert = unpick.vzs\nparam index number 0\nparam strict bool false\n").unwrap();
+
+        let mut executor = mock_executor();
+        assert!(executor.presets_mut().load_dir(&dir).is_empty());
+        assert!(ScriptExecutor::handles(&executor, &Command::parse("preset apply pick").unwrap()));
+        assert!(!ScriptExecutor::handles(&executor, &Command::parse("preset apply antiroot").unwrap()));
+
+        let (report, printed) = run(&mut executor, "preset apply pick index=1\npreset apply pick\n");
+        assert_eq!(printed[2..], ["picked 1 strict=false".to_string(), "Applied preset pick".to_string()]);
+        assert_eq!(report.errors[0].message, "Failed to apply preset: Preset 'pick' is already applied");
+        assert_eq!(executor.get_prompt(), "vlitz:Module:libgame.so>");
+        assert!(executor.presets().is_applied("pick"));
+
+        let (report, printed) = run(&mut executor, "preset revert pick\n");
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(report.is_success());
+        assert_eq!(printed.last().unwrap(), "Reverted preset pick");
+        assert_eq!(executor.get_prompt(), "vlitz>");
+        assert!(!executor.presets().is_applied("pick"));
+    }
+
     #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
+    fn test_procedures_become_commands() {
+        let mut executor = CommandExecutor::new(20);
+        let mut scripts = ScriptExecutor::new();
+        let mut printed = Vec::new();
+        let mut collect = |_: &ScriptLine, result: &CommandResult| {
+            if let CommandResult::Success(message) = result {
+                printed.push(message.clone());
+            }
+        };
+
+        let report = scripts.execute(&mut executor, "proc greet $who\n  print hello $who\nend\nproc sav $x\nend\n", &mut collect);
+        assert!(report.is_success(), "{:?}", report.errors);
+        let greet = Command::parse("greet world").unwrap();
+        assert_eq!(executor.command_spec(&greet).unwrap().usage(), "greet <who>");
+        assert!(ScriptExecutor::handles(&executor, &greet));
+        // 내장 명령어와 겹치는 프로시저는 명령어로 등록되지 않음
+        assert!(!ScriptExecutor::handles(&executor, &Command::parse("sav 0").unwrap()));
+        assert!(matches!(executor.execute(&greet), CommandResult::Error(_)));
+
+        let report = scripts.execute(&mut executor, "greet world", &mut collect);
+        assert!(report.is_success(), "{:?}", report.errors);
+        assert_eq!(printed, vec!["hello world"]);
     }
-} 
\ No newline at end of file
+}
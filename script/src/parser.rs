@@ -1,14 +1,71 @@
+use std::fmt;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while, take_while1},
+    character::complete::{char, digit1, hex_digit1, space0},
+    combinator::{cut, map, map_res, opt, recognize},
+    error::{Error, ErrorKind},
+    multi::separated_list0,
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    IResult, Offset,
+};
+use crate::ast::*;
+
+type PResult<'a, T> = IResult<&'a str, T>;
+
+/// 스크립트 파싱 오류 (위치 포함)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub pos: Pos,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.pos, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl ParseError {
+    /// 블록 끝(`end`)이 빠진 오류
+    fn unclosed(pos: Pos, keyword: &str) -> Self {
+        ParseError { pos, message: format!("'{}' without matching 'end'", keyword) }
+    }
+
+    /// 블록이 `end`로 닫히지 않아 생긴 오류인지 (입력이 더 필요한 경우)
+    pub fn is_unclosed(&self) -> bool {
+        self.message.ends_with("without matching 'end'")
+    }
+}
+
 /// 스크립트 파일을 파싱하는 함수
-pub fn parse_script(content: &str) -> Result<Vec<String>, String> {
-    // 기본 구현 - 줄 단위로 명령어 반환
-    let commands = content
+///
+/// 한 줄에 한 문장을 쓰며, `#`으로 시작하는 줄은 주석이다.
+/// `if`/`while`/`for`/`proc`/`on` 블록은 `end`로 닫는다.
+pub fn parse_script(content: &str) -> Result<Script, ParseError> {
+    let lines = content
         .lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty() && !line.starts_with('#'))
-        .map(|line| line.to_string())
+        .enumerate()
+        .filter(|(_, line)| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#')
+        })
+        .map(|(idx, line)| (idx + 1, line))
         .collect();
-    
-    Ok(commands)
+
+    let mut parser = BlockParser { lines, next: 0, loop_depth: 0, in_proc: false };
+    let (body, terminator) = parser.block()?;
+    match terminator {
+        Some(terminator) => Err(terminator.unexpected()),
+        None => Ok(Script { body }),
+    }
+}
+
+/// 열린 블록이 아직 `end`로 닫히지 않은 입력인지 (REPL 여러 줄 입력용)
+pub fn is_incomplete(content: &str) -> bool {
+    parse_script(content).is_err_and(|e| e.is_unclosed())
 }
 
 /// 스크립트 파서 구조체
@@ -19,9 +76,573 @@ impl ScriptParser {
     pub fn new() -> Self {
         ScriptParser
     }
-    
+
     /// 스크립트 내용 파싱
-    pub fn parse(&self, content: &str) -> Result<Vec<String>, String> {
+    pub fn parse(&self, content: &str) -> Result<Script, ParseError> {
         parse_script(content)
     }
-} 
\ No newline at end of file
+}
+
+/// 블록을 닫는 줄
+enum Terminator {
+    End(Pos),
+    Else(Pos),
+    Elif(Pos, Expr),
+}
+
+impl Terminator {
+    fn unexpected(&self) -> ParseError {
+        let (pos, keyword) = match self {
+            Terminator::End(pos) => (*pos, "end"),
+            Terminator::Else(pos) => (*pos, "else"),
+            Terminator::Elif(pos, _) => (*pos, "elif"),
+        };
+        ParseError { pos, message: format!("Unexpected '{}'", keyword) }
+    }
+}
+
+/// 줄 단위 블록 파서
+struct BlockParser<'a> {
+    /// (줄 번호, 원본 줄)
+    lines: Vec<(usize, &'a str)>,
+    next: usize,
+    loop_depth: usize,
+    in_proc: bool,
+}
+
+impl<'a> BlockParser<'a> {
+    /// 블록 종료 줄(또는 파일 끝)까지 문장 파싱
+    fn block(&mut self) -> Result<(Vec<Stmt>, Option<Terminator>), ParseError> {
+        let mut body = Vec::new();
+
+        while self.next < self.lines.len() {
+            let (number, line) = self.lines[self.next];
+            self.next += 1;
+
+            let text = line.trim();
+            let pos = Pos::new(number, line.offset(text) + 1);
+            let ctx = LineContext { number, line };
+            let (keyword, rest) = split_keyword(text);
+
+            let kind = match keyword {
+                "end" => return ctx.no_trailing(rest, "end").map(|_| (body, Some(Terminator::End(pos)))),
+                "else" => {
+                    let (next_keyword, condition) = split_keyword(rest);
+                    if next_keyword == "if" {
+                        let condition = ctx.expr(condition)?;
+                        return Ok((body, Some(Terminator::Elif(pos, condition))));
+                    }
+                    ctx.no_trailing(rest, "else")?;
+                    return Ok((body, Some(Terminator::Else(pos))));
+                },
+                "elif" => return Ok((body, Some(Terminator::Elif(pos, ctx.expr(rest)?)))),
+                "if" => self.parse_if(&ctx, pos, rest)?,
+                "while" => {
+                    let condition = ctx.expr(rest)?;
+                    let body = self.loop_body(pos, "while")?;
+                    StmtKind::While { condition, body }
+                },
+                "for" => self.parse_for(&ctx, pos, rest)?,
+                "proc" => self.parse_proc(&ctx, pos, rest)?,
+                "return" => {
+                    if !self.in_proc {
+                        return Err(ctx.error(text, "'return' outside of proc"));
+                    }
+                    let value = if rest.is_empty() { None } else { Some(ctx.expr(rest)?) };
+                    StmtKind::Return(value)
+                },
+                "break" | "continue" => {
+                    ctx.no_trailing(rest, keyword)?;
+                    if self.loop_depth == 0 {
+                        return Err(ctx.error(text, &format!("'{}' outside of loop", keyword)));
+                    }
+                    if keyword == "break" { StmtKind::Break } else { StmtKind::Continue }
+                },
+                "print" => StmtKind::Print(ctx.template(rest, false)?),
+                "wait" => StmtKind::Wait(ctx.expr(rest)?),
+                "on" if matches!(split_keyword(rest).0, "hook" | "watch" | "timer") => self.parse_on(&ctx, pos, rest)?,
+                _ => match assignment(text) {
+                    Ok((value, name)) => StmtKind::Assign { value: ctx.assign_value(value)?, name: name.to_string() },
+                    Err(_) => StmtKind::Command(ctx.command(text)?),
+                },
+            };
+
+            body.push(Stmt { pos, source: text.to_string(), kind });
+        }
+
+        Ok((body, None))
+    }
+
+    /// 블록 파싱 후 `end`로 닫혔는지 확인
+    fn closed_block(&mut self, open: Pos, keyword: &str) -> Result<Vec<Stmt>, ParseError> {
+        match self.block()? {
+            (body, Some(Terminator::End(_))) => Ok(body),
+            (_, Some(terminator)) => Err(terminator.unexpected()),
+            (_, None) => Err(ParseError::unclosed(open, keyword)),
+        }
+    }
+
+    fn loop_body(&mut self, open: Pos, keyword: &str) -> Result<Vec<Stmt>, ParseError> {
+        self.loop_depth += 1;
+        let body = self.closed_block(open, keyword);
+        self.loop_depth -= 1;
+        body
+    }
+
+    fn parse_if(&mut self, ctx: &LineContext<'a>, pos: Pos, rest: &str) -> Result<StmtKind, ParseError> {
+        let mut branches = Vec::new();
+        let mut condition = ctx.expr(rest)?;
+
+        loop {
+            let (body, terminator) = self.block()?;
+            branches.push((condition, body));
+
+            match terminator {
+                Some(Terminator::End(_)) => return Ok(StmtKind::If { branches, otherwise: None }),
+                Some(Terminator::Elif(_, next)) => condition = next,
+                Some(Terminator::Else(else_pos)) => {
+                    let otherwise = self.closed_block(else_pos, "else")?;
+                    return Ok(StmtKind::If { branches, otherwise: Some(otherwise) });
+                },
+                None => return Err(ParseError::unclosed(pos, "if")),
+            }
+        }
+    }
+
+    /// `for [each] $x in <식>`
+    fn parse_for(&mut self, ctx: &LineContext<'a>, pos: Pos, rest: &str) -> Result<StmtKind, ParseError> {
+        let header = tuple((
+            opt(terminated(tag("each"), space0)),
+            variable,
+            delimited(space0, tag("in"), space0),
+        ))(rest);
+
+        let (iterable, var) = match header {
+            Ok((iterable, (_, var, _))) if !iterable.is_empty() => (iterable, var.to_string()),
+            Ok((iterable, _)) => return Err(ctx.error(iterable, "Expected expression after 'in'")),
+            Err(_) => return Err(ctx.error(rest, "Expected 'for each $var in <expr>'")),
+        };
+        let iterable = ctx.expr(iterable)?;
+        let body = self.loop_body(pos, "for")?;
+        Ok(StmtKind::ForEach { var, iterable, body })
+    }
+
+    /// `on hook <식> [enter|leave]`, `on watch <식> [타입]`, `on timer <식>`
+    fn parse_on(&mut self, ctx: &LineContext<'a>, pos: Pos, rest: &str) -> Result<StmtKind, ParseError> {
+        let (kind, header) = split_keyword(rest);
+        let trigger = match kind {
+            "hook" => {
+                let (target, phase) = ctx.expr_with_word(header)?;
+                let phase = match phase {
+                    None => None,
+                    Some("enter") => Some(HookPhase::Enter),
+                    Some("leave") => Some(HookPhase::Leave),
+                    Some(other) => return Err(ctx.error(other, &format!("Expected 'enter' or 'leave', got '{}'", other))),
+                };
+                Trigger::Hook { target, phase }
+            },
+            "watch" => {
+                let (target, memory_type) = ctx.expr_with_word(header)?;
+                Trigger::Watch { target, memory_type: memory_type.map(str::to_string) }
+            },
+            _ => Trigger::Timer { interval: ctx.expr(header)? },
+        };
+
+        // 핸들러 본문은 프로시저처럼 실행되므로 return으로 빠져나올 수 있다
+        let saved = (std::mem::replace(&mut self.loop_depth, 0), std::mem::replace(&mut self.in_proc, true));
+        let body = self.closed_block(pos, "on");
+        (self.loop_depth, self.in_proc) = saved;
+
+        Ok(StmtKind::On { trigger, body: body? })
+    }
+
+    /// `proc name $a $b`
+    fn parse_proc(&mut self, ctx: &LineContext<'a>, pos: Pos, rest: &str) -> Result<StmtKind, ParseError> {
+        let (params, name) = identifier(rest).map_err(|_| ctx.error(rest, "Expected procedure name"))?;
+
+        let mut names = Vec::new();
+        for param in params.split_whitespace() {
+            match variable(param) {
+                Ok(("", name)) if !names.contains(&name.to_string()) => names.push(name.to_string()),
+                Ok(("", name)) => return Err(ctx.error(param, &format!("Duplicate parameter '${}'", name))),
+                _ => return Err(ctx.error(param, &format!("Invalid parameter '{}'", param))),
+            }
+        }
+
+        if self.in_proc {
+            return Err(ctx.error(rest, "Nested proc definitions are not allowed"));
+        }
+        let saved_depth = std::mem::replace(&mut self.loop_depth, 0);
+        self.in_proc = true;
+        let body = self.closed_block(pos, "proc");
+        self.in_proc = false;
+        self.loop_depth = saved_depth;
+
+        Ok(StmtKind::Proc { name: name.to_string(), params: names, body: body? })
+    }
+}
+
+/// 한 줄 안의 위치 계산과 하위 파서 호출
+struct LineContext<'a> {
+    number: usize,
+    line: &'a str,
+}
+
+impl<'a> LineContext<'a> {
+    /// 줄 안의 부분 문자열 위치에서 오류 생성
+    fn error(&self, at: &str, message: &str) -> ParseError {
+        let column = if at.is_empty() {
+            self.line.trim_end().len() + 1
+        } else {
+            self.line.offset(at) + 1
+        };
+        ParseError { pos: Pos::new(self.number, column), message: message.to_string() }
+    }
+
+    fn no_trailing(&self, rest: &str, keyword: &str) -> Result<(), ParseError> {
+        if rest.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(rest, &format!("Unexpected '{}' after '{}'", rest, keyword)))
+        }
+    }
+
+    /// 남는 입력 없이 식 전체 파싱
+    fn expr(&self, input: &str) -> Result<Expr, ParseError> {
+        if input.trim().is_empty() {
+            return Err(self.error(input, "Expected expression"));
+        }
+        match delimited(space0, expr, space0)(input) {
+            Ok(("", parsed)) => Ok(parsed),
+            Ok((rest, _)) => Err(self.error(rest, &format!("Unexpected '{}'", rest))),
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(self.nom_error(e)),
+            Err(nom::Err::Incomplete(_)) => Err(self.error(input, "Incomplete expression")),
+        }
+    }
+
+    /// 식 뒤에 선택적인 단어 하나 (예: `$addr float`)
+    fn expr_with_word(&self, input: &'a str) -> Result<(Expr, Option<&'a str>), ParseError> {
+        if input.trim().is_empty() {
+            return Err(self.error(input, "Expected expression"));
+        }
+        match terminated(expr, space0)(input) {
+            Ok(("", parsed)) => Ok((parsed, None)),
+            Ok((rest, parsed)) => match identifier(rest) {
+                Ok(("", word)) => Ok((parsed, Some(word))),
+                _ => Err(self.error(rest, &format!("Unexpected '{}'", rest))),
+            },
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(self.nom_error(e)),
+            Err(nom::Err::Incomplete(_)) => Err(self.error(input, "Incomplete expression")),
+        }
+    }
+
+    fn nom_error(&self, e: Error<&str>) -> ParseError {
+        if e.input.is_empty() {
+            self.error(e.input, "Unexpected end of expression")
+        } else {
+            let token = e.input.split_whitespace().next().unwrap_or(e.input);
+            self.error(e.input, &format!("Invalid expression near '{}'", token))
+        }
+    }
+
+    fn template(&self, input: &str, string_escapes: bool) -> Result<Template, ParseError> {
+        match template(input, None, string_escapes) {
+            Ok((_, parsed)) => Ok(parsed),
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(self.nom_error(e)),
+            Err(nom::Err::Incomplete(_)) => Err(self.error(input, "Incomplete text")),
+        }
+    }
+
+    /// 대입문 오른쪽: 식 전체로 해석되면 식, 아니면 결과를 저장할 명령어
+    fn assign_value(&self, input: &str) -> Result<AssignValue, ParseError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(self.error(input, "Expected expression or command after '='"));
+        }
+        match delimited(space0, expr, space0)(input) {
+            Ok(("", parsed)) => Ok(AssignValue::Expr(parsed)),
+            _ => self.command(input).map(AssignValue::Command),
+        }
+    }
+
+    fn command(&self, input: &str) -> Result<CommandLine, ParseError> {
+        let text = self.template(input, false)?;
+        let mut words = split_words(input).into_iter();
+        let name = words.next().unwrap_or_default().to_string();
+
+        // 프로시저 호출 인자: 식으로 해석되는 단어는 식, 아니면 문자열
+        let args = words
+            .map(|word| match terminated(expr, space0)(word) {
+                Ok(("", parsed)) => Ok(parsed),
+                _ => self.template(word, false).map(Expr::Text),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(CommandLine { name, text, args })
+    }
+}
+
+/// 줄의 첫 단어와 나머지 분리
+fn split_keyword(text: &str) -> (&str, &str) {
+    match text.find(char::is_whitespace) {
+        Some(idx) => (&text[..idx], text[idx..].trim_start()),
+        None => (text, ""),
+    }
+}
+
+/// 따옴표를 고려한 공백 단위 분리 (따옴표는 유지)
+fn split_words(input: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (idx, c) in input.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if let Some(begin) = start.take() {
+                    words.push(&input[begin..idx]);
+                }
+                continue;
+            },
+            _ => {},
+        }
+        start.get_or_insert(idx);
+    }
+    if let Some(begin) = start {
+        words.push(&input[begin..]);
+    }
+    words
+}
+
+/// `$name = ...` (나머지 입력과 변수 이름 반환)
+fn assignment(input: &str) -> PResult<'_, &str> {
+    terminated(
+        variable,
+        tuple((space0, char('='), nom::combinator::not(char('=')), space0)),
+    )(input)
+}
+
+fn identifier(input: &str) -> PResult<'_, &str> {
+    recognize(pair(
+        take_while1(|c: char| c.is_ascii_alphabetic() || c == '_'),
+        take_while(|c: char| c.is_ascii_alphanumeric() || c == '_'),
+    ))(input)
+}
+
+fn variable(input: &str) -> PResult<'_, &str> {
+    preceded(char('$'), identifier)(input)
+}
+
+/// 식 파서 (우선순위: or < and < 비교 < 덧셈 < 곱셈 < 단항 < 후위)
+pub fn expr(input: &str) -> PResult<'_, Expr> {
+    binary_level(input, and_expr, &[("||", BinaryOp::Or), ("or", BinaryOp::Or)])
+}
+
+fn and_expr(input: &str) -> PResult<'_, Expr> {
+    binary_level(input, comparison, &[("&&", BinaryOp::And), ("and", BinaryOp::And)])
+}
+
+fn comparison(input: &str) -> PResult<'_, Expr> {
+    binary_level(input, additive, &[
+        ("==", BinaryOp::Eq),
+        ("!=", BinaryOp::Ne),
+        ("<=", BinaryOp::Le),
+        (">=", BinaryOp::Ge),
+        ("<", BinaryOp::Lt),
+        (">", BinaryOp::Gt),
+    ])
+}
+
+fn additive(input: &str) -> PResult<'_, Expr> {
+    binary_level(input, multiplicative, &[("+", BinaryOp::Add), ("-", BinaryOp::Sub)])
+}
+
+fn multiplicative(input: &str) -> PResult<'_, Expr> {
+    binary_level(input, unary, &[("*", BinaryOp::Mul), ("/", BinaryOp::Div), ("%", BinaryOp::Rem)])
+}
+
+/// 왼쪽 결합 이항 연산자 한 단계
+fn binary_level<'a>(
+    input: &'a str,
+    operand: fn(&'a str) -> PResult<'a, Expr>,
+    operators: &[(&'static str, BinaryOp)],
+) -> PResult<'a, Expr> {
+    let (mut input, mut lhs) = operand(input)?;
+
+    loop {
+        let (after_space, _) = space0(input)?;
+        let matched = operators.iter().find(|(symbol, _)| {
+            after_space.starts_with(symbol) && !(symbol.chars().all(char::is_alphabetic)
+                && after_space[symbol.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+        });
+        let Some((symbol, op)) = matched else {
+            return Ok((input, lhs));
+        };
+
+        let (rest, rhs) = cut(preceded(space0, operand))(&after_space[symbol.len()..])?;
+        lhs = Expr::Binary(*op, Box::new(lhs), Box::new(rhs));
+        input = rest;
+    }
+}
+
+fn unary(input: &str) -> PResult<'_, Expr> {
+    alt((
+        map(preceded(terminated(char('-'), space0), unary), |e| Expr::Unary(UnaryOp::Neg, Box::new(e))),
+        map(preceded(terminated(char('!'), space0), unary), |e| Expr::Unary(UnaryOp::Not, Box::new(e))),
+        map(preceded(pair(keyword("not"), space0), unary), |e| Expr::Unary(UnaryOp::Not, Box::new(e))),
+        postfix,
+    ))(input)
+}
+
+/// 인덱스 접근 (`$list[0]`)
+fn postfix(input: &str) -> PResult<'_, Expr> {
+    let (mut input, mut target) = primary(input)?;
+    while let Ok((rest, index)) = delimited(pair(char('['), space0), expr, cut(pair(space0, char(']'))))(input) {
+        target = Expr::Index(Box::new(target), Box::new(index));
+        input = rest;
+    }
+    Ok((input, target))
+}
+
+fn primary(input: &str) -> PResult<'_, Expr> {
+    alt((
+        number,
+        map(delimited(char('"'), |i| template(i, Some('"'), true), cut(char('"'))), Expr::Text),
+        map(variable, |name| Expr::Var(name.to_string())),
+        delimited(pair(char('('), space0), expr, cut(pair(space0, char(')')))),
+        map(
+            delimited(pair(char('['), space0), separated_list0(delimited(space0, char(','), space0), expr), cut(pair(space0, char(']')))),
+            Expr::List,
+        ),
+        selector,
+        call,
+        word,
+    ))(input)
+}
+
+fn number(input: &str) -> PResult<'_, Expr> {
+    alt((
+        map_res(preceded(alt((tag("0x"), tag("0X"))), hex_digit1), |hex| u64::from_str_radix(hex, 16).map(Expr::Address)),
+        map_res(recognize(tuple((digit1, char('.'), digit1))), |float: &str| float.parse().map(Expr::Float)),
+        map_res(digit1, |int: &str| int.parse().map(Expr::Int)),
+    ))(input)
+}
+
+/// 저장소 선택자 (`lib:all`, `log:0-3`, `lib:1,4`, `log:-1`, `lib:@main`, `lib:#hot`, `nav:0`)
+fn selector(input: &str) -> PResult<'_, Expr> {
+    map(
+        recognize(pair(
+            alt((tag("lib:"), tag("log:"), tag("nav:"))),
+            take_while1(|c: char| c.is_ascii_alphanumeric() || matches!(c, ',' | '-' | '@' | '#' | '_')),
+        )),
+        |s: &str| Expr::Selector(s.to_string()),
+    )(input)
+}
+
+/// 내장 함수 호출 (`len($x)`)
+fn call(input: &str) -> PResult<'_, Expr> {
+    map(
+        pair(
+            terminated(identifier, pair(char('('), space0)),
+            cut(terminated(separated_list0(delimited(space0, char(','), space0), expr), pair(space0, char(')')))),
+        ),
+        |(name, args)| Expr::Call(name.to_string(), args),
+    )(input)
+}
+
+/// 예약어 리터럴 (true, false, nil, sel)
+fn word(input: &str) -> PResult<'_, Expr> {
+    let (rest, name) = identifier(input)?;
+    let parsed = match name {
+        "true" => Expr::Bool(true),
+        "false" => Expr::Bool(false),
+        "nil" => Expr::Nil,
+        "sel" => Expr::Selected,
+        _ => return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag))),
+    };
+    Ok((rest, parsed))
+}
+
+fn keyword<'a>(name: &'static str) -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    move |input: &'a str| {
+        let (rest, ident) = identifier(input)?;
+        if ident == name {
+            Ok((rest, ident))
+        } else {
+            Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)))
+        }
+    }
+}
+
+/// 변수 치환 텍스트
+///
+/// `$name`은 변수, `${식}`은 식 결과로 치환된다. `\$`는 `$` 문자 그대로 남는다.
+/// 문자열 리터럴에서는 `\n`, `\t`, `\"`, `\\` 이스케이프도 처리한다.
+/// 명령어 텍스트에서는 그 외 역슬래시를 그대로 두어 명령어 파서가 처리하게 한다.
+fn template(input: &str, terminator: Option<char>, string_escapes: bool) -> PResult<'_, Template> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut rest = input;
+
+    while let Some(c) = rest.chars().next() {
+        if Some(c) == terminator {
+            break;
+        }
+
+        if c == '\\' {
+            let escaped = rest[1..].chars().next();
+            match escaped {
+                Some('$') => literal.push('$'),
+                Some('n') if string_escapes => literal.push('\n'),
+                Some('t') if string_escapes => literal.push('\t'),
+                Some(other) if string_escapes => literal.push(other),
+                Some(other) => {
+                    literal.push('\\');
+                    literal.push(other);
+                },
+                None => {
+                    literal.push('\\');
+                    rest = &rest[1..];
+                    continue;
+                },
+            }
+            rest = &rest[1 + escaped.map_or(0, char::len_utf8)..];
+            continue;
+        }
+
+        if c == '$' {
+            let substitution = alt((
+                delimited(pair(tag("${"), space0), cut(expr), cut(pair(space0, char('}')))),
+                map(variable, |name| Expr::Var(name.to_string())),
+            ))(rest);
+
+            match substitution {
+                Ok((after, parsed)) => {
+                    if !literal.is_empty() {
+                        parts.push(TextPart::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(TextPart::Expr(parsed));
+                    rest = after;
+                    continue;
+                },
+                Err(nom::Err::Failure(e)) => return Err(nom::Err::Failure(e)),
+                Err(_) => {},
+            }
+        }
+
+        literal.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    if !literal.is_empty() {
+        parts.push(TextPart::Literal(literal));
+    }
+    Ok((rest, Template(parts)))
+}
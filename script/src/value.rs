@@ -0,0 +1,251 @@
+use std::cmp::Ordering;
+use std::fmt;
+use vlitz_shared::VzData;
+use vlitz_shared::utils::addr_to_hex;
+use crate::ast::BinaryOp;
+
+/// 스크립트 값
+#[derive(Debug, Clone)]
+pub enum Value {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Address(u64),
+    Float(f64),
+    Str(String),
+    /// 저장소/내비게이터 항목
+    Data(DataValue),
+    List(Vec<Value>),
+}
+
+/// 저장소 항목 값
+#[derive(Debug, Clone)]
+pub struct DataValue {
+    pub data: VzData,
+    /// 명령어에 넘길 때 쓰는 선택자 (예: `lib:3`)
+    pub reference: Option<String>,
+}
+
+impl Value {
+    /// 에이전트 메시지 필드 변환
+    ///
+    /// `0x`로 시작하는 문자열은 주소로, 객체는 JSON 문자열로 바꾼다.
+    pub fn from_json(value: &serde_json::Value) -> Value {
+        match value {
+            serde_json::Value::Null => Value::Nil,
+            serde_json::Value::Bool(b) => Value::Bool(*b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => Value::Int(i),
+                None => Value::Float(n.as_f64().unwrap_or_default()),
+            },
+            serde_json::Value::String(s) => match s.strip_prefix("0x").map(|hex| u64::from_str_radix(hex, 16)) {
+                Some(Ok(address)) => Value::Address(address),
+                _ => Value::Str(s.clone()),
+            },
+            serde_json::Value::Array(items) => Value::List(items.iter().map(Value::from_json).collect()),
+            object => Value::Str(object.to_string()),
+        }
+    }
+
+    /// 값 종류 이름 (오류 메시지용)
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Nil => "nil",
+            Value::Bool(_) => "bool",
+            Value::Int(_) => "int",
+            Value::Address(_) => "address",
+            Value::Float(_) => "float",
+            Value::Str(_) => "string",
+            Value::Data(_) => "data",
+            Value::List(_) => "list",
+        }
+    }
+
+    /// 조건식 판정
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Nil => false,
+            Value::Bool(b) => *b,
+            Value::Int(i) => *i != 0,
+            Value::Address(a) => *a != 0,
+            Value::Float(f) => *f != 0.0,
+            Value::Str(s) => !s.is_empty(),
+            Value::Data(_) => true,
+            Value::List(items) => !items.is_empty(),
+        }
+    }
+
+    /// 주소로 해석 (주소 연산, addr())
+    pub fn as_address(&self) -> Option<u64> {
+        match self {
+            Value::Address(a) => Some(*a),
+            Value::Int(i) => u64::try_from(*i).ok(),
+            Value::Data(d) => d.data.get_address(),
+            Value::Str(s) => vlitz_shared::utils::hex_to_addr(s).ok(),
+            _ => None,
+        }
+    }
+
+    /// 명령어 인자로 치환할 때의 문자열
+    ///
+    /// 저장소 항목은 선택자로, 주소를 가진 항목은 주소로 바뀐다.
+    /// 공백이 있는 문자열은 따옴표로 감싼다.
+    pub fn to_arg(&self) -> String {
+        match self {
+            Value::Str(s) if s.is_empty() || s.contains(char::is_whitespace) || s.contains('"') => {
+                format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+            },
+            Value::Data(d) => match (&d.reference, d.data.get_address()) {
+                (Some(reference), _) => reference.clone(),
+                (None, Some(address)) => addr_to_hex(address),
+                (None, None) => Value::Str(d.data.get_name().unwrap_or_default().to_string()).to_arg(),
+            },
+            Value::List(items) => items.iter().map(Value::to_arg).collect::<Vec<_>>().join(" "),
+            other => other.to_string(),
+        }
+    }
+
+    /// 동등 비교 (숫자끼리는 종류와 무관하게 값 비교)
+    pub fn loose_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Nil, Value::Nil) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Str(a), Value::Str(b)) => a == b,
+            (Value::List(a), Value::List(b)) => a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.loose_eq(y)),
+            (Value::Data(a), Value::Data(b)) => a.data.get_display_name() == b.data.get_display_name(),
+            (Value::Data(_), _) | (_, Value::Data(_)) => match (self.as_address(), other.as_address()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+            _ => self.compare(other) == Some(Ordering::Equal),
+        }
+    }
+
+    /// 크기 비교 (숫자/주소/문자열)
+    pub fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Str(a), Value::Str(b)) => Some(a.cmp(b)),
+            (Value::Int(a), Value::Int(b)) => Some(a.cmp(b)),
+            (Value::Address(a), Value::Address(b)) => Some(a.cmp(b)),
+            (Value::Address(a), Value::Int(b)) => Some(i128::from(*a).cmp(&i128::from(*b))),
+            (Value::Int(a), Value::Address(b)) => Some(i128::from(*a).cmp(&i128::from(*b))),
+            _ => self.as_float()?.partial_cmp(&other.as_float()?),
+        }
+    }
+
+    fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Int(i) => Some(*i as f64),
+            Value::Address(a) => Some(*a as f64),
+            Value::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// 산술 연산
+    ///
+    /// 주소 ± 정수는 주소, 주소 - 주소는 정수가 된다.
+    /// 주소를 가진 저장소 항목은 주소로 취급한다.
+    /// 문자열 + 값은 이어 붙이고, 리스트 + 리스트는 합친다.
+    pub fn arithmetic(&self, op: BinaryOp, other: &Value) -> Result<Value, String> {
+        let overflow = || format!("Arithmetic overflow in {} {} {}", self, op.symbol(), other);
+        let unsupported = || format!("Cannot apply '{}' to {} and {}", op.symbol(), self.type_name(), other.type_name());
+
+        match (self, other) {
+            (Value::Str(a), b) if op == BinaryOp::Add => return Ok(Value::Str(format!("{}{}", a, b))),
+            (Value::List(a), Value::List(b)) if op == BinaryOp::Add => {
+                return Ok(Value::List(a.iter().chain(b).cloned().collect()));
+            },
+            _ => {},
+        }
+
+        let lhs = self.as_number().ok_or_else(unsupported)?;
+        let rhs = other.as_number().ok_or_else(unsupported)?;
+
+        match (lhs, rhs) {
+            (Value::Int(a), Value::Int(b)) => {
+                let result = match op {
+                    BinaryOp::Add => a.checked_add(b),
+                    BinaryOp::Sub => a.checked_sub(b),
+                    BinaryOp::Mul => a.checked_mul(b),
+                    BinaryOp::Div | BinaryOp::Rem if b == 0 => return Err("Division by zero".to_string()),
+                    BinaryOp::Div => a.checked_div(b),
+                    BinaryOp::Rem => a.checked_rem(b),
+                    _ => return Err(unsupported()),
+                };
+                result.map(Value::Int).ok_or_else(overflow)
+            },
+            (Value::Address(a), Value::Int(b)) => {
+                let result = match op {
+                    BinaryOp::Add => a.checked_add_signed(b),
+                    BinaryOp::Sub => b.checked_neg().and_then(|b| a.checked_add_signed(b)),
+                    BinaryOp::Mul => u64::try_from(b).ok().and_then(|b| a.checked_mul(b)),
+                    BinaryOp::Div | BinaryOp::Rem if b == 0 => return Err("Division by zero".to_string()),
+                    BinaryOp::Div => u64::try_from(b).ok().map(|b| a / b),
+                    BinaryOp::Rem => u64::try_from(b).ok().map(|b| a % b),
+                    _ => return Err(unsupported()),
+                };
+                result.map(Value::Address).ok_or_else(overflow)
+            },
+            (Value::Address(a), Value::Address(b)) if op == BinaryOp::Sub => {
+                let diff = i128::from(a) - i128::from(b);
+                i64::try_from(diff).map(Value::Int).map_err(|_| overflow())
+            },
+            // 16진수 리터럴 오프셋도 주소 값이므로 주소끼리의 덧셈/곱셈은 주소로 계산
+            (Value::Address(a), Value::Address(b)) => {
+                let result = match op {
+                    BinaryOp::Add => a.checked_add(b),
+                    BinaryOp::Mul => a.checked_mul(b),
+                    BinaryOp::Div | BinaryOp::Rem if b == 0 => return Err("Division by zero".to_string()),
+                    BinaryOp::Div => Some(a / b),
+                    BinaryOp::Rem => Some(a % b),
+                    _ => return Err(unsupported()),
+                };
+                result.map(Value::Address).ok_or_else(overflow)
+            },
+            (Value::Int(a), Value::Address(b)) => match op {
+                BinaryOp::Add => b.checked_add_signed(a).map(Value::Address).ok_or_else(overflow),
+                BinaryOp::Mul => u64::try_from(a).ok().and_then(|a| a.checked_mul(b)).map(Value::Address).ok_or_else(overflow),
+                _ => Err(unsupported()),
+            },
+            (a, b) => {
+                let (a, b) = (a.as_float().unwrap_or_default(), b.as_float().unwrap_or_default());
+                match op {
+                    BinaryOp::Add => Ok(Value::Float(a + b)),
+                    BinaryOp::Sub => Ok(Value::Float(a - b)),
+                    BinaryOp::Mul => Ok(Value::Float(a * b)),
+                    BinaryOp::Div => Ok(Value::Float(a / b)),
+                    BinaryOp::Rem => Ok(Value::Float(a % b)),
+                    _ => Err(unsupported()),
+                }
+            },
+        }
+    }
+
+    /// 산술 연산용 숫자 값 (저장소 항목은 주소)
+    fn as_number(&self) -> Option<Value> {
+        match self {
+            Value::Int(_) | Value::Address(_) | Value::Float(_) => Some(self.clone()),
+            Value::Data(d) => d.data.get_address().map(Value::Address),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Nil => write!(f, "nil"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Address(a) => write!(f, "{}", addr_to_hex(*a)),
+            Value::Float(v) => write!(f, "{}", v),
+            Value::Str(s) => write!(f, "{}", s),
+            Value::Data(d) => write!(f, "[{}] {}", d.data.data_type, d.data.get_display_name()),
+            Value::List(items) => {
+                let items: Vec<String> = items.iter().map(Value::to_string).collect();
+                write!(f, "[{}]", items.join(", "))
+            },
+        }
+    }
+}
@@ -23,9 +23,15 @@ pub enum VlitzError {
     #[error("셀렉터 오류: {0}")]
     Selector(String),
 
+    #[error("프리셋 오류: {0}")]
+    Preset(String),
+
     #[error("타입 변환 오류: {0}")]
     TypeConversion(String),
 
+    #[error("작업 취소됨")]
+    Cancelled,
+
     #[error("I/O 오류: {0}")]
     Io(#[from] std::io::Error),
 }
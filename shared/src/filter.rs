@@ -1,7 +1,7 @@
 use std::str::FromStr;
 use regex::Regex;
 use crate::vzdata::{VzData, VzDataType};
-use crate::memory::MemoryType;
+use crate::memory::{MemoryType, MemoryValue};
 use crate::error::{VlitzError, VlitzResult};
 
 /// 필터 명령에서 사용하는 연산자
@@ -33,6 +33,9 @@ impl FromStr for FilterOperator {
     }
 }
 
+/// 필드 조건에서 쓸 수 있는 필드 이름
+pub const FILTER_FIELDS: &[&str] = &["type", "label", "tags", "name", "address", "class_name", "size", "protection"];
+
 /// 필터 표현식 조건의 종류
 #[derive(Debug, Clone)]
 pub enum FilterCondition {
@@ -53,21 +56,40 @@ pub enum FilterCondition {
     Or(Box<FilterCondition>, Box<FilterCondition>),
 }
 
+/// 메모리 데이터 조건에 쓸 값을 읽는 함수 (읽을 수 없으면 None)
+pub type MemoryReader<'a> = dyn FnMut(&VzData, MemoryType) -> Option<MemoryValue> + 'a;
+
 impl FilterCondition {
-    /// VzData에 조건을 적용하여 필터링
+    /// VzData에 조건을 적용하여 필터링 (메모리 데이터 조건은 항상 거짓)
     pub fn apply(&self, data: &VzData) -> bool {
+        self.apply_with(data, &mut |_, _| None)
+    }
+
+    /// 메모리 데이터 조건의 값을 `read`로 읽어 조건 적용
+    pub fn apply_with(&self, data: &VzData, read: &mut MemoryReader) -> bool {
         match self {
             FilterCondition::Field { field_name, operator, value } => {
                 Self::check_field(data, field_name, operator, value)
             },
             FilterCondition::MemoryData { memory_type, operator, value } => {
-                Self::check_memory_data(data, memory_type, operator, value)
+                Self::check_memory_data(data, *memory_type, operator, value, read)
             },
             FilterCondition::And(cond1, cond2) => {
-                cond1.apply(data) && cond2.apply(data)
+                cond1.apply_with(data, read) && cond2.apply_with(data, read)
             },
             FilterCondition::Or(cond1, cond2) => {
-                cond1.apply(data) || cond2.apply(data)
+                cond1.apply_with(data, read) || cond2.apply_with(data, read)
+            },
+        }
+    }
+
+    /// 메모리 값을 읽어야 하는 조건이 있는지
+    pub fn reads_memory(&self) -> bool {
+        match self {
+            FilterCondition::Field { .. } => false,
+            FilterCondition::MemoryData { .. } => true,
+            FilterCondition::And(cond1, cond2) | FilterCondition::Or(cond1, cond2) => {
+                cond1.reads_memory() || cond2.reads_memory()
             },
         }
     }
@@ -155,21 +177,43 @@ impl FilterCondition {
     }
 
     /// 메모리 데이터 필터링 (포인터 전용)
-    fn check_memory_data(data: &VzData, _memory_type: &MemoryType, _operator: &FilterOperator, _value: &str) -> bool {
-        // 실제 구현에서는 Frida API를 사용하여 메모리의 값을 읽고 비교
-        // 이 예제에서는 간단한 구현만 제공
-        // (실제 구현은 runtime 모듈에서 메모리 접근 후 필터링)
-        
-        // 1. 해당 데이터가 포인터인지 확인
+    fn check_memory_data(data: &VzData, memory_type: MemoryType, operator: &FilterOperator, value: &str, read: &mut MemoryReader) -> bool {
         if data.as_pointer().is_none() {
             return false;
         }
+        let Ok(expected) = MemoryValue::parse(value, memory_type) else {
+            return false;
+        };
+        match read(data, memory_type) {
+            Some(actual) => Self::compare_memory(&actual, operator, &expected),
+            None => false,
+        }
+    }
 
-        // 2. 메모리에서 해당 타입의 값을 읽어옴 (이 예제에서는 생략)
-        // 3. 읽은 값을 주어진 값과 비교
-        
-        // 단순화 버전: 항상 false 반환 (실제 구현에서는 메모리 값 비교)
-        false
+    /// 같은 타입의 메모리 값 비교
+    fn compare_memory(a: &MemoryValue, operator: &FilterOperator, b: &MemoryValue) -> bool {
+        match (a, b) {
+            (MemoryValue::Byte(a), MemoryValue::Byte(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::UByte(a), MemoryValue::UByte(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::Short(a), MemoryValue::Short(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::UShort(a), MemoryValue::UShort(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::Int(a), MemoryValue::Int(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::UInt(a), MemoryValue::UInt(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::Long(a), MemoryValue::Long(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::ULong(a), MemoryValue::ULong(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::Float(a), MemoryValue::Float(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::Double(a), MemoryValue::Double(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::Bool(a), MemoryValue::Bool(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::Pointer(a), MemoryValue::Pointer(b)) => Self::compare_number(a, operator, b),
+            (MemoryValue::String(a), MemoryValue::String(b)) => Self::compare_string(a, operator, b),
+            (MemoryValue::Bytes(a), MemoryValue::Bytes(b)) => match operator {
+                FilterOperator::Equal => a == b,
+                FilterOperator::NotEqual => a != b,
+                FilterOperator::Contains => b.is_empty() || a.windows(b.len()).any(|window| window == b.as_slice()),
+                _ => false,
+            },
+            _ => false,
+        }
     }
 
     /// 문자열 비교
@@ -225,67 +269,41 @@ impl FilterParser {
             ));
         }
 
+        // 괄호로 감싼 식
+        if let Some(inner) = Self::strip_parens(expr) {
+            return Self::parse(inner);
+        }
+
         // 기본 조건 파싱
-        // 1. 필드 조건 (field=value, field!=value, field<value, field>value, field:value)
-        let field_regex = Regex::new(r"^([a-zA-Z_][a-zA-Z0-9_]*)\s*([=!<>:][=]?)\s*(.+)$").unwrap();
-        if let Some(caps) = field_regex.captures(expr) {
-            let field_name = caps.get(1).unwrap().as_str().to_string();
+        // 필드 조건 (name:open, size>100)과 메모리 데이터 조건 (float<30.5, uint=42, string:"hello")
+        let condition_regex = Regex::new(r"^([a-zA-Z_][a-zA-Z0-9_]*)\s*(!=|<=|>=|[=<>:])\s*(.+)$").unwrap();
+        if let Some(caps) = condition_regex.captures(expr) {
+            let name = caps.get(1).unwrap().as_str();
             let op_str = caps.get(2).unwrap().as_str();
             let value = caps.get(3).unwrap().as_str().trim();
             
             // 값에 따옴표가 있으면 제거
-            let value = if (value.starts_with('"') && value.ends_with('"')) || 
-                          (value.starts_with('\'') && value.ends_with('\'')) {
+            let value = if value.len() >= 2 && ((value.starts_with('"') && value.ends_with('"')) || 
+                          (value.starts_with('\'') && value.ends_with('\''))) {
                 &value[1..value.len()-1]
             } else {
                 value
             };
 
             let operator = FilterOperator::from_str(op_str)?;
-            
-            return Ok(FilterCondition::Field {
-                field_name,
-                operator,
-                value: value.to_string(),
-            });
-        }
 
-        // 2. 메모리 데이터 조건 (float<30.5, uint=42, string:"hello")
-        let memory_regex = Regex::new(r"^([a-zA-Z]+)\s*([=!<>:][=]?)\s*(.+)$").unwrap();
-        if let Some(caps) = memory_regex.captures(expr) {
-            let type_name = caps.get(1).unwrap().as_str();
-            let op_str = caps.get(2).unwrap().as_str();
-            let value = caps.get(3).unwrap().as_str().trim();
-            
-            // 값에 따옴표가 있으면 제거
-            let value = if (value.starts_with('"') && value.ends_with('"')) || 
-                          (value.starts_with('\'') && value.ends_with('\'')) {
-                &value[1..value.len()-1]
-            } else {
-                value
-            };
-
-            // 메모리 타입 파싱
-            let memory_type = match type_name.to_lowercase().as_str() {
-                "byte" | "int8" => MemoryType::Byte,
-                "ubyte" | "uint8" => MemoryType::UByte,
-                "short" | "int16" => MemoryType::Short,
-                "ushort" | "uint16" => MemoryType::UShort,
-                "int" | "int32" => MemoryType::Int,
-                "uint" | "uint32" => MemoryType::UInt,
-                "long" | "int64" => MemoryType::Long,
-                "ulong" | "uint64" => MemoryType::ULong,
-                "float" => MemoryType::Float,
-                "double" => MemoryType::Double,
-                "bool" => MemoryType::Bool,
-                "pointer" => MemoryType::Pointer,
-                "string" | "utf8" | "ascii" => MemoryType::String,
-                "bytes" | "bytearray" => MemoryType::Bytes,
-                _ => return Err(VlitzError::FilterExpr(format!("Unknown memory type: {}", type_name))),
-            };
+            if FILTER_FIELDS.contains(&name) {
+                return Ok(FilterCondition::Field {
+                    field_name: name.to_string(),
+                    operator,
+                    value: value.to_string(),
+                });
+            }
 
-            let operator = FilterOperator::from_str(op_str)?;
-            
+            // 필드 이름이 아니면 메모리 타입
+            let memory_type = MemoryType::from_name(name).ok_or_else(|| {
+                VlitzError::FilterExpr(format!("Unknown filter field or memory type: {}", name))
+            })?;
             return Ok(FilterCondition::MemoryData {
                 memory_type,
                 operator,
@@ -296,6 +314,21 @@ impl FilterParser {
         Err(VlitzError::FilterExpr(format!("Failed to parse filter expression: {}", expr)))
     }
 
+    /// 식 전체를 감싼 괄호 제거 (`(a|b)` -> `a|b`, `(a)|(b)`는 그대로)
+    fn strip_parens(expr: &str) -> Option<&str> {
+        let inner = expr.trim().strip_prefix('(')?.strip_suffix(')')?;
+        let mut depth = 0;
+        for c in inner.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' if depth == 0 => return None,
+                ')' => depth -= 1,
+                _ => {},
+            }
+        }
+        Some(inner.trim())
+    }
+
     /// 연산자 위치 찾기 (괄호 내부는 무시)
     fn find_operator(expr: &str, op: &str) -> Option<usize> {
         let mut depth = 0;
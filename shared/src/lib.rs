@@ -2,9 +2,12 @@ pub mod vzdata;
 pub mod memory;
 pub mod error;
 pub mod filter;
+pub mod selector;
 pub mod utils;
 
 pub use vzdata::*;
 pub use memory::*;
 pub use error::*;
-pub use filter::*; 
\ No newline at end of file
+pub use filter::*;
+pub use selector::*;
+pub use utils::*; 
\ No newline at end of file
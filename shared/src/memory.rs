@@ -23,6 +23,24 @@ pub enum MemoryType {
 }
 
 impl MemoryType {
+    /// 모든 메모리 타입 (자동완성 등에서 사용)
+    pub const ALL: [MemoryType; 14] = [
+        MemoryType::Byte,
+        MemoryType::UByte,
+        MemoryType::Short,
+        MemoryType::UShort,
+        MemoryType::Int,
+        MemoryType::UInt,
+        MemoryType::Long,
+        MemoryType::ULong,
+        MemoryType::Float,
+        MemoryType::Double,
+        MemoryType::Bool,
+        MemoryType::Pointer,
+        MemoryType::String,
+        MemoryType::Bytes,
+    ];
+
     /// 메모리 타입에 따른 크기(바이트) 반환
     pub fn size(&self) -> usize {
         match self {
@@ -58,6 +76,32 @@ impl MemoryType {
     pub fn is_numeric(&self) -> bool {
         self.is_integer() || self.is_float()
     }
+
+    /// 타입 이름(별칭 포함, 대소문자 무시)에서 메모리 타입 파싱
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "byte" | "int8" => Some(MemoryType::Byte),
+            "ubyte" | "uint8" => Some(MemoryType::UByte),
+            "short" | "int16" => Some(MemoryType::Short),
+            "ushort" | "uint16" => Some(MemoryType::UShort),
+            "int" | "int32" => Some(MemoryType::Int),
+            "uint" | "uint32" => Some(MemoryType::UInt),
+            "long" | "int64" => Some(MemoryType::Long),
+            "ulong" | "uint64" => Some(MemoryType::ULong),
+            "float" => Some(MemoryType::Float),
+            "double" => Some(MemoryType::Double),
+            "bool" => Some(MemoryType::Bool),
+            "pointer" => Some(MemoryType::Pointer),
+            "string" | "utf8" | "ascii" => Some(MemoryType::String),
+            "bytes" | "bytearray" => Some(MemoryType::Bytes),
+            _ => None,
+        }
+    }
+
+    /// 에이전트 스크립트에서 사용하는 타입 이름 (readMemory 등의 type 인자)
+    pub fn agent_name(&self) -> String {
+        self.to_string().to_lowercase()
+    }
 }
 
 /// 메모리 값을 나타내는 열거형
@@ -179,6 +223,30 @@ impl MemoryValue {
             },
         }
     }
+
+    /// 에이전트 스크립트에 전달할 JSON 값으로 변환
+    ///
+    /// 64비트 정수와 포인터는 JS 숫자 정밀도를 넘을 수 있으므로 문자열로 전달한다.
+    pub fn to_agent_value(&self) -> serde_json::Value {
+        use serde_json::json;
+
+        match self {
+            MemoryValue::Byte(v) => json!(v),
+            MemoryValue::UByte(v) => json!(v),
+            MemoryValue::Short(v) => json!(v),
+            MemoryValue::UShort(v) => json!(v),
+            MemoryValue::Int(v) => json!(v),
+            MemoryValue::UInt(v) => json!(v),
+            MemoryValue::Long(v) => json!(v.to_string()),
+            MemoryValue::ULong(v) => json!(v.to_string()),
+            MemoryValue::Float(v) => json!(v),
+            MemoryValue::Double(v) => json!(v),
+            MemoryValue::Bool(v) => json!(v),
+            MemoryValue::Pointer(v) => json!(format!("0x{:x}", v)),
+            MemoryValue::String(v) => json!(v),
+            MemoryValue::Bytes(v) => json!(v),
+        }
+    }
 }
 
 impl fmt::Display for MemoryValue {
@@ -0,0 +1,295 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::error::{VlitzError, VlitzResult};
+use crate::filter::{FilterParser, MemoryReader};
+use crate::VzData;
+
+/// 셀렉터가 가리키는 항목 목록
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectorSource {
+    /// 라이브러리 (lib:3)
+    Lib,
+    /// 로그, 즉 마지막 쿼리 결과 (log:3)
+    Log,
+    /// 내비게이터 항목 (nav:0)
+    Nav,
+}
+
+impl SelectorSource {
+    /// 셀렉터 접두사 이름
+    pub fn name(&self) -> &'static str {
+        match self {
+            SelectorSource::Lib => "lib",
+            SelectorSource::Log => "log",
+            SelectorSource::Nav => "nav",
+        }
+    }
+}
+
+impl fmt::Display for SelectorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for SelectorSource {
+    type Err = VlitzError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lib" => Ok(SelectorSource::Lib),
+            "log" => Ok(SelectorSource::Log),
+            "nav" => Ok(SelectorSource::Nav),
+            _ => Err(VlitzError::Selector(format!("Unknown selector prefix: {}", s))),
+        }
+    }
+}
+
+/// 쉼표로 구분된 셀렉터 항목 하나
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectorTerm {
+    /// 단일 인덱스 (음수는 끝에서부터, -1 = 마지막)
+    Index(i64),
+    /// 범위 (3-6, 6-3은 역순, 끝이 없으면 마지막까지: 5-)
+    Range(i64, Option<i64>),
+    /// 전체 선택
+    All,
+    /// 라벨이 같은 항목 (@name)
+    Label(String),
+    /// 태그가 있는 항목 (#tag)
+    Tag(String),
+    /// 필터 식에 맞는 항목 (lib:name:open)
+    Filter(String),
+}
+
+impl fmt::Display for SelectorTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectorTerm::Index(idx) => write!(f, "{}", idx),
+            SelectorTerm::Range(start, Some(end)) => write!(f, "{}-{}", start, end),
+            SelectorTerm::Range(start, None) => write!(f, "{}-", start),
+            SelectorTerm::All => write!(f, "all"),
+            SelectorTerm::Label(label) => write!(f, "@{}", label),
+            SelectorTerm::Tag(tag) => write!(f, "#{}", tag),
+            SelectorTerm::Filter(expr) => write!(f, "{}", expr),
+        }
+    }
+}
+
+/// 셀렉터 (`3`, `lib:1,4`, `log:5-`, `-1`, `lib:@main`, `#hot`, `lib:type=Function`)
+///
+/// 인덱스는 항상 한 목록(lib, log, nav 중 하나)의 인덱스다. 접두사가 없으면 명령어가 정한 기본 목록을 쓴다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    /// 대상 목록 (None이면 명령어 기본 목록)
+    pub source: Option<SelectorSource>,
+    /// 선택 항목 (순서대로 합치고 중복은 제거)
+    pub terms: Vec<SelectorTerm>,
+}
+
+impl Selector {
+    /// 접두사 없는 단일 인덱스 셀렉터
+    pub fn index(idx: i64) -> Self {
+        Self { source: None, terms: vec![SelectorTerm::Index(idx)] }
+    }
+
+    /// 접두사 없는 전체 선택 셀렉터
+    pub fn all() -> Self {
+        Self { source: None, terms: vec![SelectorTerm::All] }
+    }
+
+    /// 접두사 없는 필터 셀렉터 (`sav name:open`처럼 필터를 셀렉터 대신 받을 때)
+    pub fn filter(expr: &str) -> VlitzResult<Self> {
+        FilterParser::parse(expr.trim())?;
+        Ok(Self { source: None, terms: vec![SelectorTerm::Filter(expr.trim().to_string())] })
+    }
+
+    /// 접두사 없는 전체 선택인지
+    pub fn is_all(&self) -> bool {
+        self.source.is_none() && self.terms == [SelectorTerm::All]
+    }
+
+    /// 항목 하나만 가리키는 단일 인덱스인지
+    pub fn is_single(&self) -> bool {
+        matches!(self.terms.as_slice(), [SelectorTerm::Index(_)])
+    }
+
+    /// 메모리 값을 읽어야 하는 필터가 있는지
+    pub fn reads_memory(&self) -> bool {
+        self.terms.iter().any(|term| match term {
+            SelectorTerm::Filter(expr) => FilterParser::parse(expr).is_ok_and(|filter| filter.reads_memory()),
+            _ => false,
+        })
+    }
+
+    /// 길이가 `len`인 목록의 인덱스 (데이터가 아닌 목록용, 라벨/태그/필터는 오류)
+    pub fn indices(&self, len: usize) -> VlitzResult<Vec<usize>> {
+        self.collect(len, |term| Err(VlitzError::Selector(format!("'{}' can only select data items", term))))
+    }
+
+    /// 데이터 목록에서 셀렉터에 해당하는 인덱스
+    pub fn resolve(&self, items: &[VzData]) -> VlitzResult<Vec<usize>> {
+        self.resolve_with(items, &mut |_, _| None)
+    }
+
+    /// 데이터 목록에서 셀렉터에 해당하는 인덱스 (필터의 메모리 값 조건은 `read`로 읽음)
+    pub fn resolve_with(&self, items: &[VzData], read: &mut MemoryReader) -> VlitzResult<Vec<usize>> {
+        self.collect(items.len(), |term| {
+            let (matched, missing): (Vec<usize>, String) = match term {
+                SelectorTerm::Label(label) => (
+                    matching(items, |data| data.label.as_deref() == Some(label.as_str())),
+                    format!("No items labeled '{}'", label),
+                ),
+                SelectorTerm::Tag(tag) => (
+                    matching(items, |data| data.tags.contains(tag)),
+                    format!("No items tagged '{}'", tag),
+                ),
+                SelectorTerm::Filter(expr) => {
+                    let filter = FilterParser::parse(expr)?;
+                    (matching(items, |data| filter.apply_with(data, read)), format!("No items match '{}'", expr))
+                },
+                _ => unreachable!("index terms are resolved by collect"),
+            };
+            if matched.is_empty() {
+                return Err(VlitzError::Selector(missing));
+            }
+            Ok(matched)
+        })
+    }
+
+    /// 항목별 인덱스를 순서대로 합침 (인덱스/범위/전체는 직접, 그 외는 `select`로)
+    fn collect(&self, len: usize, mut select: impl FnMut(&SelectorTerm) -> VlitzResult<Vec<usize>>) -> VlitzResult<Vec<usize>> {
+        let mut indices = Vec::new();
+        for term in &self.terms {
+            let matched = match term {
+                SelectorTerm::Index(idx) => match position(*idx, len) {
+                    Some(idx) => vec![idx],
+                    None => return Err(VlitzError::Selector(format!("Index {} out of bounds ({} items)", idx, len))),
+                },
+                SelectorTerm::Range(start, end) => range_indices(*start, *end, len),
+                SelectorTerm::All => (0..len).collect(),
+                other => select(other)?,
+            };
+            for idx in matched {
+                if !indices.contains(&idx) {
+                    indices.push(idx);
+                }
+            }
+        }
+
+        if indices.is_empty() {
+            return Err(VlitzError::Selector("No data found for selector".to_string()));
+        }
+        Ok(indices)
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(source) = self.source {
+            write!(f, "{}:", source)?;
+        }
+        let terms: Vec<String> = self.terms.iter().map(|term| term.to_string()).collect();
+        write!(f, "{}", terms.join(","))
+    }
+}
+
+impl FromStr for Selector {
+    type Err = VlitzError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(VlitzError::Selector("Empty selector".to_string()));
+        }
+
+        // lib:, log:, nav: 접두사 (name:open 같은 필터 식은 접두사가 아님)
+        let (source, body) = match s.split_once(':') {
+            Some((prefix, body)) => match prefix.parse::<SelectorSource>() {
+                Ok(source) => (Some(source), body),
+                Err(_) => (None, s),
+            },
+            None => (None, s),
+        };
+        if body.is_empty() {
+            return Err(VlitzError::Selector(format!("Empty selector after '{}'", s)));
+        }
+
+        match body.split(',').map(parse_term).collect::<VlitzResult<Vec<_>>>() {
+            Ok(terms) => Ok(Self { source, terms }),
+            // 접두사 뒤에는 필터 식도 올 수 있음 (lib:type=Function)
+            Err(_) if source.is_some() && FilterParser::parse(body).is_ok() => {
+                Ok(Self { source, terms: vec![SelectorTerm::Filter(body.to_string())] })
+            },
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// 셀렉터 항목 하나 파싱
+fn parse_term(part: &str) -> VlitzResult<SelectorTerm> {
+    if part.eq_ignore_ascii_case("all") {
+        return Ok(SelectorTerm::All);
+    }
+    if let Some(label) = part.strip_prefix('@') {
+        if label.is_empty() {
+            return Err(VlitzError::Selector("Empty label in selector".to_string()));
+        }
+        return Ok(SelectorTerm::Label(label.to_string()));
+    }
+    if let Some(tag) = part.strip_prefix('#') {
+        if tag.is_empty() {
+            return Err(VlitzError::Selector("Empty tag in selector".to_string()));
+        }
+        return Ok(SelectorTerm::Tag(tag.to_string()));
+    }
+    if let Ok(idx) = part.parse::<i64>() {
+        return Ok(SelectorTerm::Index(idx));
+    }
+
+    // 범위 (-3-처럼 시작이 음수일 수 있으므로 첫 글자 뒤의 '-'로 나눔)
+    let Some(dash) = part.char_indices().skip(1).find(|(_, c)| *c == '-').map(|(i, _)| i) else {
+        return Err(VlitzError::Selector(format!("Invalid selector: {}", part)));
+    };
+    let (start, end) = (&part[..dash], &part[dash + 1..]);
+    let start = start.parse::<i64>()
+        .map_err(|_| VlitzError::Selector(format!("Invalid range start: {}", start)))?;
+    let end = match end {
+        "" => None,
+        end => Some(end.parse::<i64>().map_err(|_| VlitzError::Selector(format!("Invalid range end: {}", end)))?),
+    };
+    Ok(SelectorTerm::Range(start, end))
+}
+
+/// 인덱스를 목록 위치로 변환 (음수는 끝에서부터, 범위 밖이면 None)
+fn position(idx: i64, len: usize) -> Option<usize> {
+    let idx = if idx < 0 { len as i64 + idx } else { idx };
+    (0..len as i64).contains(&idx).then_some(idx as usize)
+}
+
+/// 범위에 해당하는 인덱스 (목록 밖 부분은 잘라냄, 시작이 끝보다 크면 역순)
+fn range_indices(start: i64, end: Option<i64>, len: usize) -> Vec<usize> {
+    if len == 0 {
+        return Vec::new();
+    }
+    let last = len as i64 - 1;
+    let clamp = |idx: i64| if idx < 0 { len as i64 + idx } else { idx };
+    let (start, end) = match end {
+        Some(end) => (clamp(start), clamp(end)),
+        // 끝이 없는 범위는 역순이 되지 않음 (9-는 항목이 9개 이하면 빈 범위)
+        None => (clamp(start), last.max(clamp(start))),
+    };
+
+    if start <= end {
+        (start.max(0)..=end.min(last)).map(|idx| idx as usize).collect()
+    } else {
+        (end.max(0)..=start.min(last)).rev().map(|idx| idx as usize).collect()
+    }
+}
+
+/// 조건에 맞는 항목 인덱스
+fn matching(items: &[VzData], mut predicate: impl FnMut(&VzData) -> bool) -> Vec<usize> {
+    items.iter().enumerate()
+        .filter(|(_, data)| predicate(data))
+        .map(|(idx, _)| idx)
+        .collect()
+}
@@ -14,6 +14,21 @@ pub enum VzDataType {
     Variable,
 }
 
+impl VzDataType {
+    /// 타입별 내용 필드 이름 (표 출력의 열 순서)
+    pub fn fields(&self) -> &'static [&'static str] {
+        match self {
+            VzDataType::Pointer => &["address", "memory_type", "size"],
+            VzDataType::Function => &["name", "address"],
+            VzDataType::Method => &["class_name", "name", "args", "ret"],
+            VzDataType::Module => &["name", "address", "size"],
+            VzDataType::Class => &["name"],
+            VzDataType::Range => &["address", "size", "protection", "file"],
+            VzDataType::Variable => &["name", "address"],
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VzData {
     // 공통 필드
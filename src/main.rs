@@ -1,7 +1,13 @@
+use std::io::BufRead;
+use std::path::Path;
+use std::process::ExitCode;
 use anyhow::{Result, anyhow};
+use frida_rust::Device;
 use vlitz_cli::{Cli, Repl};
+use vlitz_runtime::{FridaManager, VlitzSession};
+use vlitz_script::OnError;
 
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
     // CLI 인자 파싱
     let cli = Cli::new();
     
@@ -9,15 +15,88 @@ fn main() -> Result<()> {
     if cli.is_command() {
         // 나중에 구현
         println!("서브 명령어는 아직 구현되지 않았습니다.");
-        return Ok(());
+        return Ok(ExitCode::SUCCESS);
     }
     
-    // 대상 프로세스 확인
-    let _target = cli.get_target();
+    let mut repl = Repl::new(20)?;
+    repl.set_output(cli.output_format());
+    let batch = cli.is_batch();
+    if batch {
+        repl.set_batch();
+    }
+    let target = cli.get_target();
+    
+    // 대상 프로세스에 연결
+    if let Some(target) = &target {
+        let session = attach(&cli, target)?;
+        repl.set_backend(Box::new(session));
+    }
+    
+    // 대상별 입력 히스토리 (배치 모드 입력은 남기지 않음)
+    if !batch {
+        repl.load_history(target.as_deref());
+    }
+    repl.load_bookmarks(target.as_deref());
+    
+    // -l/--load 스크립트 실행 (exit로 끝나면 REPL을 열지 않음)
+    if let Some(script) = &cli.load_script {
+        let on_error = if cli.continue_on_error { OnError::Continue } else { OnError::Stop };
+        if repl.run_script(Path::new(script), on_error) {
+            return Ok(if batch && repl.has_failed() { ExitCode::FAILURE } else { ExitCode::SUCCESS });
+        }
+    }
+    
+    // 배치 모드: -e 명령어 또는 표준 입력을 실행하고 실패가 있으면 1로 종료
+    if batch {
+        let succeeded = if cli.exec.is_empty() {
+            repl.run_batch(std::io::stdin().lock().lines().map_while(|line| line.ok()))
+        } else {
+            repl.run_batch(cli.exec.iter().cloned())
+        };
+        return Ok(if succeeded { ExitCode::SUCCESS } else { ExitCode::FAILURE });
+    }
     
     // 기본 REPL 실행
-    let mut repl = Repl::new(20)?;
     repl.run()?;
     
-    Ok(())
-} 
\ No newline at end of file
+    Ok(ExitCode::SUCCESS)
+}
+
+/// 옵션에 맞는 디바이스 선택 (기본은 로컬)
+fn select_device(cli: &Cli) -> Result<Device> {
+    let device = if let Some(id) = &cli.device_id {
+        FridaManager::get_device_by_id(id)?
+    } else if cli.usb {
+        FridaManager::get_usb_device()?
+    } else if cli.remote {
+        FridaManager::get_remote_device(cli.host.as_deref())?
+    } else {
+        FridaManager::get_local_device()?
+    };
+    
+    Ok(device)
+}
+
+/// 대상 프로세스에 attach 후 기본 에이전트 스크립트 로드
+fn attach(cli: &Cli, target: &str) -> Result<VlitzSession> {
+    let device = select_device(cli)?;
+    
+    // 스폰한 프로세스는 에이전트 로드 후 재개
+    if let Some(program) = &cli.spawn_target {
+        let (pid, mut session) = FridaManager::spawn_and_attach(&device, program, None)?;
+        session.load_base_script()?;
+        FridaManager::resume(&device, pid)?;
+        return Ok(session);
+    }
+    
+    let pid = match cli.attach_pid.or_else(|| target.parse().ok()) {
+        Some(pid) => pid,
+        None => FridaManager::find_process_by_name(&device, target)?
+            .ok_or_else(|| anyhow!("Process not found: {}", target))?
+            .pid(),
+    };
+    
+    let mut session = FridaManager::attach(&device, pid)?;
+    session.load_base_script()?;
+    Ok(session)
+}